@@ -0,0 +1,177 @@
+use crate::data::Candle;
+use crate::ui::indicators::{calculate_sma, crossovers, Cross};
+use std::fmt;
+
+pub const FAST_PERIOD: usize = 20;
+pub const SLOW_PERIOD: usize = 50;
+
+/// Summary of a long-only moving-average crossover run: buy the close of a
+/// golden cross, sell the close of the next death cross. A position still
+/// open at the end is closed at the last close.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BacktestReport {
+    pub candles: usize,
+    pub fast_period: usize,
+    pub slow_period: usize,
+    pub trades: usize,
+    pub wins: usize,
+    /// Compounded return of every trade, as a fraction.
+    pub total_return: f64,
+    /// Largest peak-to-trough fall of the marked-to-market equity curve, as
+    /// a fraction.
+    pub max_drawdown: f64,
+    /// The last trade was still open and closed at the final candle.
+    pub open_at_end: bool,
+}
+
+impl BacktestReport {
+    pub fn win_rate(&self) -> Option<f64> {
+        (self.trades > 0).then(|| self.wins as f64 / self.trades as f64)
+    }
+}
+
+/// `candles` without the still-forming ones at the end, so a run only sees
+/// finished bars and doesn't change until the next one closes.
+pub fn closed_candles(candles: &[Candle]) -> &[Candle] {
+    let end = candles
+        .iter()
+        .rposition(|candle| candle.is_closed)
+        .map_or(0, |idx| idx + 1);
+    &candles[..end]
+}
+
+pub fn run_crossover(candles: &[Candle], fast_period: usize, slow_period: usize) -> BacktestReport {
+    let closes: Vec<Option<f64>> = candles
+        .iter()
+        .map(|c| c.close.parse::<f64>().ok().filter(|close| *close > 0.0))
+        .collect();
+    let crosses = crossovers(
+        &calculate_sma(candles, fast_period),
+        &calculate_sma(candles, slow_period),
+    );
+
+    let mut report = BacktestReport {
+        candles: candles.len(),
+        fast_period,
+        slow_period,
+        trades: 0,
+        wins: 0,
+        total_return: 0.0,
+        max_drawdown: 0.0,
+        open_at_end: false,
+    };
+    let mut realized = 1.0;
+    let mut peak: f64 = 1.0;
+    let mut entry: Option<f64> = None;
+    let mut last_close = None;
+
+    for (close, cross) in closes.iter().zip(&crosses) {
+        let Some(close) = *close else {
+            continue;
+        };
+        last_close = Some(close);
+        match (cross, entry) {
+            (Some(Cross::Golden), None) => entry = Some(close),
+            (Some(Cross::Death), Some(price)) => {
+                realized = close_trade(&mut report, realized, price, close);
+                entry = None;
+            }
+            _ => {}
+        }
+        let equity = entry.map_or(realized, |price| realized * close / price);
+        peak = peak.max(equity);
+        report.max_drawdown = report.max_drawdown.max((peak - equity) / peak);
+    }
+
+    if let (Some(price), Some(close)) = (entry, last_close) {
+        realized = close_trade(&mut report, realized, price, close);
+        report.open_at_end = true;
+    }
+    report.total_return = realized - 1.0;
+    report
+}
+
+fn close_trade(report: &mut BacktestReport, realized: f64, entry: f64, exit: f64) -> f64 {
+    report.trades += 1;
+    if exit > entry {
+        report.wins += 1;
+    }
+    realized * exit / entry
+}
+
+impl fmt::Display for BacktestReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "SMA{}/SMA{} crossover over {} candles",
+            self.fast_period, self.slow_period, self.candles
+        )?;
+        write!(f, "Trades:        {}", self.trades)?;
+        if self.open_at_end {
+            write!(f, " (last closed at the final candle)")?;
+        }
+        writeln!(f)?;
+        match self.win_rate() {
+            Some(rate) => writeln!(f, "Win rate:      {:.1}%", rate * 100.0)?,
+            None => writeln!(f, "Win rate:      n/a")?,
+        }
+        writeln!(f, "Total return:  {:+.2}%", self.total_return * 100.0)?;
+        write!(f, "Max drawdown:  {:.2}%", self.max_drawdown * 100.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candles(closes: &[f64]) -> Vec<Candle> {
+        closes
+            .iter()
+            .enumerate()
+            .map(|(i, &close)| Candle {
+                is_closed: true,
+                ..crate::data::fetch::candle(i as u64 * 60_000, close, close, close, close)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn forming_candles_at_the_end_are_left_out() {
+        let mut series = candles(&[10.0, 11.0, 12.0]);
+        assert_eq!(closed_candles(&series).len(), 3);
+        series[2].is_closed = false;
+        assert_eq!(closed_candles(&series).len(), 2);
+        series
+            .iter_mut()
+            .for_each(|candle| candle.is_closed = false);
+        assert!(closed_candles(&series).is_empty());
+    }
+
+    #[test]
+    fn trades_each_crossover_pair() {
+        // SMA1 vs SMA2: up at 2 (golden, buy 12), down at 4 (death, sell 9),
+        // up at 6 (buy 11), still open at the end (sell 12).
+        let series = candles(&[10.0, 10.0, 12.0, 13.0, 9.0, 9.0, 11.0, 12.0]);
+        let report = run_crossover(&series, 1, 2);
+
+        assert_eq!(report.trades, 2);
+        assert_eq!(report.wins, 1);
+        assert!(report.open_at_end);
+        assert_eq!(report.win_rate(), Some(0.5));
+        let expected = 9.0 / 12.0 * (12.0 / 11.0) - 1.0;
+        assert!((report.total_return - expected).abs() < 1e-12);
+        // From 13/12 at the high to 9/12 after the first exit.
+        assert!((report.max_drawdown - 4.0 / 13.0).abs() < 1e-12);
+        assert_eq!(run_crossover(&series, 1, 2), report);
+    }
+
+    #[test]
+    fn no_crosses_means_no_trades() {
+        let report = run_crossover(&candles(&[10.0, 11.0, 12.0]), 20, 50);
+        assert_eq!(report.trades, 0);
+        assert_eq!(report.win_rate(), None);
+        assert_eq!(report.total_return, 0.0);
+        assert_eq!(report.max_drawdown, 0.0);
+        assert!(report.to_string().contains("Win rate:      n/a"));
+    }
+}
@@ -0,0 +1,107 @@
+use crate::ui::theme::Theme;
+use std::io::{self, Write};
+
+/// How a copy reached the user's clipboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyMethod {
+    #[cfg_attr(not(feature = "clipboard"), allow(dead_code))]
+    System,
+    Osc52,
+}
+
+/// Copies `text` to the system clipboard when built with the `clipboard`
+/// feature, falling back to an OSC 52 escape so terminals (including over
+/// SSH) can set the clipboard themselves.
+pub fn copy_text(text: &str) -> io::Result<CopyMethod> {
+    #[cfg(feature = "clipboard")]
+    if arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.set_text(text.to_string()))
+        .is_ok()
+    {
+        return Ok(CopyMethod::System);
+    }
+
+    let mut stdout = io::stdout();
+    stdout.write_all(osc52_sequence(text).as_bytes())?;
+    stdout.flush()?;
+    Ok(CopyMethod::Osc52)
+}
+
+/// `BTCUSDT 69,420.50`, or just the symbol when there's no price yet.
+pub fn format_copy_text(symbol: &str, price: Option<f64>, theme: &Theme) -> String {
+    match price.filter(|p| p.is_finite()) {
+        Some(price) => format!("{} {}", symbol, theme.format_price(price)),
+        None => symbol.to_string(),
+    }
+}
+
+fn osc52_sequence(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", base64_encode(text.as_bytes()))
+}
+
+fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for (i, shift) in [18, 12, 6, 0].into_iter().enumerate() {
+            if i <= chunk.len() {
+                out.push(ALPHABET[((n >> shift) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn copy_text_groups_thousands() {
+        let theme = Theme::default();
+        assert_eq!(
+            format_copy_text("BTCUSDT", Some(69420.5), &theme),
+            "BTCUSDT 69,420.50"
+        );
+        assert_eq!(
+            format_copy_text("ADAUSDT", Some(0.4512), &theme),
+            "ADAUSDT 0.4512"
+        );
+        assert_eq!(
+            format_copy_text("SHIBUSDT", Some(0.00002431), &theme),
+            "SHIBUSDT 0.00002431"
+        );
+        assert_eq!(
+            format_copy_text("BTCUSDT", Some(1234567.0), &theme),
+            "BTCUSDT 1,234,567.00"
+        );
+        assert_eq!(format_copy_text("BTCUSDT", None, &theme), "BTCUSDT");
+
+        // A known precision wins over the small-price heuristic.
+        let theme = Theme {
+            price_precision: Some(4),
+            ..Theme::default()
+        };
+        assert_eq!(
+            format_copy_text("ADAUSDT", Some(1.2345), &theme),
+            "ADAUSDT 1.2345"
+        );
+    }
+
+    #[test]
+    fn osc52_wraps_base64_payload() {
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(osc52_sequence("hi"), "\x1b]52;c;aGk=\x07");
+    }
+}
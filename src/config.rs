@@ -1,5 +1,29 @@
-use crate::ui::Timeframe;
+use crate::data::prices::{ChangeBaseline, PriceSource, DEFAULT_MAX_STREAMS};
+use crate::data::trades::TradeStreamKind;
+use crate::ui::chart::{
+    CandleEmphasis, CandleStyle, LiveCandleStyle, PriceAxis, PriceHud, DEFAULT_CANDLE_GAP,
+    DEFAULT_CANDLE_WIDTH, DEFAULT_RECENT_CANDLES, ZOOM_PRESETS,
+};
+use crate::ui::events::DEFAULT_EVENT_LOG_SIZE;
+use crate::ui::format::{VolumeFormat, DEFAULT_MAX_PRICE_DECIMALS};
+use crate::ui::grid::{GridLineStyle, GridMode, DEFAULT_GRID_SPACING};
+use crate::ui::indicators::{
+    IndicatorBasis, IndicatorConfig, IndicatorPrefs, SymbolIndicators, DEFAULT_ROC_PERIOD,
+};
+use crate::ui::liquidity::DEFAULT_WIDE_SPREAD_PCT;
+use crate::ui::notes::SymbolNotes;
+use crate::ui::orderbook::BookViewMode;
+use crate::ui::overlay::LineStyles;
+use crate::ui::preset::{LayoutPreset, PanelLayout};
+use crate::ui::quiet::QuietHours;
+use crate::ui::timeaxis::CandleTimeBasis;
+use crate::ui::tradetape::{TapeDirection, TapeFormat, DEFAULT_TAPE_FORMAT};
+use crate::ui::watchlist::{
+    flatten_sections, sort_by_volume, top_mover, WatchlistOrder, WatchlistSection,
+};
+use crate::ui::{Palette, Timeframe};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -9,6 +33,206 @@ pub struct AppConfig {
     pub symbol: String,
     pub timeframe: Timeframe,
     pub zoom: usize,
+    /// Candle count last picked with `*`; `None` for the regular zoom.
+    #[serde(default)]
+    pub zoom_preset: Option<usize>,
+    #[serde(default = "default_max_fps")]
+    pub max_fps: u32,
+    /// Minimum milliseconds between chart redraws caused by streamed candles;
+    /// 0 redraws on every update.
+    #[serde(default)]
+    pub chart_refresh_ms: u64,
+    /// Seconds without input before the UI slows down and stops consuming
+    /// trades; 0 never idles.
+    #[serde(default)]
+    pub idle_after_secs: u64,
+    #[serde(default = "default_flash_updates")]
+    pub flash_updates: bool,
+    #[serde(default)]
+    pub half_block: bool,
+    /// Draws the chart as a braille close-price line; needs a font with
+    /// braille glyphs.
+    #[serde(default)]
+    pub braille: bool,
+    #[serde(default)]
+    pub palette: Palette,
+    #[serde(default)]
+    pub trade_stream: TradeStreamKind,
+    #[serde(default)]
+    pub price_source: PriceSource,
+    /// What the watchlist's percent change is measured from.
+    #[serde(default)]
+    pub change_baseline: ChangeBaseline,
+    /// Watchlist streams per WebSocket connection.
+    #[serde(default = "default_max_streams_per_connection")]
+    pub max_streams_per_connection: usize,
+    /// Most queued trades handled in one pass; older ones beyond it are
+    /// skipped so the tape can't fall ever further behind.
+    #[serde(default = "default_max_trades_per_drain")]
+    pub max_trades_per_drain: usize,
+    #[serde(default)]
+    pub candle_emphasis: CandleEmphasis,
+    /// How the still-forming newest candle is marked.
+    #[serde(default)]
+    pub live_candle: LiveCandleStyle,
+    #[serde(default)]
+    pub candle_style: CandleStyle,
+    /// Preferred candle body width and gap, in columns; they set how many
+    /// candles are shown.
+    #[serde(default = "default_candle_width")]
+    pub candle_width: u16,
+    #[serde(default = "default_candle_gap")]
+    pub candle_gap: u16,
+    /// Trade tape row layout; see [`TapeFormat`] for the placeholders.
+    #[serde(default = "default_tape_format")]
+    pub tape_format: String,
+    /// How tape rows are judged buys or sells.
+    #[serde(default)]
+    pub tape_direction: TapeDirection,
+    #[serde(default = "default_show_mtf")]
+    pub show_mtf: bool,
+    #[serde(default)]
+    pub show_relative: bool,
+    /// Rate-of-change strip under the volume bars.
+    #[serde(default)]
+    pub show_roc: bool,
+    #[serde(default = "default_roc_period")]
+    pub roc_period: usize,
+    #[serde(default)]
+    pub notes: SymbolNotes,
+    /// Overlays and periods per symbol.
+    #[serde(default)]
+    pub indicators: SymbolIndicators,
+    /// Overlays that are on for symbols without an `indicators` entry.
+    #[serde(default)]
+    pub active_indicators: Vec<IndicatorConfig>,
+    /// How each overlay's points are joined.
+    #[serde(default)]
+    pub line_styles: LineStyles,
+    #[serde(default)]
+    pub invert_y: bool,
+    #[serde(default)]
+    pub price_axis: PriceAxis,
+    /// Plot corner for the large price readout; off by default.
+    #[serde(default)]
+    pub price_hud: PriceHud,
+    /// Label the price axis at round prices instead of evenly spaced rows.
+    #[serde(default = "default_round_price_labels")]
+    pub round_price_labels: bool,
+    /// Round the crosshair price readout to the symbol's tick size.
+    #[serde(default = "default_snap_crosshair")]
+    pub snap_crosshair: bool,
+    /// Without the exchange's tick size (offline, replay), read the price
+    /// precision off the candles instead of guessing from magnitude.
+    #[serde(default = "default_infer_price_precision")]
+    pub infer_price_precision: bool,
+    /// Order applied to the watchlist once at startup.
+    #[serde(default)]
+    pub watchlist_order: WatchlistOrder,
+    /// Open on the watchlist symbol with the largest 24h move instead of
+    /// the saved one.
+    #[serde(default)]
+    pub start_on_top_mover: bool,
+    /// Optional named groups; when present the flat `watchlist` is derived
+    /// from them.
+    #[serde(default)]
+    pub watchlist_sections: Vec<WatchlistSection>,
+    #[serde(default = "default_chart_padding")]
+    pub chart_padding: f64,
+    #[serde(default)]
+    pub show_ribbon: bool,
+    #[serde(default)]
+    pub show_returns: bool,
+    #[serde(default)]
+    pub show_events: bool,
+    /// Connection events kept for the events panel.
+    #[serde(default = "default_event_log_size")]
+    pub event_log_size: usize,
+    #[serde(default)]
+    pub book_view: BookViewMode,
+    /// Start the order book's levels at the last traded price.
+    #[serde(default)]
+    pub book_follow_trades: bool,
+    /// Average spread, in percent of price, that flags a thin book.
+    #[serde(default = "default_wide_spread_pct")]
+    pub wide_spread_pct: f64,
+    /// Panel arrangement applied at startup.
+    #[serde(default)]
+    pub layout_preset: LayoutPreset,
+    /// Used when `layout_preset` is `Custom`.
+    #[serde(default)]
+    pub custom_layout: PanelLayout,
+    #[serde(default)]
+    pub show_heatmap: bool,
+    /// Shade Bollinger/Keltner squeezes on the chart and mark their release.
+    #[serde(default)]
+    pub show_squeeze: bool,
+    #[serde(default)]
+    pub volume_format: VolumeFormat,
+    /// Drop panel borders except the titled top edge.
+    #[serde(default)]
+    pub dense: bool,
+    /// Most decimals shown for prices below 1, which otherwise get enough to
+    /// keep four significant digits.
+    #[serde(default = "default_max_price_decimals")]
+    pub max_price_decimals: usize,
+    #[serde(default)]
+    pub activity_tint: bool,
+    /// Fade candles older than the newest `recent_candles` toward gray.
+    #[serde(default)]
+    pub highlight_recent: bool,
+    #[serde(default = "default_recent_candles")]
+    pub recent_candles: usize,
+    /// Background gridlines under the candles.
+    #[serde(default)]
+    pub grid: GridMode,
+    #[serde(default)]
+    pub grid_style: GridLineStyle,
+    /// Rows between horizontal gridlines; vertical ones are twice as far
+    /// apart.
+    #[serde(default = "default_grid_spacing")]
+    pub grid_spacing: u16,
+    /// Compute the range readout and returns panel on log prices or log
+    /// returns instead of raw closes.
+    #[serde(default)]
+    pub indicator_basis: IndicatorBasis,
+    /// Label candles by their open or close time.
+    #[serde(default)]
+    pub candle_time_basis: CandleTimeBasis,
+    /// Open each newly loaded symbol zoomed out to its whole fetched history.
+    #[serde(default)]
+    pub fit_on_load: bool,
+    /// Scroll back to the live edge over a few frames instead of snapping.
+    #[serde(default = "default_smooth_catch_up")]
+    pub smooth_catch_up: bool,
+    /// Switch to a longer kline interval, instead of only hinting at one,
+    /// when zooming out merges more than `max_candles_per_column`.
+    #[serde(default)]
+    pub auto_timeframe: bool,
+    #[serde(default = "default_max_candles_per_column")]
+    pub max_candles_per_column: usize,
+    /// Key that blanks the screen until pressed again.
+    #[serde(default = "default_hide_key")]
+    pub hide_key: char,
+    /// Daily UTC window during which the whole UI is dimmed.
+    #[serde(default)]
+    pub quiet_hours: Option<QuietHours>,
+    /// Consecutive failed connects before a stream gives up; 0 retries forever.
+    #[serde(default = "default_max_reconnect_attempts")]
+    pub max_reconnect_attempts: u32,
+    /// Hours between refreshes of the exchange's symbol list; 0 loads it
+    /// only at startup.
+    #[serde(default = "default_symbols_refresh_hours")]
+    pub symbols_refresh_hours: u64,
+    /// Ping Binance and check the clock before opening the UI.
+    #[serde(default = "default_preflight")]
+    pub preflight: bool,
+    /// Show startup progress until the market streams deliver.
+    #[serde(default = "default_startup_splash")]
+    pub startup_splash: bool,
+    /// Ask before `q` or Esc quits.
+    #[serde(default)]
+    pub confirm_quit: bool,
 }
 
 impl Default for AppConfig {
@@ -20,12 +244,115 @@ impl Default for AppConfig {
             selected_symbol: 0,
             timeframe: Timeframe::OneMonth,
             zoom: 1,
+            zoom_preset: None,
+            max_fps: default_max_fps(),
+            chart_refresh_ms: 0,
+            flash_updates: default_flash_updates(),
+            half_block: false,
+            braille: false,
+            palette: Palette::default(),
+            trade_stream: TradeStreamKind::default(),
+            price_source: PriceSource::default(),
+            change_baseline: ChangeBaseline::default(),
+            max_streams_per_connection: default_max_streams_per_connection(),
+            max_trades_per_drain: default_max_trades_per_drain(),
+            candle_emphasis: CandleEmphasis::default(),
+            live_candle: LiveCandleStyle::default(),
+            candle_style: CandleStyle::default(),
+            candle_width: default_candle_width(),
+            candle_gap: default_candle_gap(),
+            tape_format: default_tape_format(),
+            tape_direction: TapeDirection::default(),
+            show_mtf: default_show_mtf(),
+            show_relative: false,
+            show_roc: false,
+            roc_period: DEFAULT_ROC_PERIOD,
+            notes: SymbolNotes::new(),
+            indicators: SymbolIndicators::new(),
+            active_indicators: Vec::new(),
+            line_styles: LineStyles::default(),
+            invert_y: false,
+            price_axis: PriceAxis::default(),
+            price_hud: PriceHud::default(),
+            round_price_labels: default_round_price_labels(),
+            snap_crosshair: default_snap_crosshair(),
+            infer_price_precision: default_infer_price_precision(),
+            watchlist_sections: Vec::new(),
+            watchlist_order: WatchlistOrder::default(),
+            start_on_top_mover: false,
+            chart_padding: default_chart_padding(),
+            show_ribbon: false,
+            show_returns: false,
+            show_events: false,
+            event_log_size: default_event_log_size(),
+            book_view: BookViewMode::default(),
+            book_follow_trades: false,
+            wide_spread_pct: default_wide_spread_pct(),
+            layout_preset: LayoutPreset::default(),
+            custom_layout: PanelLayout::default(),
+            show_heatmap: false,
+            show_squeeze: false,
+            volume_format: VolumeFormat::default(),
+            dense: false,
+            max_price_decimals: default_max_price_decimals(),
+            activity_tint: false,
+            highlight_recent: false,
+            recent_candles: default_recent_candles(),
+            grid: GridMode::default(),
+            grid_style: GridLineStyle::default(),
+            grid_spacing: default_grid_spacing(),
+            indicator_basis: IndicatorBasis::default(),
+            candle_time_basis: CandleTimeBasis::default(),
+            fit_on_load: false,
+            smooth_catch_up: default_smooth_catch_up(),
+            auto_timeframe: false,
+            max_candles_per_column: default_max_candles_per_column(),
+            hide_key: default_hide_key(),
+            quiet_hours: None,
+            idle_after_secs: 0,
+            max_reconnect_attempts: default_max_reconnect_attempts(),
+            symbols_refresh_hours: default_symbols_refresh_hours(),
+            preflight: default_preflight(),
+            startup_splash: default_startup_splash(),
+            confirm_quit: false,
         }
     }
 }
 
 impl AppConfig {
+    /// Reorders the watchlist, and each section, busiest first. The selection
+    /// stays on the same symbol.
+    pub fn sort_watchlist_by_volume(&mut self, volumes: &HashMap<String, f64>) {
+        let selected = self.watchlist.get(self.selected_symbol).cloned();
+        if self.watchlist_sections.is_empty() {
+            sort_by_volume(&mut self.watchlist, volumes);
+        } else {
+            for section in &mut self.watchlist_sections {
+                sort_by_volume(&mut section.symbols, volumes);
+            }
+            self.watchlist = flatten_sections(&self.watchlist_sections);
+        }
+        if let Some(position) = selected.and_then(|s| self.watchlist.iter().position(|w| *w == s)) {
+            self.selected_symbol = position;
+        }
+    }
+
+    /// Selects and charts the watchlist symbol with the largest absolute
+    /// change; leaves the saved symbol when no change is known.
+    pub fn select_top_mover(&mut self, changes: &HashMap<String, f64>) {
+        if let Some(idx) = top_mover(&self.watchlist, changes) {
+            self.selected_symbol = idx;
+            self.symbol = self.watchlist[idx].clone();
+        }
+    }
+
     pub fn sanitized(mut self) -> Self {
+        self.watchlist_sections
+            .retain(|section| !section.symbols.is_empty());
+        if !self.watchlist_sections.is_empty() {
+            self.watchlist = flatten_sections(&self.watchlist_sections);
+        }
+
         if self.watchlist.is_empty() {
             self.watchlist = default_watchlist();
         }
@@ -38,11 +365,64 @@ impl AppConfig {
             self.symbol = self.watchlist[self.selected_symbol].clone();
         }
 
+        let watchlist = &self.watchlist;
+        self.indicators
+            .retain(|symbol, _| watchlist.iter().any(|s| s == symbol));
+        for prefs in self.indicators.values_mut() {
+            *prefs = prefs.sanitized();
+        }
+        self.active_indicators = IndicatorPrefs::from_active(&self.active_indicators)
+            .sanitized()
+            .active();
+        self.quiet_hours = self.quiet_hours.filter(QuietHours::is_valid);
+        if TapeFormat::parse(&self.tape_format).is_err() {
+            self.tape_format = default_tape_format();
+        }
+        if !self.hide_key.is_ascii_graphic()
+            || BOUND_KEYS.contains(self.hide_key.to_ascii_lowercase())
+            || BOUND_KEYS.contains(self.hide_key.to_ascii_uppercase())
+        {
+            self.hide_key = default_hide_key();
+        }
+        self.custom_layout = self.custom_layout.sanitized();
+        self.max_streams_per_connection = self.max_streams_per_connection.clamp(1, 1024);
+        self.max_trades_per_drain = self.max_trades_per_drain.clamp(10, 100_000);
+        self.max_candles_per_column = self.max_candles_per_column.clamp(2, 64);
         self.zoom = self.zoom.clamp(1, 32);
+        self.zoom_preset = self
+            .zoom_preset
+            .filter(|count| ZOOM_PRESETS.contains(count));
+        self.roc_period = self.roc_period.clamp(1, 200);
+        self.candle_width = self.candle_width.clamp(1, 15);
+        self.candle_gap = self.candle_gap.min(8);
+        self.recent_candles = self.recent_candles.clamp(1, 1000);
+        self.event_log_size = self.event_log_size.clamp(10, 1000);
+        self.grid_spacing = self.grid_spacing.clamp(2, 20);
+        self.max_fps = self.max_fps.clamp(1, 120);
+        self.chart_refresh_ms = self.chart_refresh_ms.min(MAX_CHART_REFRESH_MS);
+        self.idle_after_secs = self.idle_after_secs.min(86_400);
+        self.symbols_refresh_hours = self.symbols_refresh_hours.min(168);
+        self.max_price_decimals = self.max_price_decimals.clamp(2, 12);
+        self.wide_spread_pct = if self.wide_spread_pct.is_finite() {
+            self.wide_spread_pct.clamp(0.01, 10.0)
+        } else {
+            default_wide_spread_pct()
+        };
+        self.chart_padding = if self.chart_padding.is_finite() {
+            self.chart_padding.clamp(0.0, 5.0)
+        } else {
+            default_chart_padding()
+        };
         self
     }
 }
 
+const MAX_CHART_REFRESH_MS: u64 = 60_000;
+
+/// Characters the main key dispatch already binds. The hide key is matched
+/// ahead of them and ignoring case, so it can't be any of these.
+pub const BOUND_KEYS: &str = "#$%*+-/=?@_|abcdefghijklmnoqrstuvwxyzABCDEFGIJKLMNORSTUVWXYZ";
+
 pub fn default_watchlist() -> Vec<String> {
     vec![
         "BTCUSDT".to_string(),
@@ -53,6 +433,106 @@ pub fn default_watchlist() -> Vec<String> {
     ]
 }
 
+pub fn default_max_price_decimals() -> usize {
+    DEFAULT_MAX_PRICE_DECIMALS
+}
+
+pub fn default_max_fps() -> u32 {
+    30
+}
+
+pub fn default_max_reconnect_attempts() -> u32 {
+    8
+}
+
+pub fn default_symbols_refresh_hours() -> u64 {
+    6
+}
+
+pub fn default_max_trades_per_drain() -> usize {
+    500
+}
+
+pub fn default_max_streams_per_connection() -> usize {
+    DEFAULT_MAX_STREAMS
+}
+
+pub fn default_round_price_labels() -> bool {
+    true
+}
+
+pub fn default_recent_candles() -> usize {
+    DEFAULT_RECENT_CANDLES
+}
+
+pub fn default_grid_spacing() -> u16 {
+    DEFAULT_GRID_SPACING
+}
+
+pub fn default_event_log_size() -> usize {
+    DEFAULT_EVENT_LOG_SIZE
+}
+
+pub fn default_candle_width() -> u16 {
+    DEFAULT_CANDLE_WIDTH
+}
+
+pub fn default_candle_gap() -> u16 {
+    DEFAULT_CANDLE_GAP
+}
+
+pub fn default_snap_crosshair() -> bool {
+    true
+}
+
+pub fn default_infer_price_precision() -> bool {
+    true
+}
+
+pub fn default_preflight() -> bool {
+    true
+}
+
+pub fn default_startup_splash() -> bool {
+    true
+}
+
+pub fn default_tape_format() -> String {
+    DEFAULT_TAPE_FORMAT.to_string()
+}
+
+pub fn default_flash_updates() -> bool {
+    true
+}
+
+pub fn default_smooth_catch_up() -> bool {
+    true
+}
+
+pub fn default_max_candles_per_column() -> usize {
+    3
+}
+
+pub fn default_hide_key() -> char {
+    'p'
+}
+
+pub fn default_roc_period() -> usize {
+    DEFAULT_ROC_PERIOD
+}
+
+pub fn default_show_mtf() -> bool {
+    true
+}
+
+pub fn default_wide_spread_pct() -> f64 {
+    DEFAULT_WIDE_SPREAD_PCT
+}
+
+pub fn default_chart_padding() -> f64 {
+    crate::ui::chart::DEFAULT_PADDING_FACTOR
+}
+
 pub fn config_path() -> PathBuf {
     std::env::current_dir()
         .unwrap_or_else(|_| PathBuf::from("."))
@@ -0,0 +1,198 @@
+use crate::data::Candle;
+use std::collections::VecDeque;
+
+/// Merges `incoming` into `existing`, which stays sorted by `open_time` with
+/// one candle per time. A candle for a time already present replaces it
+/// unless that one is closed and the newcomer is still in progress, so a
+/// late or replayed live update can't undo a finalized candle. Between two
+/// in-progress copies the one with fewer trades is older, so a REST snapshot
+/// taken before the latest live tick doesn't roll it back. Duplicates within
+/// `incoming` are applied in order under the same rule.
+///
+/// Returns whether `existing` changed.
+pub fn merge_candles(
+    existing: &mut VecDeque<Candle>,
+    incoming: impl IntoIterator<Item = Candle>,
+) -> bool {
+    let mut changed = false;
+    for candle in incoming {
+        // Live updates land at the back, so check there before searching.
+        let slot = match existing.back() {
+            Some(last) if last.open_time < candle.open_time => Err(existing.len()),
+            None => Err(0),
+            _ => existing.binary_search_by_key(&candle.open_time, |c| c.open_time),
+        };
+        match slot {
+            Ok(idx) if existing[idx].is_closed && !candle.is_closed => {}
+            Ok(idx)
+                if !candle.is_closed
+                    && candle.number_of_trades < existing[idx].number_of_trades => {}
+            Ok(idx) => {
+                if existing[idx] != candle {
+                    existing[idx] = candle;
+                    changed = true;
+                }
+            }
+            Err(idx) => {
+                existing.insert(idx, candle);
+                changed = true;
+            }
+        }
+    }
+    changed
+}
+
+/// Whether a candle's prices make sense: OHLC all parse to finite positive
+/// values, the high is the highest and the low the lowest of them, and the
+/// volume isn't negative. Anything else would scale the chart around garbage.
+pub fn validate_candle(candle: &Candle) -> bool {
+    let parse = |value: &str| value.parse::<f64>().ok().filter(|v| v.is_finite());
+    let (Some(open), Some(high), Some(low), Some(close)) = (
+        parse(&candle.open),
+        parse(&candle.high),
+        parse(&candle.low),
+        parse(&candle.close),
+    ) else {
+        return false;
+    };
+    let volume_ok = parse(&candle.volume).is_some_and(|v| v >= 0.0);
+    low > 0.0
+        && high >= low
+        && (low..=high).contains(&open)
+        && (low..=high).contains(&close)
+        && volume_ok
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candle(open_time: u64, close: &str, is_closed: bool) -> Candle {
+        Candle {
+            close: close.to_string(),
+            is_closed,
+            ..crate::data::fetch::candle(open_time, 1.0, 1.0, 1.0, 1.0)
+        }
+    }
+
+    fn summary(candles: &VecDeque<Candle>) -> Vec<(u64, &str, bool)> {
+        candles
+            .iter()
+            .map(|c| (c.open_time, c.close.as_str(), c.is_closed))
+            .collect()
+    }
+
+    #[test]
+    fn closed_candles_win_over_in_progress_ones() {
+        let mut candles = VecDeque::from([candle(0, "a", true), candle(60_000, "b", false)]);
+
+        // A stale live update for a finalized candle is ignored.
+        assert!(!merge_candles(&mut candles, [candle(0, "stale", false)]));
+        // A closed candle finalizes an in-progress one.
+        assert!(merge_candles(&mut candles, [candle(60_000, "final", true)]));
+        // A later closed copy (e.g. the REST backfill) replaces a closed one.
+        assert!(merge_candles(&mut candles, [candle(0, "rest", true)]));
+        assert_eq!(
+            summary(&candles),
+            vec![(0, "rest", true), (60_000, "final", true)]
+        );
+
+        // Newer in-progress snapshots replace older ones.
+        merge_candles(&mut candles, [candle(120_000, "x", false)]);
+        merge_candles(&mut candles, [candle(120_000, "y", false)]);
+        assert_eq!(summary(&candles)[2], (120_000, "y", false));
+    }
+
+    #[test]
+    fn stale_rest_row_does_not_roll_back_a_live_candle() {
+        let with_trades = |close: &str, is_closed: bool, trades: u64| Candle {
+            number_of_trades: trades,
+            ..candle(60_000, close, is_closed)
+        };
+        let mut candles = VecDeque::from([candle(0, "a", true), with_trades("live", false, 40)]);
+
+        // The fetch went out before the last few ticks arrived.
+        assert!(!merge_candles(
+            &mut candles,
+            [with_trades("rest", false, 35)]
+        ));
+        assert_eq!(summary(&candles)[1], (60_000, "live", false));
+
+        // Once the REST row is closed it is final, whatever the live copy says.
+        assert!(merge_candles(&mut candles, [with_trades("rest", true, 41)]));
+        assert!(!merge_candles(
+            &mut candles,
+            [with_trades("late", false, 50)]
+        ));
+        assert_eq!(summary(&candles)[1], (60_000, "rest", true));
+    }
+
+    #[test]
+    fn keeps_ascending_order_without_duplicates() {
+        let mut candles = VecDeque::from([candle(60_000, "b", false), candle(240_000, "e", false)]);
+        let changed = merge_candles(
+            &mut candles,
+            [
+                candle(180_000, "d", true),
+                candle(0, "a", true),
+                candle(120_000, "c", false),
+                candle(180_000, "d-live", false),
+                candle(120_000, "c-final", true),
+                candle(300_000, "f", false),
+            ],
+        );
+        assert!(changed);
+        assert_eq!(
+            summary(&candles),
+            vec![
+                (0, "a", true),
+                (60_000, "b", false),
+                (120_000, "c-final", true),
+                (180_000, "d", true),
+                (240_000, "e", false),
+                (300_000, "f", false),
+            ]
+        );
+
+        // Replaying the same data is a no-op.
+        let snapshot = candles.clone();
+        assert!(!merge_candles(&mut candles, snapshot.clone()));
+        assert_eq!(candles, snapshot);
+
+        let mut empty = VecDeque::new();
+        assert!(!merge_candles(&mut empty, []));
+        assert!(merge_candles(
+            &mut empty,
+            [candle(60_000, "b", false), candle(0, "a", false)]
+        ));
+        assert_eq!(summary(&empty), vec![(0, "a", false), (60_000, "b", false)]);
+    }
+
+    #[test]
+    fn rejects_impossible_prices() {
+        let ohlc = |open: &str, high: &str, low: &str, close: &str| Candle {
+            open: open.to_string(),
+            high: high.to_string(),
+            low: low.to_string(),
+            ..candle(0, close, true)
+        };
+
+        assert!(validate_candle(&ohlc("2", "3", "1", "2.5")));
+        assert!(validate_candle(&ohlc("1", "1", "1", "1")));
+        // Zero or negative prices.
+        assert!(!validate_candle(&ohlc("0", "3", "0", "2")));
+        assert!(!validate_candle(&ohlc("-2", "3", "-5", "2")));
+        // High below low, or open/close outside the range.
+        assert!(!validate_candle(&ohlc("2", "1", "3", "2")));
+        assert!(!validate_candle(&ohlc("4", "3", "1", "2")));
+        assert!(!validate_candle(&ohlc("2", "3", "1", "0.5")));
+        // Unparseable or non-finite values.
+        assert!(!validate_candle(&ohlc("", "3", "1", "2")));
+        assert!(!validate_candle(&ohlc("2", "inf", "1", "2")));
+        assert!(!validate_candle(&ohlc("2", "3", "1", "NaN")));
+        assert!(!validate_candle(&Candle {
+            volume: "-1".to_string(),
+            ..ohlc("2", "3", "1", "2")
+        }));
+    }
+}
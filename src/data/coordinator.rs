@@ -0,0 +1,804 @@
+use crate::data::orderbook::stream_orderbook;
+use crate::data::prices::{stream_watchlist_prices, PriceSource};
+use crate::data::reconnect::ReconnectPolicy;
+use crate::data::stats::{FeedStats, StreamStats};
+use crate::data::stream::{candle_matches_interval, stream_klines, KlineEvent};
+use crate::data::trades::{stream_trade_feed, TradeStreamKind};
+use crate::data::transport::{TungsteniteConnect, WsConnect};
+use crate::data::{Candle, OrderBook, Trade, WatchPrice};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio::task::JoinHandle;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Feed {
+    Kline,
+    OrderBook,
+    Trades,
+    Prices,
+}
+
+impl Feed {
+    /// How long the feed may go without data before its panel is flagged
+    /// stale, a few multiples of how often Binance pushes it.
+    pub fn stale_after(self) -> Duration {
+        match self {
+            // Kline updates arrive every 1-2s.
+            Feed::Kline => Duration::from_secs(10),
+            // Depth snapshots arrive every 100ms.
+            Feed::OrderBook => Duration::from_secs(3),
+            // Trades are irregular and can pause on quiet markets.
+            Feed::Trades => Duration::from_secs(30),
+            // Mini tickers arrive every second for an active watchlist.
+            Feed::Prices => Duration::from_secs(10),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionEvent {
+    /// A (re)subscription was started; no data has arrived yet.
+    Connecting,
+    /// The socket came back after dropping; `downtime` covers the gap since
+    /// its last message.
+    Reconnected { downtime: Duration },
+    /// The feed used up its reconnect attempts and stopped.
+    GaveUp,
+}
+
+#[derive(Debug, Clone)]
+pub enum StreamEvent {
+    CandleUpdate(Candle),
+    /// Open time of a kline dropped as invalid.
+    CandleRejected(u64),
+    TradeUpdate(Trade),
+    BookUpdate(OrderBook),
+    PriceUpdate(WatchPrice),
+    Connection(Feed, ConnectionEvent),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Subscription {
+    Kline {
+        symbol: String,
+        interval: String,
+    },
+    OrderBook {
+        symbol: String,
+    },
+    Trades {
+        kind: TradeStreamKind,
+        symbol: String,
+    },
+    Prices {
+        source: PriceSource,
+        symbols: Vec<String>,
+        /// Streams per connection; longer lists use several connections.
+        max_streams: usize,
+    },
+}
+
+impl Subscription {
+    pub fn feed(&self) -> Feed {
+        match self {
+            Subscription::Kline { .. } => Feed::Kline,
+            Subscription::OrderBook { .. } => Feed::OrderBook,
+            Subscription::Trades { .. } => Feed::Trades,
+            Subscription::Prices { .. } => Feed::Prices,
+        }
+    }
+}
+
+/// Opens the upstream connection behind a subscription. The returned task
+/// forwards events through `events` and finishes only when the feed gives up;
+/// aborting it must stop the connection.
+pub trait FeedSource {
+    fn subscribe(
+        &self,
+        subscription: &Subscription,
+        policy: ReconnectPolicy,
+        events: FeedSender,
+    ) -> JoinHandle<()>;
+}
+
+/// Sending half handed to a [`FeedSource`]; events are tagged so the
+/// coordinator can drop anything from a subscription it has since replaced.
+#[derive(Clone)]
+pub struct FeedSender {
+    id: u64,
+    feed: Feed,
+    tx: UnboundedSender<Tagged>,
+    stats: Arc<StreamStats>,
+}
+
+impl FeedSender {
+    /// Counters for the stream task to bump, cleared when it was subscribed.
+    pub fn stats(&self) -> Arc<StreamStats> {
+        self.stats.clone()
+    }
+
+    /// Returns false once the coordinator is gone and the sender should stop.
+    pub fn send(&self, event: StreamEvent) -> bool {
+        self.tx
+            .send(Tagged {
+                id: self.id,
+                feed: self.feed,
+                sent_at: Instant::now(),
+                event,
+            })
+            .is_ok()
+    }
+}
+
+struct Tagged {
+    id: u64,
+    feed: Feed,
+    sent_at: Instant,
+    event: StreamEvent,
+}
+
+struct AbortOnDrop(JoinHandle<()>);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+struct ActiveSubscription {
+    id: u64,
+    subscription: Subscription,
+    /// When the feed last delivered data, or when it was subscribed.
+    last_data: Instant,
+    _watcher: AbortOnDrop,
+}
+
+/// Owns every live market-data subscription and merges them into one event
+/// stream. Replacing or dropping a subscription aborts its task, so switching
+/// symbols never leaves old connections running.
+pub struct StreamCoordinator<S: FeedSource = BinanceFeeds> {
+    source: S,
+    policy: ReconnectPolicy,
+    tx: UnboundedSender<Tagged>,
+    rx: UnboundedReceiver<Tagged>,
+    /// Events pulled off the channel early by [`Self::newest_book`].
+    pending: VecDeque<Tagged>,
+    next_id: u64,
+    active: HashMap<Feed, ActiveSubscription>,
+    stats: Arc<FeedStats>,
+}
+
+impl<S: FeedSource> StreamCoordinator<S> {
+    pub fn new(source: S, policy: ReconnectPolicy) -> Self {
+        let (tx, rx) = mpsc::unbounded_channel();
+        Self {
+            source,
+            policy,
+            tx,
+            rx,
+            pending: VecDeque::new(),
+            next_id: 0,
+            active: HashMap::new(),
+            stats: Arc::new(FeedStats::default()),
+        }
+    }
+
+    /// Message counters for every feed, for a debug view. Each feed's are
+    /// cleared whenever it is resubscribed.
+    pub fn stats(&self) -> Arc<FeedStats> {
+        self.stats.clone()
+    }
+
+    /// Starts `subscription`, replacing whatever was running for its feed.
+    pub fn subscribe(&mut self, subscription: Subscription) {
+        let feed = subscription.feed();
+        self.next_id += 1;
+        let stats = self.stats.get(feed).clone();
+        stats.reset();
+        let events = FeedSender {
+            id: self.next_id,
+            feed,
+            tx: self.tx.clone(),
+            stats,
+        };
+        events.send(StreamEvent::Connection(feed, ConnectionEvent::Connecting));
+
+        let upstream = self
+            .source
+            .subscribe(&subscription, self.policy, events.clone());
+        let watcher = tokio::spawn(async move {
+            let mut upstream = AbortOnDrop(upstream);
+            let _ = (&mut upstream.0).await;
+            events.send(StreamEvent::Connection(feed, ConnectionEvent::GaveUp));
+        });
+
+        self.active.insert(
+            feed,
+            ActiveSubscription {
+                id: self.next_id,
+                subscription,
+                last_data: Instant::now(),
+                _watcher: AbortOnDrop(watcher),
+            },
+        );
+    }
+
+    /// Moves the chart feeds (klines, depth, trades) to a new market in one
+    /// step.
+    pub fn switch_market(&mut self, symbol: &str, interval: &str, trade_kind: TradeStreamKind) {
+        self.subscribe(Subscription::Kline {
+            symbol: symbol.to_string(),
+            interval: interval.to_string(),
+        });
+        self.subscribe(Subscription::OrderBook {
+            symbol: symbol.to_string(),
+        });
+        self.subscribe(Subscription::Trades {
+            kind: trade_kind,
+            symbol: symbol.to_string(),
+        });
+    }
+
+    /// Follows `source` prices for `symbols`, at most `max_streams` per
+    /// connection; an empty list just stops the feed.
+    pub fn set_watchlist(&mut self, symbols: Vec<String>, source: PriceSource, max_streams: usize) {
+        if symbols.is_empty() {
+            self.active.remove(&Feed::Prices);
+            self.stats.get(Feed::Prices).reset();
+            return;
+        }
+        self.subscribe(Subscription::Prices {
+            source,
+            symbols,
+            max_streams,
+        });
+    }
+
+    /// Reopens every active subscription, e.g. after feeds gave up.
+    pub fn restart_all(&mut self) {
+        let mut subscriptions: Vec<Subscription> = self
+            .active
+            .values()
+            .map(|active| active.subscription.clone())
+            .collect();
+        subscriptions.sort_by_key(|subscription| subscription.feed() as u8);
+        for subscription in subscriptions {
+            self.subscribe(subscription);
+        }
+    }
+
+    /// Waits for the next event from a current subscription.
+    pub async fn recv(&mut self) -> StreamEvent {
+        if let Some(event) = self.try_recv() {
+            return event;
+        }
+        loop {
+            // The coordinator holds a sender itself, so the channel never
+            // closes while it is alive.
+            if let Some(tagged) = self.rx.recv().await {
+                if let Some(event) = self.current(tagged) {
+                    return event;
+                }
+            }
+        }
+    }
+
+    /// Non-blocking [`Self::recv`], for draining bursts.
+    pub fn try_recv(&mut self) -> Option<StreamEvent> {
+        while let Some(tagged) = self.pending.pop_front().or_else(|| self.rx.try_recv().ok()) {
+            if let Some(event) = self.current(tagged) {
+                return Some(event);
+            }
+        }
+        None
+    }
+
+    /// Skips order books already queued behind `book` and returns the newest,
+    /// keeping every other event in order for later.
+    pub fn newest_book(&mut self, mut book: OrderBook) -> OrderBook {
+        let mut kept = VecDeque::new();
+        while let Some(tagged) = self.pending.pop_front().or_else(|| self.rx.try_recv().ok()) {
+            match tagged.event {
+                StreamEvent::BookUpdate(newer) if self.is_current(&tagged) => {
+                    self.mark_data(tagged.feed, tagged.sent_at);
+                    book = newer;
+                }
+                _ => kept.push_back(tagged),
+            }
+        }
+        self.pending = kept;
+        book
+    }
+
+    /// Takes the trades queued behind `trade` so a burst is handled in one
+    /// pass, keeping every other event in order for later. When more than
+    /// `max` are waiting the handler has fallen behind, so only the newest
+    /// `max` are returned; the second value counts the trades skipped.
+    pub fn drain_trades(&mut self, trade: Trade, max: usize) -> (Vec<Trade>, usize) {
+        let mut trades = vec![trade];
+        let mut kept = VecDeque::new();
+        while let Some(tagged) = self.pending.pop_front().or_else(|| self.rx.try_recv().ok()) {
+            match tagged.event {
+                StreamEvent::TradeUpdate(newer) if self.is_current(&tagged) => {
+                    self.mark_data(tagged.feed, tagged.sent_at);
+                    trades.push(newer);
+                }
+                _ => kept.push_back(tagged),
+            }
+        }
+        self.pending = kept;
+        let skipped = trades.len().saturating_sub(max.max(1));
+        trades.drain(..skipped);
+        (trades, skipped)
+    }
+
+    /// Feeds that have gone longer than [`Feed::stale_after`] without data,
+    /// counting from their subscription if nothing has arrived yet.
+    pub fn stale_feeds(&self, now: Instant) -> Vec<Feed> {
+        let mut stale: Vec<Feed> = self
+            .active
+            .iter()
+            .filter(|(feed, active)| {
+                now.saturating_duration_since(active.last_data) >= feed.stale_after()
+            })
+            .map(|(feed, _)| *feed)
+            .collect();
+        stale.sort_by_key(|feed| *feed as u8);
+        stale
+    }
+
+    fn is_current(&self, tagged: &Tagged) -> bool {
+        self.active.get(&tagged.feed).map(|active| active.id) == Some(tagged.id)
+    }
+
+    fn mark_data(&mut self, feed: Feed, sent_at: Instant) {
+        if let Some(active) = self.active.get_mut(&feed) {
+            active.last_data = active.last_data.max(sent_at);
+        }
+    }
+
+    fn current(&mut self, tagged: Tagged) -> Option<StreamEvent> {
+        if !self.is_current(&tagged) {
+            return None;
+        }
+        // Even the current socket can hand over a candle of another interval,
+        // e.g. one buffered across a switch, and it must not reach the chart.
+        if let (StreamEvent::CandleUpdate(candle), Some(active)) =
+            (&tagged.event, self.active.get(&Feed::Kline))
+        {
+            if let Subscription::Kline { interval, .. } = &active.subscription {
+                if !candle_matches_interval(candle, interval) {
+                    return None;
+                }
+            }
+        }
+        if !matches!(tagged.event, StreamEvent::Connection(..)) {
+            self.mark_data(tagged.feed, tagged.sent_at);
+        }
+        Some(tagged.event)
+    }
+}
+
+/// Live Binance websocket feeds, opened through `connector`.
+pub struct BinanceFeeds {
+    connector: Arc<dyn WsConnect>,
+}
+
+impl BinanceFeeds {
+    #[cfg(test)]
+    pub fn with_connector(connector: impl WsConnect) -> Self {
+        Self {
+            connector: Arc::new(connector),
+        }
+    }
+}
+
+impl Default for BinanceFeeds {
+    fn default() -> Self {
+        Self {
+            connector: Arc::new(TungsteniteConnect),
+        }
+    }
+}
+
+impl FeedSource for BinanceFeeds {
+    fn subscribe(
+        &self,
+        subscription: &Subscription,
+        policy: ReconnectPolicy,
+        events: FeedSender,
+    ) -> JoinHandle<()> {
+        let connector = self.connector.clone();
+        match subscription {
+            Subscription::Kline { symbol, interval } => {
+                let (rx, upstream) =
+                    stream_klines(connector, symbol, interval, policy, events.stats());
+                forward(rx, upstream, events, |event| match event {
+                    KlineEvent::Candle(candle) => Some(StreamEvent::CandleUpdate(candle)),
+                    KlineEvent::Rejected { open_time } => {
+                        Some(StreamEvent::CandleRejected(open_time))
+                    }
+                    KlineEvent::Connected { downtime } => downtime.map(|downtime| {
+                        StreamEvent::Connection(
+                            Feed::Kline,
+                            ConnectionEvent::Reconnected { downtime },
+                        )
+                    }),
+                })
+            }
+            Subscription::OrderBook { symbol } => {
+                let (rx, upstream) = stream_orderbook(connector, symbol, policy, events.stats());
+                forward(rx, upstream, events, |book| {
+                    Some(StreamEvent::BookUpdate(book))
+                })
+            }
+            Subscription::Trades { kind, symbol } => {
+                let (rx, upstream) =
+                    stream_trade_feed(connector, *kind, symbol, policy, events.stats());
+                forward(rx, upstream, events, |trade| {
+                    Some(StreamEvent::TradeUpdate(trade))
+                })
+            }
+            Subscription::Prices {
+                source,
+                symbols,
+                max_streams,
+            } => {
+                let (rx, upstream) = stream_watchlist_prices(
+                    connector,
+                    symbols,
+                    *source,
+                    *max_streams,
+                    policy,
+                    events.stats(),
+                );
+                forward(rx, upstream, events, |price| {
+                    Some(StreamEvent::PriceUpdate(price))
+                })
+            }
+        }
+    }
+}
+
+fn forward<T: Send + 'static>(
+    mut rx: mpsc::Receiver<T>,
+    upstream: JoinHandle<()>,
+    events: FeedSender,
+    wrap: fn(T) -> Option<StreamEvent>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let _upstream = AbortOnDrop(upstream);
+        while let Some(item) = rx.recv().await {
+            if let Some(event) = wrap(item) {
+                if !events.send(event) {
+                    return;
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    /// Hands out the senders instead of connecting, and counts tasks still
+    /// running so tests can see teardown.
+    #[derive(Default, Clone)]
+    struct MockFeeds {
+        senders: Arc<Mutex<Vec<(Subscription, FeedSender)>>>,
+        running: Arc<AtomicUsize>,
+    }
+
+    struct Running(Arc<AtomicUsize>);
+
+    impl Drop for Running {
+        fn drop(&mut self) {
+            self.0.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+
+    impl MockFeeds {
+        fn sender(&self, idx: usize) -> FeedSender {
+            self.senders.lock().unwrap()[idx].1.clone()
+        }
+    }
+
+    impl FeedSource for MockFeeds {
+        fn subscribe(
+            &self,
+            subscription: &Subscription,
+            _policy: ReconnectPolicy,
+            events: FeedSender,
+        ) -> JoinHandle<()> {
+            self.senders
+                .lock()
+                .unwrap()
+                .push((subscription.clone(), events));
+            self.running.fetch_add(1, Ordering::SeqCst);
+            let running = Running(self.running.clone());
+            tokio::spawn(async move {
+                let _running = running;
+                std::future::pending::<()>().await;
+            })
+        }
+    }
+
+    fn price(symbol: &str) -> StreamEvent {
+        StreamEvent::PriceUpdate(WatchPrice {
+            symbol: symbol.to_string(),
+            last_price: 1.0,
+            change_pct: 0.0,
+        })
+    }
+
+    async fn settle() {
+        for _ in 0..4 {
+            tokio::task::yield_now().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn switching_market_tears_down_old_feeds_and_drops_their_events() {
+        let feeds = MockFeeds::default();
+        let mut coordinator = StreamCoordinator::new(feeds.clone(), ReconnectPolicy::default());
+        coordinator.switch_market("BTCUSDT", "1m", TradeStreamKind::Raw);
+        settle().await;
+        assert_eq!(feeds.running.load(Ordering::SeqCst), 3);
+
+        let old_kline = feeds.sender(0);
+        coordinator.switch_market("ETHUSDT", "1m", TradeStreamKind::Raw);
+        settle().await;
+        assert_eq!(feeds.running.load(Ordering::SeqCst), 3);
+
+        assert!(old_kline.send(price("BTCUSDT")));
+        feeds.sender(3).send(price("ETHUSDT"));
+
+        let mut received = Vec::new();
+        while let Some(event) = coordinator.try_recv() {
+            received.push(event);
+        }
+        let connecting = received
+            .iter()
+            .filter(|event| {
+                matches!(
+                    event,
+                    StreamEvent::Connection(_, ConnectionEvent::Connecting)
+                )
+            })
+            .count();
+        assert_eq!(connecting, 3);
+        let prices: Vec<_> = received
+            .iter()
+            .filter_map(|event| match event {
+                StreamEvent::PriceUpdate(price) => Some(price.symbol.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(prices, vec!["ETHUSDT"]);
+
+        drop(coordinator);
+        settle().await;
+        assert_eq!(feeds.running.load(Ordering::SeqCst), 0);
+    }
+
+    fn candle(open_time: u64, close_time: u64) -> StreamEvent {
+        StreamEvent::CandleUpdate(Candle {
+            close_time,
+            ..crate::data::fetch::candle(open_time, 1.0, 1.0, 1.0, 1.0)
+        })
+    }
+
+    #[tokio::test]
+    async fn overlapping_interval_streams_only_deliver_the_active_interval() {
+        let feeds = MockFeeds::default();
+        let mut coordinator = StreamCoordinator::new(feeds.clone(), ReconnectPolicy::default());
+        coordinator.switch_market("BTCUSDT", "1m", TradeStreamKind::Raw);
+        let old_kline = feeds.sender(0);
+        coordinator.subscribe(Subscription::Kline {
+            symbol: "BTCUSDT".to_string(),
+            interval: "5m".to_string(),
+        });
+        settle().await;
+
+        // Both sockets are briefly alive and deliver at once, and the new one
+        // still has a 1m candle queued from before the switch.
+        old_kline.send(candle(60_000, 119_999));
+        feeds.sender(3).send(candle(0, 299_999));
+        feeds.sender(3).send(candle(120_000, 179_999));
+        old_kline.send(candle(120_000, 179_999));
+
+        let mut candles = Vec::new();
+        while let Some(event) = coordinator.try_recv() {
+            if let StreamEvent::CandleUpdate(candle) = event {
+                candles.push(candle);
+            }
+        }
+        assert_eq!(candles.len(), 1);
+        assert_eq!((candles[0].open_time, candles[0].close_time), (0, 299_999));
+    }
+
+    fn kline_text(open_time: u64, close_time: u64) -> tokio_tungstenite::tungstenite::Message {
+        tokio_tungstenite::tungstenite::Message::Text(
+            serde_json::json!({"e": "kline", "k": {
+                "t": open_time, "T": close_time, "o": "1", "h": "1", "l": "1", "c": "1",
+                "v": "0", "q": "0", "n": 0, "V": "0", "Q": "0", "x": false
+            }})
+            .to_string(),
+        )
+    }
+
+    #[tokio::test]
+    async fn binance_feeds_run_over_a_scripted_socket() {
+        use crate::data::transport::mock::{MockWs, Session};
+
+        let connector = MockWs::new(vec![
+            Session::Messages(vec![kline_text(0, 59_999)]),
+            Session::Messages(vec![kline_text(60_000, 119_999)]),
+        ]);
+        let policy = ReconnectPolicy {
+            max_attempts: 1,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(1),
+        };
+        let mut coordinator =
+            StreamCoordinator::new(BinanceFeeds::with_connector(connector), policy);
+        coordinator.subscribe(Subscription::Kline {
+            symbol: "BTCUSDT".to_string(),
+            interval: "1m".to_string(),
+        });
+
+        let mut events = Vec::new();
+        loop {
+            let event = tokio::time::timeout(Duration::from_secs(5), coordinator.recv())
+                .await
+                .unwrap();
+            let gave_up = matches!(event, StreamEvent::Connection(_, ConnectionEvent::GaveUp));
+            events.push(event);
+            if gave_up {
+                break;
+            }
+        }
+
+        assert!(matches!(
+            events[0],
+            StreamEvent::Connection(Feed::Kline, ConnectionEvent::Connecting)
+        ));
+        assert!(matches!(&events[1], StreamEvent::CandleUpdate(c) if c.open_time == 0));
+        assert!(matches!(
+            events[2],
+            StreamEvent::Connection(Feed::Kline, ConnectionEvent::Reconnected { .. })
+        ));
+        assert!(matches!(&events[3], StreamEvent::CandleUpdate(c) if c.open_time == 60_000));
+        assert_eq!(events.len(), 5);
+        assert_eq!(
+            coordinator.stats().get(Feed::Kline).snapshot().reconnects,
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn resubscribing_clears_the_feed_counters() {
+        let feeds = MockFeeds::default();
+        let mut coordinator = StreamCoordinator::new(feeds.clone(), ReconnectPolicy::default());
+        let stats = coordinator.stats();
+        coordinator.switch_market("BTCUSDT", "1m", TradeStreamKind::Raw);
+        feeds.sender(0).stats().record_message();
+        feeds.sender(1).stats().record_message();
+        assert_eq!(stats.get(Feed::Kline).snapshot().messages, 1);
+
+        coordinator.subscribe(Subscription::Kline {
+            symbol: "ETHUSDT".to_string(),
+            interval: "1m".to_string(),
+        });
+        assert_eq!(stats.get(Feed::Kline).snapshot().messages, 0);
+        assert_eq!(stats.get(Feed::OrderBook).snapshot().messages, 1);
+    }
+
+    #[tokio::test]
+    async fn finished_feed_reports_gave_up_and_restarts() {
+        struct EndedFeeds;
+        impl FeedSource for EndedFeeds {
+            fn subscribe(
+                &self,
+                _subscription: &Subscription,
+                _policy: ReconnectPolicy,
+                _events: FeedSender,
+            ) -> JoinHandle<()> {
+                tokio::spawn(async {})
+            }
+        }
+
+        let mut coordinator = StreamCoordinator::new(EndedFeeds, ReconnectPolicy::default());
+        coordinator.set_watchlist(vec!["BTCUSDT".to_string()], PriceSource::Last, 1);
+        assert!(matches!(
+            coordinator.recv().await,
+            StreamEvent::Connection(Feed::Prices, ConnectionEvent::Connecting)
+        ));
+        assert!(matches!(
+            coordinator.recv().await,
+            StreamEvent::Connection(Feed::Prices, ConnectionEvent::GaveUp)
+        ));
+
+        coordinator.restart_all();
+        assert!(matches!(
+            coordinator.recv().await,
+            StreamEvent::Connection(Feed::Prices, ConnectionEvent::Connecting)
+        ));
+    }
+
+    fn trade(price: f64) -> Trade {
+        Trade {
+            price,
+            quantity: 1.0,
+            is_buyer_maker: false,
+            timestamp: 0,
+            first_trade_id: 1,
+            last_trade_id: 1,
+        }
+    }
+
+    #[tokio::test]
+    async fn backlogged_trades_keep_only_the_newest() {
+        let feeds = MockFeeds::default();
+        let mut coordinator = StreamCoordinator::new(feeds.clone(), ReconnectPolicy::default());
+        coordinator.switch_market("BTCUSDT", "1m", TradeStreamKind::Raw);
+        coordinator.set_watchlist(vec!["BTCUSDT".to_string()], PriceSource::Last, 1);
+        settle().await;
+        while coordinator.try_recv().is_some() {}
+
+        let trades = feeds.sender(2);
+        for price in 2..=6 {
+            trades.send(StreamEvent::TradeUpdate(trade(price as f64)));
+        }
+        feeds.sender(3).send(price("BTCUSDT"));
+
+        let prices = |trades: &[Trade]| trades.iter().map(|t| t.price).collect::<Vec<_>>();
+        let (drained, skipped) = coordinator.drain_trades(trade(1.0), 10);
+        assert_eq!(
+            (prices(&drained), skipped),
+            (vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], 0)
+        );
+
+        for price in 2..=6 {
+            trades.send(StreamEvent::TradeUpdate(trade(price as f64)));
+        }
+        let (drained, skipped) = coordinator.drain_trades(trade(1.0), 2);
+        assert_eq!((prices(&drained), skipped), (vec![5.0, 6.0], 4));
+        // Other feeds' events wait their turn.
+        assert!(matches!(
+            coordinator.try_recv(),
+            Some(StreamEvent::PriceUpdate(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn quiet_feeds_go_stale_on_their_own_thresholds() {
+        let feeds = MockFeeds::default();
+        let mut coordinator = StreamCoordinator::new(feeds.clone(), ReconnectPolicy::default());
+        coordinator.switch_market("BTCUSDT", "1m", TradeStreamKind::Raw);
+        coordinator.set_watchlist(vec!["BTCUSDT".to_string()], PriceSource::Last, 1);
+        settle().await;
+        let start = Instant::now();
+        assert!(coordinator.stale_feeds(start).is_empty());
+
+        // Depth goes stale first; the slower trade feed is still within its
+        // allowance.
+        let later = start + Duration::from_secs(12);
+        assert_eq!(
+            coordinator.stale_feeds(later),
+            vec![Feed::Kline, Feed::OrderBook, Feed::Prices]
+        );
+
+        feeds.sender(3).send(price("BTCUSDT"));
+        while coordinator.try_recv().is_some() {}
+        assert_eq!(
+            coordinator.stale_feeds(Instant::now() + Duration::from_secs(5)),
+            vec![Feed::OrderBook]
+        );
+    }
+}
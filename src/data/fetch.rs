@@ -1,9 +1,111 @@
-use reqwest::Client;
+use crate::data::candles::validate_candle;
+use crate::data::server::server_now_ms;
+use crate::data::transport::{HttpGet, ReqwestHttp};
+use reqwest::StatusCode;
 use serde::Deserialize;
 use serde_json::Value;
+use std::fmt;
+use std::time::Duration;
+
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(1);
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(120);
+
+#[derive(Debug)]
+pub enum FetchError {
+    /// Binance answered 429 (weight limit) or 418 (IP ban after ignoring 429s).
+    RateLimited {
+        retry_after: Duration,
+    },
+    Http(reqwest::Error),
+    /// Any other non-success status.
+    Status(u16),
+    /// The response decoded but didn't have the expected shape.
+    Malformed(&'static str),
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::RateLimited { retry_after } => {
+                write!(f, "rate limited, retry after {}s", retry_after.as_secs())
+            }
+            FetchError::Http(err) => write!(f, "{}", err),
+            FetchError::Status(status) => write!(f, "HTTP status {}", status),
+            FetchError::Malformed(what) => write!(f, "malformed {} response", what),
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
+
+impl FetchError {
+    /// Binance rejected the request itself (a 4xx other than rate limits),
+    /// e.g. an unknown symbol, as opposed to a timeout or server trouble.
+    pub fn is_rejected(&self) -> bool {
+        match self {
+            FetchError::Status(status) => (400..500).contains(status),
+            FetchError::Http(err) => err.status().is_some_and(|s| s.is_client_error()),
+            FetchError::RateLimited { .. } | FetchError::Malformed(_) => false,
+        }
+    }
+}
+
+impl From<reqwest::Error> for FetchError {
+    fn from(err: reqwest::Error) -> Self {
+        FetchError::Http(err)
+    }
+}
+
+pub fn parse_retry_after(header: Option<&str>) -> Duration {
+    header
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_RETRY_AFTER)
+        .min(MAX_RETRY_AFTER)
+}
+
+/// `FetchError::RateLimited` for Binance's rate-limit statuses.
+fn check_rate_limit(status: u16, retry_after: Option<&str>) -> Result<(), FetchError> {
+    if status == StatusCode::TOO_MANY_REQUESTS.as_u16()
+        || status == StatusCode::IM_A_TEAPOT.as_u16()
+    {
+        return Err(FetchError::RateLimited {
+            retry_after: parse_retry_after(retry_after),
+        });
+    }
+    Ok(())
+}
+
+/// GETs `url` through `http`, turning rate-limit statuses into
+/// `FetchError::RateLimited` and any other non-success status into
+/// `FetchError::Status`, and returns the body.
+pub async fn get_checked(
+    http: &dyn HttpGet,
+    url: &str,
+    query: &[(&str, String)],
+) -> Result<String, FetchError> {
+    let res = http.get(url, query).await?;
+    check_rate_limit(res.status, res.retry_after.as_deref())?;
+    if !(200..300).contains(&res.status) {
+        return Err(FetchError::Status(res.status));
+    }
+    Ok(res.body)
+}
+
+/// [`get_checked`], decoding the body as JSON; a body that isn't JSON is
+/// `FetchError::Malformed(what)`.
+pub async fn get_json(
+    http: &dyn HttpGet,
+    url: &str,
+    query: &[(&str, String)],
+    what: &'static str,
+) -> Result<Value, FetchError> {
+    let body = get_checked(http, url, query).await?;
+    serde_json::from_str(&body).map_err(|_| FetchError::Malformed(what))
+}
 
 #[allow(dead_code)]
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct Candle {
     pub open_time: u64,
     pub open: String,
@@ -17,45 +119,211 @@ pub struct Candle {
     pub taker_buy_base: String,
     pub taker_buy_quote: String,
     pub ignore: String,
+    /// Set by the kline stream's `x` flag once the candle's interval has
+    /// ended. REST candles are closed once their close time has passed on
+    /// the server's clock.
+    #[serde(default)]
+    pub is_closed: bool,
+}
+
+/// An in-progress one-minute candle for tests; wrap it with struct update
+/// syntax for anything else.
+#[cfg(test)]
+pub(crate) fn candle(open_time: u64, open: f64, high: f64, low: f64, close: f64) -> Candle {
+    Candle {
+        open_time,
+        open: open.to_string(),
+        high: high.to_string(),
+        low: low.to_string(),
+        close: close.to_string(),
+        volume: "1".to_string(),
+        close_time: open_time + 59_999,
+        quote_volume: "0".to_string(),
+        number_of_trades: 1,
+        taker_buy_base: "0".to_string(),
+        taker_buy_quote: "0".to_string(),
+        ignore: "0".to_string(),
+        is_closed: false,
+    }
+}
+
+/// Open times, in Unix milliseconds, bounding a historical fetch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KlineWindow {
+    pub start_ms: u64,
+    pub end_ms: u64,
+}
+
+/// A klines response split by [`validate_candle`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct KlineBatch {
+    pub candles: Vec<Candle>,
+    /// Open times of the rows that failed validation.
+    pub rejected: Vec<u64>,
 }
 
+/// The latest `limit` candles.
 pub async fn fetch_klines(
     symbol: &str,
     interval: &str,
     limit: u32,
-) -> Result<Vec<Candle>, reqwest::Error> {
-    let client = Client::new();
-    let url = "https://api.binance.com/api/v3/klines";
-    let limit_str = limit.to_string();
-    let res = client
-        .get(url)
-        .query(&[
-            ("symbol", symbol),
-            ("interval", interval),
-            ("limit", &limit_str),
-        ])
-        .send()
+) -> Result<Vec<Candle>, FetchError> {
+    fetch_kline_window(symbol, interval, limit, None).await
+}
+
+/// Up to `limit` valid candles, from the start of `window` when given rather
+/// than ending at the latest one.
+pub async fn fetch_kline_window(
+    symbol: &str,
+    interval: &str,
+    limit: u32,
+    window: Option<KlineWindow>,
+) -> Result<Vec<Candle>, FetchError> {
+    Ok(fetch_kline_batch(symbol, interval, limit, window)
         .await?
-        .json::<Vec<Vec<Value>>>()
-        .await?;
+        .candles)
+}
+
+/// [`fetch_kline_window`], also reporting the rows it dropped.
+pub async fn fetch_kline_batch(
+    symbol: &str,
+    interval: &str,
+    limit: u32,
+    window: Option<KlineWindow>,
+) -> Result<KlineBatch, FetchError> {
+    fetch_kline_window_with(&ReqwestHttp::default(), symbol, interval, limit, window).await
+}
 
-    let candles: Vec<Candle> = res
+/// [`fetch_kline_batch`] over any [`HttpGet`].
+pub async fn fetch_kline_window_with(
+    http: &dyn HttpGet,
+    symbol: &str,
+    interval: &str,
+    limit: u32,
+    window: Option<KlineWindow>,
+) -> Result<KlineBatch, FetchError> {
+    let url = "https://api.binance.com/api/v3/klines";
+    let mut query = vec![
+        ("symbol", symbol.to_string()),
+        ("interval", interval.to_string()),
+        ("limit", limit.to_string()),
+    ];
+    if let Some(window) = window {
+        query.push(("startTime", window.start_ms.to_string()));
+        query.push(("endTime", window.end_ms.to_string()));
+    }
+    let body = get_checked(http, url, &query).await?;
+    let (candles, rejected) = parse_klines(&body, server_now_ms())
+        .ok_or(FetchError::Malformed("klines"))?
         .into_iter()
-        .map(|arr| Candle {
-            open_time: arr[0].as_u64().unwrap_or(0),
-            open: arr[1].as_str().unwrap_or("0").to_string(),
-            high: arr[2].as_str().unwrap_or("0").to_string(),
-            low: arr[3].as_str().unwrap_or("0").to_string(),
-            close: arr[4].as_str().unwrap_or("0").to_string(),
-            volume: arr[5].as_str().unwrap_or("0").to_string(),
-            close_time: arr[6].as_u64().unwrap_or(0),
-            quote_volume: arr[7].as_str().unwrap_or("0").to_string(),
-            number_of_trades: arr[8].as_u64().unwrap_or(0),
-            taker_buy_base: arr[9].as_str().unwrap_or("0").to_string(),
-            taker_buy_quote: arr[10].as_str().unwrap_or("0").to_string(),
-            ignore: arr[11].as_str().unwrap_or("0").to_string(),
+        .partition::<Vec<_>, _>(validate_candle);
+    Ok(KlineBatch {
+        candles,
+        rejected: rejected.iter().map(|c| c.open_time).collect(),
+    })
+}
+
+/// The REST klines array. `None` when the body isn't an array of rows or a
+/// row is short; fields of the wrong type read as zero. A row whose close
+/// time is before `now_ms` (server time) is marked closed.
+fn parse_klines(body: &str, now_ms: u64) -> Option<Vec<Candle>> {
+    let rows: Vec<Vec<Value>> = serde_json::from_str(body).ok()?;
+    rows.iter()
+        .map(|arr| {
+            if arr.len() < 12 {
+                return None;
+            }
+            let text = |idx: usize| arr[idx].as_str().unwrap_or("0").to_string();
+            let close_time = arr[6].as_u64().unwrap_or(0);
+            Some(Candle {
+                open_time: arr[0].as_u64().unwrap_or(0),
+                open: text(1),
+                high: text(2),
+                low: text(3),
+                close: text(4),
+                volume: text(5),
+                close_time,
+                quote_volume: text(7),
+                number_of_trades: arr[8].as_u64().unwrap_or(0),
+                taker_buy_base: text(9),
+                taker_buy_quote: text(10),
+                ignore: text(11),
+                is_closed: close_time < now_ms,
+            })
         })
-        .collect();
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::transport::mock::MockHttp;
+
+    const KLINE_ROW: &str = r#"[1700000000000,"100.5","101","99","100.75","12.5",1700000059999,"1259.4",42,"6","603.1","0"]"#;
+
+    #[tokio::test]
+    async fn fetch_parses_canned_klines_and_sends_the_window() {
+        // High below low: dropped here rather than by every consumer.
+        let bad = r#"[1700000060000,"100","99","101","100","1",1700000119999,"0",1,"0","0","0"]"#;
+        let http = MockHttp::default().respond(200, &format!("[{KLINE_ROW},{bad}]"));
+        let window = KlineWindow {
+            start_ms: 1,
+            end_ms: 2,
+        };
+        let batch = fetch_kline_window_with(&http, "BTCUSDT", "1m", 500, Some(window))
+            .await
+            .unwrap();
+        assert_eq!(batch.rejected, vec![1700000060000]);
+        let candles = batch.candles;
+        assert_eq!(candles.len(), 1);
+        assert_eq!(candles[0].close, "100.75");
+        assert_eq!(candles[0].number_of_trades, 42);
+        let queries = http.queries.lock().unwrap();
+        assert!(queries[0].contains(&("startTime".to_string(), "1".to_string())));
+        assert!(queries[0].contains(&("limit".to_string(), "500".to_string())));
+    }
+
+    #[tokio::test]
+    async fn fetch_reports_rate_limits_statuses_and_bad_bodies() {
+        let http = MockHttp::default()
+            .rate_limited("9")
+            .respond(503, "")
+            .respond(400, "{\"code\": -1121, \"msg\": \"Invalid symbol.\"}")
+            .respond(200, "{\"code\": -1121}")
+            .respond(200, "[[1700000000000, \"1\"]]");
+        let fetch = || fetch_kline_window_with(&http, "BTCUSDT", "1m", 10, None);
+        assert!(matches!(
+            fetch().await,
+            Err(FetchError::RateLimited { retry_after }) if retry_after == Duration::from_secs(9)
+        ));
+        let unavailable = fetch().await.unwrap_err();
+        assert!(matches!(unavailable, FetchError::Status(503)));
+        assert!(!unavailable.is_rejected());
+        assert!(fetch().await.unwrap_err().is_rejected());
+        // Neither an error object nor a short row is taken as candles.
+        assert!(matches!(
+            fetch().await,
+            Err(FetchError::Malformed("klines"))
+        ));
+        assert!(matches!(
+            fetch().await,
+            Err(FetchError::Malformed("klines"))
+        ));
+    }
+
+    #[test]
+    fn rest_rows_are_closed_once_their_close_time_has_passed() {
+        let body = format!("[{KLINE_ROW}]");
+        let close_time = 1_700_000_059_999;
+        assert!(!parse_klines(&body, close_time).unwrap()[0].is_closed);
+        assert!(parse_klines(&body, close_time + 1).unwrap()[0].is_closed);
+    }
 
-    Ok(candles)
+    #[test]
+    fn retry_after_header_is_parsed_and_capped() {
+        assert_eq!(parse_retry_after(Some("7")), Duration::from_secs(7));
+        assert_eq!(parse_retry_after(None), DEFAULT_RETRY_AFTER);
+        assert_eq!(parse_retry_after(Some("soon")), DEFAULT_RETRY_AFTER);
+        assert_eq!(parse_retry_after(Some("86400")), MAX_RETRY_AFTER);
+    }
 }
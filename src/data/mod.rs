@@ -1,11 +1,20 @@
+pub mod candles;
+pub mod coordinator;
 pub mod fetch;
 pub mod orderbook;
 pub mod prices;
+pub mod reconnect;
+pub mod server;
+pub mod stats;
 pub mod stream;
+pub mod symbols;
+pub mod ticker;
 pub mod trades;
+pub mod transport;
 
+pub use candles::{merge_candles, validate_candle};
 pub use fetch::*;
 pub use orderbook::OrderBook;
-pub use prices::WatchPrice;
-pub use stream::*;
+pub use prices::{ChangeBaseline, DailyAnchors, WatchPrice};
+pub use ticker::Ticker24h;
 pub use trades::Trade;
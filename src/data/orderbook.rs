@@ -1,8 +1,12 @@
-use futures_util::StreamExt;
+use crate::data::fetch::{get_json, FetchError};
+use crate::data::reconnect::{forward_frames, ReconnectPolicy};
+use crate::data::stats::StreamStats;
+use crate::data::transport::{HttpGet, WsConnect};
 use serde_json::Value;
+use std::sync::Arc;
 use tokio::task::JoinHandle;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct OrderBookEntry {
     pub price: f64,
     pub quantity: f64,
@@ -16,90 +20,110 @@ pub struct OrderBook {
     pub last_update: u64,
 }
 
-#[allow(dead_code)]
-pub async fn fetch_orderbook(symbol: &str) -> Result<OrderBook, reqwest::Error> {
-    let client = reqwest::Client::new();
-    let url = format!(
-        "https://api.binance.com/api/v3/depth?symbol={}&limit=20",
-        symbol
-    );
+impl OrderBook {
+    /// Gap between the best ask and best bid as a percent of their midpoint.
+    pub fn spread_pct(&self) -> Option<f64> {
+        let bid = self.bids.first()?.price;
+        let ask = self.asks.first()?.price;
+        let mid = (bid + ask) / 2.0;
+        (mid > 0.0).then(|| (ask - bid) / mid * 100.0)
+    }
+}
 
-    let res = client.get(&url).send().await?.json::<Value>().await?;
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Buy,
+    Sell,
+}
 
-    let bids: Vec<OrderBookEntry> = res
-        .get("bids")
-        .and_then(|v| v.as_array())
-        .unwrap_or(&vec![])
-        .iter()
-        .filter_map(|entry| {
-            let arr = entry.as_array()?;
-            Some(OrderBookEntry {
-                price: arr[0].as_str()?.parse().ok()?,
-                quantity: arr[1].as_str()?.parse().ok()?,
-            })
-        })
-        .collect();
+impl Side {
+    pub fn label(self) -> &'static str {
+        match self {
+            Side::Buy => "Buy",
+            Side::Sell => "Sell",
+        }
+    }
+}
 
-    let asks: Vec<OrderBookEntry> = res
-        .get("asks")
-        .and_then(|v| v.as_array())
-        .unwrap_or(&vec![])
-        .iter()
-        .filter_map(|entry| {
-            let arr = entry.as_array()?;
-            Some(OrderBookEntry {
-                price: arr[0].as_str()?.parse().ok()?,
-                quantity: arr[1].as_str()?.parse().ok()?,
-            })
-        })
-        .collect();
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FillEstimate {
+    pub avg_price: f64,
+    pub slippage_pct: f64,
+    pub filled: f64,
+    pub unfilled: f64,
+}
 
-    Ok(OrderBook {
-        bids,
-        asks,
-        last_update: std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs(),
-    })
+/// Walks the asks (buy) or bids (sell) from the top of book and returns the
+/// volume-weighted fill for `size`, reporting any size the book can't absorb.
+pub fn simulate_fill(book: &OrderBook, side: Side, size: f64) -> FillEstimate {
+    let levels = match side {
+        Side::Buy => &book.asks,
+        Side::Sell => &book.bids,
+    };
+
+    let mut remaining = size.max(0.0);
+    let mut filled = 0.0;
+    let mut notional = 0.0;
+
+    for level in levels {
+        if remaining <= 0.0 {
+            break;
+        }
+        let take = level.quantity.min(remaining);
+        filled += take;
+        notional += take * level.price;
+        remaining -= take;
+    }
+
+    let avg_price = if filled > 0.0 { notional / filled } else { 0.0 };
+    let slippage_pct = match levels.first() {
+        Some(best) if filled > 0.0 && best.price > 0.0 => match side {
+            Side::Buy => (avg_price - best.price) / best.price * 100.0,
+            Side::Sell => (best.price - avg_price) / best.price * 100.0,
+        },
+        _ => 0.0,
+    };
+
+    FillEstimate {
+        avg_price,
+        slippage_pct,
+        filled,
+        unfilled: remaining,
+    }
+}
+
+#[allow(dead_code)]
+pub async fn fetch_orderbook(http: &dyn HttpGet, symbol: &str) -> Result<OrderBook, FetchError> {
+    let query = [("symbol", symbol.to_string()), ("limit", "20".to_string())];
+    let json = get_json(
+        http,
+        "https://api.binance.com/api/v3/depth",
+        &query,
+        "depth",
+    )
+    .await?;
+    parse_orderbook(&json).ok_or(FetchError::Malformed("depth"))
 }
 
-pub fn stream_orderbook(symbol: &str) -> (tokio::sync::mpsc::Receiver<OrderBook>, JoinHandle<()>) {
+pub fn stream_orderbook(
+    connector: Arc<dyn WsConnect>,
+    symbol: &str,
+    policy: ReconnectPolicy,
+    stats: Arc<StreamStats>,
+) -> (tokio::sync::mpsc::Receiver<OrderBook>, JoinHandle<()>) {
     let (tx, rx) = tokio::sync::mpsc::channel(100);
     let symbol_lower = symbol.to_lowercase();
     let url = format!(
         "wss://stream.binance.com:9443/ws/{}@depth20@100ms",
         symbol_lower
     );
+    stats.set_endpoints(vec![url.clone()]);
 
     let handle = tokio::spawn(async move {
-        loop {
-            match tokio_tungstenite::connect_async(&url).await {
-                Ok((ws_stream, _)) => {
-                    let (mut _write, mut read) = ws_stream.split();
-
-                    while let Some(msg) = read.next().await {
-                        match msg {
-                            Ok(tokio_tungstenite::tungstenite::Message::Text(text)) => {
-                                if let Ok(json) = serde_json::from_str::<Value>(&text) {
-                                    if let Some(book) = parse_orderbook(&json) {
-                                        if tx.send(book).await.is_err() {
-                                            return;
-                                        }
-                                    }
-                                }
-                            }
-                            Ok(tokio_tungstenite::tungstenite::Message::Close(_)) => break,
-                            Err(_) => break,
-                            _ => {}
-                        }
-                    }
-                }
-                Err(_) => {
-                    tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
-                }
-            }
-        }
+        forward_frames(&*connector, &url, policy, &stats, &tx, |json| {
+            parse_orderbook(json).into()
+        })
+        .await;
     });
 
     (rx, handle)
@@ -141,3 +165,95 @@ fn parse_orderbook(json: &Value) -> Option<OrderBook> {
             .as_secs(),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::transport::mock::{MockHttp, MockWs, Session};
+    use std::time::Duration;
+    use tokio_tungstenite::tungstenite::Message;
+
+    const DEPTH: &str =
+        r#"{"lastUpdateId":1,"bids":[["99.5","2.0"],["99.0","1.0"]],"asks":[["100.5","3.0"]]}"#;
+
+    #[tokio::test]
+    async fn depth_snapshot_and_stream_parse_through_the_seams() {
+        let http = MockHttp::default().respond(200, DEPTH);
+        let book = fetch_orderbook(&http, "BTCUSDT").await.unwrap();
+        assert_eq!(book.bids, vec![entry(99.5, 2.0), entry(99.0, 1.0)]);
+        assert_eq!(
+            http.queries.lock().unwrap()[0],
+            vec![
+                ("symbol".to_string(), "BTCUSDT".to_string()),
+                ("limit".to_string(), "20".to_string())
+            ]
+        );
+
+        let connector = MockWs::new(vec![Session::Messages(vec![
+            Message::Text("{}".to_string()),
+            Message::Text(DEPTH.to_string()),
+        ])]);
+        let policy = ReconnectPolicy {
+            max_attempts: 1,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(1),
+        };
+        let stats = Arc::new(StreamStats::default());
+        let (mut rx, handle) =
+            stream_orderbook(Arc::new(connector), "BTCUSDT", policy, stats.clone());
+        assert_eq!(rx.recv().await.unwrap().asks, vec![entry(100.5, 3.0)]);
+        assert!(rx.recv().await.is_none());
+        handle.await.unwrap();
+        assert_eq!(stats.snapshot().parse_failures, 1);
+    }
+
+    fn entry(price: f64, quantity: f64) -> OrderBookEntry {
+        OrderBookEntry { price, quantity }
+    }
+
+    fn book() -> OrderBook {
+        OrderBook {
+            bids: vec![entry(99.0, 1.0), entry(98.0, 2.0)],
+            asks: vec![entry(101.0, 1.0), entry(102.0, 2.0)],
+            last_update: 0,
+        }
+    }
+
+    #[test]
+    fn spread_is_relative_to_the_midpoint() {
+        assert_eq!(book().spread_pct(), Some(2.0));
+        let one_sided = OrderBook {
+            asks: Vec::new(),
+            ..book()
+        };
+        assert_eq!(one_sided.spread_pct(), None);
+    }
+
+    #[test]
+    fn buy_walks_asks_and_reports_slippage() {
+        let fill = simulate_fill(&book(), Side::Buy, 2.0);
+        assert_eq!(fill.filled, 2.0);
+        assert_eq!(fill.unfilled, 0.0);
+        assert!((fill.avg_price - 101.5).abs() < 1e-9);
+        assert!((fill.slippage_pct - 0.5 / 101.0 * 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn sell_reports_partial_fill_when_book_is_thin() {
+        let fill = simulate_fill(&book(), Side::Sell, 5.0);
+        assert_eq!(fill.filled, 3.0);
+        assert_eq!(fill.unfilled, 2.0);
+        assert!((fill.avg_price - 295.0 / 3.0).abs() < 1e-9);
+        assert!(fill.slippage_pct > 0.0);
+    }
+
+    #[test]
+    fn empty_side_fills_nothing() {
+        let mut empty = book();
+        empty.asks.clear();
+        let fill = simulate_fill(&empty, Side::Buy, 1.0);
+        assert_eq!(fill.filled, 0.0);
+        assert_eq!(fill.unfilled, 1.0);
+        assert_eq!(fill.slippage_pct, 0.0);
+    }
+}
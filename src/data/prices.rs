@@ -1,6 +1,16 @@
-use futures_util::StreamExt;
+use crate::data::reconnect::{forward_frames, Frame, ReconnectPolicy};
+use crate::data::stats::StreamStats;
+use crate::data::transport::WsConnect;
+use crate::data::Candle;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use tokio::task::JoinHandle;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::task::{JoinHandle, JoinSet};
+
+/// Streams per combined connection. Binance accepts up to 1024, but URLs for
+/// that many are long enough to be refused, so lists are split well before.
+pub const DEFAULT_MAX_STREAMS: usize = 200;
 
 #[derive(Debug, Clone)]
 pub struct WatchPrice {
@@ -9,65 +19,347 @@ pub struct WatchPrice {
     pub change_pct: f64,
 }
 
+/// Which price the watchlist shows. The 24h change is always measured from
+/// the 24h open, whichever price it is compared against.
+///
+/// | Source            | Stream                                  | Price field |
+/// |-------------------|-----------------------------------------|-------------|
+/// | `Last`            | spot `@miniTicker`                      | `c`         |
+/// | `WeightedAverage` | spot `@ticker`                          | `w`         |
+/// | `Mark`            | futures `@markPrice@1s` + `@miniTicker` | `p`         |
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PriceSource {
+    /// Last traded price.
+    #[default]
+    Last,
+    /// Volume-weighted average price over the last 24h.
+    WeightedAverage,
+    /// USDⓈ-M perpetual mark price, which ignores one-off last-trade spikes.
+    Mark,
+}
+
+/// What the watchlist's percent change is measured from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ChangeBaseline {
+    /// The ticker's rolling 24h open, as streamed.
+    #[default]
+    Open24h,
+    /// The open of the current UTC day.
+    SessionOpen,
+    /// The close of the previous UTC day.
+    PrevClose,
+}
+
+/// A symbol's daily candle prices that the non-streamed baselines need,
+/// valid for one UTC day.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DailyAnchors {
+    /// Days since the Unix epoch the anchors belong to.
+    pub day: u64,
+    pub session_open: f64,
+    pub prev_close: f64,
+}
+
+impl DailyAnchors {
+    /// Reads the anchors from the last two daily candles, the newest being
+    /// today's.
+    pub fn from_daily_candles(candles: &[Candle]) -> Option<Self> {
+        let [.., previous, today] = candles else {
+            return None;
+        };
+        Some(Self {
+            day: today.open_time / 86_400_000,
+            session_open: today.open.parse().ok()?,
+            prev_close: previous.close.parse().ok()?,
+        })
+    }
+}
+
+impl ChangeBaseline {
+    /// Percent change of `price` from this baseline. Falls back to the
+    /// streamed 24h change until the anchors for the baseline are known.
+    pub fn change_pct(self, price: &WatchPrice, anchors: Option<&DailyAnchors>) -> f64 {
+        let base = match (self, anchors) {
+            (ChangeBaseline::SessionOpen, Some(anchors)) => anchors.session_open,
+            (ChangeBaseline::PrevClose, Some(anchors)) => anchors.prev_close,
+            _ => return price.change_pct,
+        };
+        if base > 0.0 {
+            (price.last_price - base) / base * 100.0
+        } else {
+            price.change_pct
+        }
+    }
+}
+
+impl PriceSource {
+    fn suffixes(self) -> &'static [&'static str] {
+        match self {
+            PriceSource::Last => &["miniTicker"],
+            PriceSource::WeightedAverage => &["ticker"],
+            PriceSource::Mark => &["markPrice@1s", "miniTicker"],
+        }
+    }
+
+    /// One combined-stream URL per chunk of symbols, each carrying at most
+    /// `max_streams` streams (but always at least one symbol).
+    fn stream_urls(self, symbols: &[String], max_streams: usize) -> Vec<String> {
+        let per_chunk = (max_streams / self.suffixes().len()).max(1);
+        symbols
+            .chunks(per_chunk)
+            .map(|chunk| self.stream_url(chunk))
+            .collect()
+    }
+
+    fn stream_url(self, symbols: &[String]) -> String {
+        let suffixes = self.suffixes();
+        let streams = symbols
+            .iter()
+            .flat_map(|symbol| {
+                let symbol = symbol.to_lowercase();
+                suffixes
+                    .iter()
+                    .map(move |suffix| format!("{symbol}@{suffix}"))
+            })
+            .collect::<Vec<_>>()
+            .join("/");
+        let host = match self {
+            PriceSource::Mark => "wss://fstream.binance.com",
+            _ => "wss://stream.binance.com:9443",
+        };
+        format!("{host}/stream?streams={streams}")
+    }
+}
+
 pub fn stream_watchlist_prices(
+    connector: Arc<dyn WsConnect>,
     symbols: &[String],
+    source: PriceSource,
+    max_streams: usize,
+    policy: ReconnectPolicy,
+    stats: Arc<StreamStats>,
 ) -> (tokio::sync::mpsc::Receiver<WatchPrice>, JoinHandle<()>) {
     let (tx, rx) = tokio::sync::mpsc::channel(500);
-    let streams = symbols
-        .iter()
-        .map(|symbol| format!("{}@miniTicker", symbol.to_lowercase()))
-        .collect::<Vec<_>>()
-        .join("/");
-
-    let url = format!("wss://stream.binance.com:9443/stream?streams={}", streams);
+    let urls = source.stream_urls(symbols, max_streams);
+    stats.set_endpoints(urls.clone());
 
+    // Each chunk reconnects on its own; the feed only ends once every chunk
+    // has given up. Dropping the set when this task is aborted stops them all.
     let handle = tokio::spawn(async move {
-        loop {
-            match tokio_tungstenite::connect_async(&url).await {
-                Ok((ws_stream, _)) => {
-                    let (mut _write, mut read) = ws_stream.split();
-
-                    while let Some(msg) = read.next().await {
-                        match msg {
-                            Ok(tokio_tungstenite::tungstenite::Message::Text(text)) => {
-                                if let Ok(json) = serde_json::from_str::<Value>(&text) {
-                                    if let Some(price) = parse_mini_ticker(&json) {
-                                        if tx.send(price).await.is_err() {
-                                            return;
-                                        }
-                                    }
-                                }
-                            }
-                            Ok(tokio_tungstenite::tungstenite::Message::Close(_)) => break,
-                            Err(_) => break,
-                            _ => {}
-                        }
-                    }
-                }
-                Err(_) => {
-                    tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
-                }
-            }
+        let mut chunks = JoinSet::new();
+        for url in urls {
+            chunks.spawn(stream_price_chunk(
+                connector.clone(),
+                url,
+                source,
+                policy,
+                stats.clone(),
+                tx.clone(),
+            ));
         }
+        while chunks.join_next().await.is_some() {}
     });
 
     (rx, handle)
 }
 
-fn parse_mini_ticker(json: &Value) -> Option<WatchPrice> {
+async fn stream_price_chunk(
+    connector: Arc<dyn WsConnect>,
+    url: String,
+    source: PriceSource,
+    policy: ReconnectPolicy,
+    stats: Arc<StreamStats>,
+    tx: tokio::sync::mpsc::Sender<WatchPrice>,
+) {
+    // Opens outlive a reconnect: they are 24h values, refreshed by the next
+    // mini ticker anyway.
+    let mut opens = HashMap::new();
+    forward_frames(&*connector, &url, policy, &stats, &tx, |json| {
+        match parse_price(source, json, &mut opens) {
+            Some(price) => Frame::Item(price),
+            // Mark mode reads its opens from messages that carry no price,
+            // so only the other sources count misses.
+            None if source == PriceSource::Mark => Frame::Skip,
+            None => Frame::Invalid,
+        }
+    })
+    .await;
+}
+
+/// Reads one combined-stream message for `source`. Mark price events carry no
+/// 24h open, so the futures mini ticker's open is remembered in `opens` and
+/// mark prices are only reported once it is known.
+fn parse_price(
+    source: PriceSource,
+    json: &Value,
+    opens: &mut HashMap<String, f64>,
+) -> Option<WatchPrice> {
     let data = json.get("data")?;
     let symbol = data.get("s")?.as_str()?.to_string();
-    let close = data.get("c")?.as_str()?.parse::<f64>().ok()?;
-    let open = data.get("o")?.as_str()?.parse::<f64>().ok()?;
+    let field = |key: &str| data.get(key)?.as_str()?.parse::<f64>().ok();
+
+    let (price, open) = match source {
+        PriceSource::Last => (field("c")?, field("o")?),
+        PriceSource::WeightedAverage => (field("w")?, field("o")?),
+        PriceSource::Mark => {
+            if data.get("e")?.as_str()? != "markPriceUpdate" {
+                opens.insert(symbol, field("o")?);
+                return None;
+            }
+            (field("p")?, *opens.get(&symbol)?)
+        }
+    };
     let change_pct = if open > 0.0 {
-        ((close - open) / open) * 100.0
+        ((price - open) / open) * 100.0
     } else {
         0.0
     };
 
     Some(WatchPrice {
         symbol,
-        last_price: close,
+        last_price: price,
         change_pct,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn spot_sources_read_their_price_field() {
+        let ticker = json!({
+            "stream": "btcusdt@ticker",
+            "data": {"e": "24hrTicker", "s": "BTCUSDT", "o": "100.0", "c": "110.0", "w": "105.0"}
+        });
+        let mut opens = HashMap::new();
+
+        let last = parse_price(PriceSource::Last, &ticker, &mut opens).unwrap();
+        assert_eq!(last.last_price, 110.0);
+        assert!((last.change_pct - 10.0).abs() < 1e-9);
+
+        let vwap = parse_price(PriceSource::WeightedAverage, &ticker, &mut opens).unwrap();
+        assert_eq!(vwap.last_price, 105.0);
+        assert!((vwap.change_pct - 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn baselines_measure_from_their_anchor() {
+        let candle = |open_time: u64, open: f64, close: f64| Candle {
+            close_time: open_time + 86_399_999,
+            ..crate::data::fetch::candle(open_time, open, open, close, close)
+        };
+        let anchors = DailyAnchors::from_daily_candles(&[
+            candle(0, 90.0, 80.0),
+            candle(86_400_000, 100.0, 110.0),
+        ])
+        .unwrap();
+        assert_eq!(anchors.day, 1);
+        let price = WatchPrice {
+            symbol: "BTCUSDT".to_string(),
+            last_price: 120.0,
+            change_pct: 3.0,
+        };
+
+        assert_eq!(
+            ChangeBaseline::Open24h.change_pct(&price, Some(&anchors)),
+            3.0
+        );
+        assert!(
+            (ChangeBaseline::SessionOpen.change_pct(&price, Some(&anchors)) - 20.0).abs() < 1e-9
+        );
+        assert!((ChangeBaseline::PrevClose.change_pct(&price, Some(&anchors)) - 50.0).abs() < 1e-9);
+        assert_eq!(ChangeBaseline::PrevClose.change_pct(&price, None), 3.0);
+        assert!(DailyAnchors::from_daily_candles(&[candle(0, 1.0, 1.0)]).is_none());
+    }
+
+    #[tokio::test]
+    async fn mark_stream_skips_open_only_messages_without_counting_failures() {
+        use crate::data::transport::mock::{MockWs, Session};
+        use std::time::Duration;
+        use tokio_tungstenite::tungstenite::Message;
+
+        let frames = [
+            json!({"stream": "btcusdt@miniTicker",
+                   "data": {"e": "24hrMiniTicker", "s": "BTCUSDT", "o": "100.0", "c": "150.0"}}),
+            json!({"stream": "btcusdt@markPrice@1s",
+                   "data": {"e": "markPriceUpdate", "s": "BTCUSDT", "p": "102.0"}}),
+        ];
+        let connector = MockWs::new(vec![Session::Messages(
+            frames
+                .iter()
+                .map(|f| Message::Text(f.to_string()))
+                .collect(),
+        )]);
+        let policy = ReconnectPolicy {
+            max_attempts: 1,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(1),
+        };
+        let stats = Arc::new(StreamStats::default());
+        let (mut rx, handle) = stream_watchlist_prices(
+            Arc::new(connector),
+            &["BTCUSDT".to_string()],
+            PriceSource::Mark,
+            200,
+            policy,
+            stats.clone(),
+        );
+
+        let price = rx.recv().await.unwrap();
+        assert_eq!(price.last_price, 102.0);
+        assert!(rx.recv().await.is_none());
+        handle.await.unwrap();
+        assert_eq!(stats.snapshot().messages, 2);
+        assert_eq!(stats.snapshot().parse_failures, 0);
+    }
+
+    #[test]
+    fn mark_price_waits_for_the_open() {
+        let mark = json!({
+            "stream": "btcusdt@markPrice@1s",
+            "data": {"e": "markPriceUpdate", "s": "BTCUSDT", "p": "102.0"}
+        });
+        let mini = json!({
+            "stream": "btcusdt@miniTicker",
+            "data": {"e": "24hrMiniTicker", "s": "BTCUSDT", "o": "100.0", "c": "150.0"}
+        });
+        let mut opens = HashMap::new();
+
+        assert!(parse_price(PriceSource::Mark, &mark, &mut opens).is_none());
+        assert!(parse_price(PriceSource::Mark, &mini, &mut opens).is_none());
+        let price = parse_price(PriceSource::Mark, &mark, &mut opens).unwrap();
+        assert_eq!(price.last_price, 102.0);
+        assert!((price.change_pct - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn large_watchlists_are_split_into_chunks() {
+        let symbols: Vec<String> = (0..300).map(|i| format!("SYM{i}USDT")).collect();
+        let stream_counts = |urls: Vec<String>| -> Vec<usize> {
+            urls.iter()
+                .map(|url| url.split_once("streams=").unwrap().1.split('/').count())
+                .collect()
+        };
+
+        let urls = PriceSource::Last.stream_urls(&symbols, DEFAULT_MAX_STREAMS);
+        assert!(urls[1].contains("sym200usdt@miniTicker"));
+        assert_eq!(stream_counts(urls), vec![200, 100]);
+        // Two streams per symbol for mark prices.
+        assert_eq!(
+            stream_counts(PriceSource::Mark.stream_urls(&symbols, DEFAULT_MAX_STREAMS)),
+            vec![200, 200, 200]
+        );
+        assert_eq!(PriceSource::Mark.stream_urls(&symbols[..1], 1).len(), 1);
+    }
+
+    #[test]
+    fn mark_source_uses_the_futures_host() {
+        let url = PriceSource::Mark.stream_url(&["BTCUSDT".to_string()]);
+        assert_eq!(
+            url,
+            "wss://fstream.binance.com/stream?streams=btcusdt@markPrice@1s/btcusdt@miniTicker"
+        );
+    }
+}
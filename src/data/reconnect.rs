@@ -0,0 +1,246 @@
+use crate::data::stats::StreamStats;
+use crate::data::transport::WsConnect;
+use futures_util::StreamExt;
+use serde_json::Value;
+use std::future::Future;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::Message;
+
+/// How a stream task retries failed connections. Consecutive failures back
+/// off exponentially; after `max_attempts` of them the task gives up so the
+/// app can show the feed as degraded and wait for a manual refresh.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReconnectPolicy {
+    /// Zero means retry forever.
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl ReconnectPolicy {
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+
+    /// Delay before the next attempt after `failures` consecutive failures.
+    pub fn backoff(&self, failures: u32) -> Duration {
+        let factor = 1u32 << failures.saturating_sub(1).min(16);
+        self.base_delay.saturating_mul(factor).min(self.max_delay)
+    }
+
+    pub fn exhausted(&self, failures: u32) -> bool {
+        self.max_attempts > 0 && failures >= self.max_attempts
+    }
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+/// Calls `connect` until it succeeds, backing off between failures and
+/// recording the backoff in `stats`. Returns `None` once the policy's attempt
+/// budget is spent.
+pub async fn connect_with_retry<T, E, F, Fut>(
+    policy: ReconnectPolicy,
+    stats: &StreamStats,
+    mut connect: F,
+) -> Option<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    E: std::fmt::Display,
+{
+    let mut failures = 0;
+    loop {
+        match connect().await {
+            Ok(conn) => {
+                stats.record_connected();
+                return Some(conn);
+            }
+            Err(err) => {
+                stats.record_error(err);
+                failures += 1;
+                if policy.exhausted(failures) {
+                    return None;
+                }
+                let delay = policy.backoff(failures);
+                stats.record_connect_failure(failures, delay);
+                tokio::time::sleep(delay).await;
+            }
+        }
+    }
+}
+
+/// What a stream's parser made of one JSON message.
+pub enum Frame<T> {
+    Item(T),
+    /// Valid, but nothing to send (e.g. bookkeeping a later message needs).
+    Skip,
+    /// Not the expected shape; counted as a parse failure.
+    Invalid,
+}
+
+impl<T> From<Option<T>> for Frame<T> {
+    fn from(parsed: Option<T>) -> Self {
+        parsed.map_or(Frame::Invalid, Frame::Item)
+    }
+}
+
+/// The read loop shared by the depth, trade and price streams: connects to
+/// `url` through `connector` per `policy`, sends what `parse` makes of each
+/// JSON text message to `tx`, and reconnects whenever the socket closes or
+/// errors. Returns once the policy gives up or `tx` is dropped.
+pub async fn forward_frames<T>(
+    connector: &dyn WsConnect,
+    url: &str,
+    policy: ReconnectPolicy,
+    stats: &StreamStats,
+    tx: &mpsc::Sender<T>,
+    mut parse: impl FnMut(&Value) -> Frame<T>,
+) {
+    let mut connected_once = false;
+    loop {
+        let Some(mut read) = connect_with_retry(policy, stats, || connector.connect(url)).await
+        else {
+            return;
+        };
+        if connected_once {
+            stats.record_reconnect();
+        }
+        connected_once = true;
+
+        while let Some(msg) = read.next().await {
+            match msg {
+                Ok(Message::Text(text)) => {
+                    stats.record_message();
+                    let frame = serde_json::from_str::<Value>(&text)
+                        .map_or(Frame::Invalid, |json| parse(&json));
+                    match frame {
+                        Frame::Item(item) => {
+                            if tx.send(item).await.is_err() {
+                                return;
+                            }
+                        }
+                        Frame::Skip => {}
+                        Frame::Invalid => stats.record_parse_failure(),
+                    }
+                }
+                Ok(Message::Close(_)) => break,
+                Err(err) => {
+                    stats.record_error(err);
+                    break;
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fast_policy(max_attempts: u32) -> ReconnectPolicy {
+        ReconnectPolicy {
+            max_attempts,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(4),
+        }
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        let policy = ReconnectPolicy::new(5);
+        let delays: Vec<u64> = (1..=7).map(|n| policy.backoff(n).as_secs()).collect();
+        assert_eq!(delays, vec![1, 2, 4, 8, 16, 30, 30]);
+        assert!(!policy.exhausted(4));
+        assert!(policy.exhausted(5));
+        assert!(!ReconnectPolicy::new(0).exhausted(u32::MAX));
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_attempts() {
+        let mut calls = 0;
+        let stats = StreamStats::default();
+        let result: Option<()> = connect_with_retry(fast_policy(3), &stats, || {
+            calls += 1;
+            async { Err::<(), _>("offline") }
+        })
+        .await;
+
+        assert_eq!(result, None);
+        assert_eq!(calls, 3);
+        assert_eq!(stats.snapshot().failures, 2);
+        assert_eq!(stats.snapshot().last_error.as_deref(), Some("offline"));
+    }
+
+    #[tokio::test]
+    async fn succeeds_once_connect_recovers() {
+        let mut calls = 0;
+        let stats = StreamStats::default();
+        let result = connect_with_retry(fast_policy(3), &stats, || {
+            calls += 1;
+            let attempt = calls;
+            async move {
+                if attempt < 3 {
+                    Err("offline")
+                } else {
+                    Ok(attempt)
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(result, Some(3));
+        assert_eq!(stats.snapshot().failures, 0);
+    }
+
+    #[tokio::test]
+    async fn forwards_parsed_frames_across_reconnects() {
+        use crate::data::transport::mock::{MockWs, Session};
+
+        let text = |body: &str| Message::Text(body.to_string());
+        let connector = MockWs::new(vec![
+            Session::Messages(vec![
+                text("1"),
+                text("not json"),
+                text("0"),
+                Message::Ping(vec![]),
+            ]),
+            Session::Refused,
+            Session::Messages(vec![text("2"), text("\"two\"")]),
+        ]);
+        let stats = StreamStats::default();
+        let (tx, mut rx) = mpsc::channel(8);
+        forward_frames(
+            &connector,
+            "wss://test",
+            fast_policy(2),
+            &stats,
+            &tx,
+            |json| match json.as_u64() {
+                Some(0) => Frame::Skip,
+                parsed => parsed.into(),
+            },
+        )
+        .await;
+        drop(tx);
+
+        let mut items = Vec::new();
+        while let Some(item) = rx.recv().await {
+            items.push(item);
+        }
+        assert_eq!(items, vec![1, 2]);
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.messages, 5);
+        assert_eq!(snapshot.parse_failures, 2);
+        assert_eq!(snapshot.reconnects, 1);
+    }
+}
@@ -0,0 +1,74 @@
+use crate::data::fetch::{get_checked, get_json, FetchError};
+use crate::data::transport::{HttpGet, ReqwestHttp};
+use serde_json::Value;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Clock skew beyond which timestamps shown in the UI are misleading and
+/// signed requests would be rejected.
+pub const MAX_CLOCK_SKEW: Duration = Duration::from_secs(1);
+
+/// The last skew [`measure_clock_skew`] found; zero until it has run.
+static CLOCK_SKEW_MS: AtomicI64 = AtomicI64::new(0);
+
+/// Checks that the REST API answers at all.
+pub async fn ping_server() -> Result<(), FetchError> {
+    let http = ReqwestHttp::default();
+    get_checked(&http, "https://api.binance.com/api/v3/ping", &[]).await?;
+    Ok(())
+}
+
+/// Binance's clock, in milliseconds since the epoch.
+pub async fn server_time() -> Result<u64, FetchError> {
+    server_time_with(&ReqwestHttp::default()).await
+}
+
+/// [`server_time`] over any [`HttpGet`].
+pub async fn server_time_with(http: &dyn HttpGet) -> Result<u64, FetchError> {
+    let json = get_json(http, "https://api.binance.com/api/v3/time", &[], "time").await?;
+    json.get("serverTime")
+        .and_then(Value::as_u64)
+        .ok_or(FetchError::Malformed("time"))
+}
+
+fn local_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// The local clock corrected by the last measured skew, in milliseconds
+/// since the epoch.
+pub fn server_now_ms() -> u64 {
+    local_millis().saturating_add_signed(CLOCK_SKEW_MS.load(Ordering::Relaxed))
+}
+
+/// How far the local clock is behind the server's, in milliseconds; negative
+/// when it runs ahead. The server time is assumed to be taken halfway
+/// between sending the request and receiving the reply.
+pub fn clock_skew_ms(server_ms: u64, sent_ms: u64, received_ms: u64) -> i64 {
+    server_ms as i64 - (sent_ms + received_ms) as i64 / 2
+}
+
+/// Round trip and clock skew measured against `/api/v3/time`.
+pub async fn measure_clock_skew() -> Result<(Duration, i64), FetchError> {
+    let sent = local_millis();
+    let server = server_time().await?;
+    let received = local_millis();
+    let skew = clock_skew_ms(server, sent, received);
+    CLOCK_SKEW_MS.store(skew, Ordering::Relaxed);
+    Ok((Duration::from_millis(received.saturating_sub(sent)), skew))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skew_is_measured_from_the_round_trip_midpoint() {
+        assert_eq!(clock_skew_ms(1_100, 1_000, 1_200), 0);
+        assert_eq!(clock_skew_ms(3_100, 1_000, 1_200), 2_000);
+        assert_eq!(clock_skew_ms(1_000, 1_500, 1_700), -600);
+    }
+}
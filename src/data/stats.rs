@@ -0,0 +1,164 @@
+use crate::data::coordinator::Feed;
+use std::fmt;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Counters for one feed's stream task. Bumping them is a relaxed atomic add,
+/// so they can sit on the per-message path.
+#[derive(Debug, Default)]
+pub struct StreamStats {
+    messages: AtomicU64,
+    parse_failures: AtomicU64,
+    reconnects: AtomicU64,
+    /// Consecutive failed connects; nonzero while backing off.
+    failures: AtomicU32,
+    backoff_ms: AtomicU64,
+    /// Only written on (re)subscribe, never per message.
+    endpoints: Mutex<Vec<String>>,
+    /// Why the socket last failed; cleared once a connect succeeds.
+    last_error: Mutex<Option<String>>,
+}
+
+/// A point-in-time copy of [`StreamStats`] for display.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StatsSnapshot {
+    pub messages: u64,
+    pub parse_failures: u64,
+    pub reconnects: u64,
+    pub failures: u32,
+    /// Wait before the next connect attempt; zero while connected.
+    pub backoff: Duration,
+    pub endpoints: Vec<String>,
+    pub last_error: Option<String>,
+}
+
+impl StreamStats {
+    pub fn record_message(&self) {
+        self.messages.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_parse_failure(&self) {
+        self.parse_failures.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_reconnect(&self) {
+        self.reconnects.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// A connect attempt failed and the task will wait `backoff` before the
+    /// next one.
+    pub fn record_connect_failure(&self, failures: u32, backoff: Duration) {
+        self.failures.store(failures, Ordering::Relaxed);
+        self.backoff_ms
+            .store(backoff.as_millis() as u64, Ordering::Relaxed);
+    }
+
+    pub fn record_connected(&self) {
+        self.failures.store(0, Ordering::Relaxed);
+        self.backoff_ms.store(0, Ordering::Relaxed);
+        self.set_error(None);
+    }
+
+    /// A connect attempt or an open socket failed with `error`.
+    pub fn record_error(&self, error: impl fmt::Display) {
+        self.set_error(Some(error.to_string()));
+    }
+
+    fn set_error(&self, error: Option<String>) {
+        if let Ok(mut last_error) = self.last_error.lock() {
+            *last_error = error;
+        }
+    }
+
+    pub fn set_endpoints(&self, endpoints: Vec<String>) {
+        if let Ok(mut current) = self.endpoints.lock() {
+            *current = endpoints;
+        }
+    }
+
+    pub fn reset(&self) {
+        self.messages.store(0, Ordering::Relaxed);
+        self.parse_failures.store(0, Ordering::Relaxed);
+        self.reconnects.store(0, Ordering::Relaxed);
+        self.record_connected();
+        self.set_endpoints(Vec::new());
+    }
+
+    pub fn snapshot(&self) -> StatsSnapshot {
+        StatsSnapshot {
+            messages: self.messages.load(Ordering::Relaxed),
+            parse_failures: self.parse_failures.load(Ordering::Relaxed),
+            reconnects: self.reconnects.load(Ordering::Relaxed),
+            failures: self.failures.load(Ordering::Relaxed),
+            backoff: Duration::from_millis(self.backoff_ms.load(Ordering::Relaxed)),
+            endpoints: self
+                .endpoints
+                .lock()
+                .map(|endpoints| endpoints.clone())
+                .unwrap_or_default(),
+            last_error: self
+                .last_error
+                .lock()
+                .map(|last_error| last_error.clone())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// One [`StreamStats`] per feed, shared between the stream tasks and the UI.
+#[derive(Debug, Default)]
+pub struct FeedStats {
+    kline: Arc<StreamStats>,
+    order_book: Arc<StreamStats>,
+    trades: Arc<StreamStats>,
+    prices: Arc<StreamStats>,
+}
+
+impl FeedStats {
+    pub fn get(&self, feed: Feed) -> &Arc<StreamStats> {
+        match feed {
+            Feed::Kline => &self.kline,
+            Feed::OrderBook => &self.order_book,
+            Feed::Trades => &self.trades,
+            Feed::Prices => &self.prices,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_until_reset() {
+        let stats = StreamStats::default();
+        stats.set_endpoints(vec!["wss://example/ws".to_string()]);
+        stats.record_message();
+        stats.record_message();
+        stats.record_parse_failure();
+        stats.record_reconnect();
+        stats.record_connect_failure(3, Duration::from_secs(4));
+        stats.record_error("connection reset");
+
+        let snapshot = stats.snapshot();
+        assert_eq!(
+            (
+                snapshot.messages,
+                snapshot.parse_failures,
+                snapshot.reconnects
+            ),
+            (2, 1, 1)
+        );
+        assert_eq!(snapshot.failures, 3);
+        assert_eq!(snapshot.backoff, Duration::from_secs(4));
+        assert_eq!(snapshot.endpoints, vec!["wss://example/ws"]);
+        assert_eq!(snapshot.last_error.as_deref(), Some("connection reset"));
+
+        stats.record_connected();
+        assert_eq!(stats.snapshot().backoff, Duration::ZERO);
+        assert_eq!(stats.snapshot().last_error, None);
+        stats.reset();
+        assert_eq!(stats.snapshot(), StatsSnapshot::default());
+    }
+}
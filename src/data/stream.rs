@@ -1,53 +1,199 @@
+use crate::data::candles::validate_candle;
+use crate::data::reconnect::{connect_with_retry, ReconnectPolicy};
+use crate::data::stats::StreamStats;
+use crate::data::transport::WsConnect;
 use crate::data::Candle;
 use futures_util::StreamExt;
 use serde_json::Value;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::task::JoinHandle;
-use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tokio_tungstenite::tungstenite::Message;
+
+/// Candles beyond the gap itself to refetch after a reconnect, so the candle
+/// that was live when the socket dropped gets its final values.
+const BACKFILL_EXTRA: u32 = 2;
+const MAX_BACKFILL: u32 = 1000;
+
+// Candles are the common case, so they travel unboxed.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone)]
+pub enum KlineEvent {
+    /// The socket (re)connected. `downtime` is how long since the last
+    /// message before the drop, and is `None` on the first connect.
+    Connected {
+        downtime: Option<Duration>,
+    },
+    Candle(Candle),
+    /// A kline that parsed but failed [`validate_candle`].
+    Rejected {
+        open_time: u64,
+    },
+}
+
+/// Length of a Binance kline interval such as `5m`, `4h` or `1d`.
+pub fn interval_duration(interval: &str) -> Option<Duration> {
+    let split = interval.len().checked_sub(1)?;
+    let (count, unit) = interval.split_at(split);
+    let count: u64 = count.parse().ok().filter(|count| *count > 0)?;
+    let unit_secs = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 3_600,
+        "d" => 86_400,
+        "w" => 604_800,
+        "M" => 2_592_000,
+        _ => return None,
+    };
+    Some(Duration::from_secs(count * unit_secs))
+}
+
+/// Whether `candle` spans exactly one `interval`, going by its open and close
+/// times. A candle left over from the previous timeframe's stream fails this,
+/// so it can't leak into the chart after a switch. Months vary in length, so
+/// `1M` accepts anything from 28 to 31 days.
+pub fn candle_matches_interval(candle: &Candle, interval: &str) -> bool {
+    let Some(step) = interval_duration(interval) else {
+        return false;
+    };
+    let span_ms = candle
+        .close_time
+        .saturating_add(1)
+        .saturating_sub(candle.open_time);
+    if interval.ends_with('M') {
+        let months = step.as_secs() / 2_592_000;
+        let day_ms = 86_400_000;
+        (months * 28 * day_ms..=months * 31 * day_ms).contains(&span_ms)
+    } else {
+        span_ms == step.as_millis() as u64
+    }
+}
+
+/// Kline intervals Binance serves, shortest first.
+const KLINE_INTERVALS: [&str; 15] = [
+    "1m", "3m", "5m", "15m", "30m", "1h", "2h", "4h", "6h", "8h", "12h", "1d", "3d", "1w", "1M",
+];
+
+/// Every kline interval the app can request, shortest first.
+pub fn supported_intervals() -> &'static [&'static str] {
+    &KLINE_INTERVALS
+}
+
+/// Checks `interval` against [`supported_intervals`], so a typo is reported
+/// instead of fetching nothing.
+pub fn validate_interval(interval: &str) -> Result<&'static str, String> {
+    supported_intervals()
+        .iter()
+        .copied()
+        .find(|supported| *supported == interval)
+        .ok_or_else(|| {
+            format!(
+                "unknown interval \"{}\"; expected one of {}",
+                interval,
+                supported_intervals().join(", ")
+            )
+        })
+}
+
+/// The shortest interval longer than `interval` that covers `span` in at most
+/// `max_candles` candles, or the longest one when none does. `None` when
+/// `interval` is already the longest or isn't a known interval.
+pub fn coarser_interval(
+    interval: &str,
+    span: Duration,
+    max_candles: usize,
+) -> Option<&'static str> {
+    let current = interval_duration(interval)?;
+    let longer: Vec<&'static str> = supported_intervals()
+        .iter()
+        .copied()
+        .filter(|candidate| interval_duration(candidate).is_some_and(|step| step > current))
+        .collect();
+    longer
+        .iter()
+        .copied()
+        .find(|candidate| {
+            interval_duration(candidate).is_some_and(|step| {
+                (span.as_secs_f64() / step.as_secs_f64()).ceil() as usize <= max_candles
+            })
+        })
+        .or(longer.last().copied())
+}
+
+/// How many recent candles to refetch to cover `downtime` on `interval`.
+pub fn backfill_limit(downtime: Duration, interval: &str) -> u32 {
+    let missed = interval_duration(interval)
+        .map(|step| downtime.as_secs_f64() / step.as_secs_f64())
+        .unwrap_or(0.0)
+        .ceil();
+    (missed as u32)
+        .saturating_add(BACKFILL_EXTRA)
+        .min(MAX_BACKFILL)
+}
 
 pub fn stream_klines(
+    connector: Arc<dyn WsConnect>,
     symbol: &str,
     interval: &str,
-) -> (tokio::sync::mpsc::Receiver<Candle>, JoinHandle<()>) {
+    policy: ReconnectPolicy,
+    stats: Arc<StreamStats>,
+) -> (tokio::sync::mpsc::Receiver<KlineEvent>, JoinHandle<()>) {
     let (tx, rx) = tokio::sync::mpsc::channel(1000);
 
     let symbol_lower = symbol.to_lowercase();
     let stream_name = format!("{}@kline_{}", symbol_lower, interval);
     let url = format!("wss://stream.binance.com:9443/ws/{}", stream_name);
+    stats.set_endpoints(vec![url.clone()]);
 
     let handle = tokio::spawn(async move {
+        let mut connected_once = false;
+        let mut last_message = Instant::now();
         loop {
-            match connect_async(&url).await {
-                Ok((ws_stream, _)) => {
-                    let (mut _write, mut read) = ws_stream.split();
-
-                    while let Some(msg) = read.next().await {
-                        match msg {
-                            Ok(Message::Text(text)) => {
-                                if let Ok(json) = serde_json::from_str::<Value>(&text) {
-                                    if let Some(k) = json.get("k") {
-                                        if let Some(candle) = parse_kline(k) {
-                                            if tx.send(candle).await.is_err() {
-                                                return;
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                            Ok(Message::Close(_)) => {
-                                break;
-                            }
-                            Err(e) => {
-                                eprintln!("WebSocket error: {}", e);
-                                tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
-                                break;
+            let Some(mut read) =
+                connect_with_retry(policy, &stats, || connector.connect(&url)).await
+            else {
+                return;
+            };
+            if connected_once {
+                stats.record_reconnect();
+            }
+            let downtime = connected_once.then(|| last_message.elapsed());
+            connected_once = true;
+            if tx.send(KlineEvent::Connected { downtime }).await.is_err() {
+                return;
+            }
+            while let Some(msg) = read.next().await {
+                last_message = Instant::now();
+                match msg {
+                    Ok(Message::Text(text)) => {
+                        stats.record_message();
+                        let candle = serde_json::from_str::<Value>(&text)
+                            .ok()
+                            .and_then(|json| parse_kline(json.get("k")?));
+                        let Some(candle) = candle else {
+                            stats.record_parse_failure();
+                            continue;
+                        };
+                        let event = if validate_candle(&candle) {
+                            KlineEvent::Candle(candle)
+                        } else {
+                            KlineEvent::Rejected {
+                                open_time: candle.open_time,
                             }
-                            _ => {}
+                        };
+                        if tx.send(event).await.is_err() {
+                            return;
                         }
                     }
-                }
-                Err(e) => {
-                    eprintln!("Connection error: {}", e);
-                    tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+                    Ok(Message::Close(_)) => {
+                        break;
+                    }
+                    Err(err) => {
+                        stats.record_error(err);
+                        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+                        break;
+                    }
+                    _ => {}
                 }
             }
         }
@@ -70,5 +216,137 @@ fn parse_kline(k: &Value) -> Option<Candle> {
         taker_buy_base: k.get("V")?.as_str()?.to_string(),
         taker_buy_quote: k.get("Q")?.as_str()?.to_string(),
         ignore: "0".to_string(),
+        is_closed: k.get("x").and_then(Value::as_bool).unwrap_or(false),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::transport::mock::{MockWs, Session};
+
+    fn kline_message(open_time: u64, close: &str) -> Message {
+        Message::Text(
+            serde_json::json!({
+                "e": "kline",
+                "k": {
+                    "t": open_time, "T": open_time + 59_999, "i": "1m",
+                    "o": "100", "h": "101", "l": "99", "c": close, "v": "5",
+                    "q": "500", "n": 7, "V": "2", "Q": "200", "B": "0", "x": false
+                }
+            })
+            .to_string(),
+        )
+    }
+
+    #[tokio::test]
+    async fn scripted_sessions_drive_parsing_and_reconnects() {
+        let connector = MockWs::new(vec![
+            Session::Refused,
+            Session::Messages(vec![
+                kline_message(60_000, "100.5"),
+                Message::Text("not json".to_string()),
+            ]),
+            Session::Messages(vec![
+                kline_message(120_000, "100.25"),
+                // Closes above its high.
+                kline_message(180_000, "150"),
+            ]),
+        ]);
+        let policy = ReconnectPolicy {
+            max_attempts: 2,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(4),
+        };
+        let stats = Arc::new(StreamStats::default());
+        let (mut rx, handle) =
+            stream_klines(Arc::new(connector), "BTCUSDT", "1m", policy, stats.clone());
+
+        let mut events = Vec::new();
+        while let Some(event) = rx.recv().await {
+            events.push(event);
+        }
+        handle.await.unwrap();
+
+        assert!(matches!(
+            events[0],
+            KlineEvent::Connected { downtime: None }
+        ));
+        assert!(matches!(&events[1], KlineEvent::Candle(c) if c.close == "100.5"));
+        assert!(matches!(
+            events[2],
+            KlineEvent::Connected { downtime: Some(_) }
+        ));
+        assert!(matches!(&events[3], KlineEvent::Candle(c) if c.open_time == 120_000));
+        assert!(matches!(
+            events[4],
+            KlineEvent::Rejected { open_time: 180_000 }
+        ));
+        assert_eq!(events.len(), 5);
+
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.messages, 4);
+        assert_eq!(snapshot.parse_failures, 1);
+        assert_eq!(snapshot.reconnects, 1);
+    }
+
+    #[test]
+    fn parses_interval_lengths() {
+        assert_eq!(interval_duration("5m"), Some(Duration::from_secs(300)));
+        assert_eq!(interval_duration("4h"), Some(Duration::from_secs(14_400)));
+        assert_eq!(interval_duration("1d"), Some(Duration::from_secs(86_400)));
+        assert_eq!(interval_duration("0m"), None);
+        assert_eq!(interval_duration("h"), None);
+        assert_eq!(interval_duration(""), None);
+    }
+
+    #[test]
+    fn candles_are_checked_against_the_active_interval() {
+        let candle = |open_time: u64, close_time: u64| Candle {
+            close_time,
+            ..crate::data::fetch::candle(open_time, 1.0, 1.0, 1.0, 1.0)
+        };
+        let one_minute = candle(60_000, 119_999);
+        assert!(candle_matches_interval(&one_minute, "1m"));
+        assert!(!candle_matches_interval(&one_minute, "5m"));
+        assert!(candle_matches_interval(&candle(0, 299_999), "5m"));
+        // February and a 31-day month are both one month.
+        let day = 86_400_000;
+        assert!(candle_matches_interval(&candle(0, 28 * day - 1), "1M"));
+        assert!(candle_matches_interval(&candle(0, 31 * day - 1), "1M"));
+        assert!(!candle_matches_interval(&candle(0, 7 * day - 1), "1M"));
+        assert!(!candle_matches_interval(&one_minute, "bogus"));
+    }
+
+    #[test]
+    fn validates_against_the_supported_list() {
+        assert_eq!(validate_interval("4h"), Ok("4h"));
+        assert_eq!(validate_interval("1M"), Ok("1M"));
+        let err = validate_interval("4H").unwrap_err();
+        assert!(err.starts_with("unknown interval \"4H\"; expected one of 1m, 3m"));
+        assert!(validate_interval("2d").is_err());
+        assert!(supported_intervals()
+            .iter()
+            .all(|interval| interval_duration(interval).is_some()));
+    }
+
+    #[test]
+    fn coarser_interval_fits_the_span() {
+        let day = Duration::from_secs(86_400);
+        // 288 five-minute candles; 100 fit once they are 15 minutes wide.
+        assert_eq!(coarser_interval("5m", day, 100), Some("15m"));
+        assert_eq!(coarser_interval("5m", day, 24), Some("1h"));
+        assert_eq!(coarser_interval("1w", day * 10_000, 10), Some("1M"));
+        assert_eq!(coarser_interval("1M", day, 10), None);
+        assert_eq!(coarser_interval("bogus", day, 10), None);
+    }
+
+    #[test]
+    fn backfill_grows_with_downtime() {
+        assert_eq!(backfill_limit(Duration::from_secs(10), "5m"), 3);
+        assert_eq!(backfill_limit(Duration::from_secs(3_600), "5m"), 14);
+        assert_eq!(backfill_limit(Duration::from_secs(3_600), "1h"), 3);
+        assert_eq!(backfill_limit(Duration::from_secs(86_400 * 30), "1m"), 1000);
+        assert_eq!(backfill_limit(Duration::from_secs(60), "bogus"), 2);
+    }
+}
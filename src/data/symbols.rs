@@ -0,0 +1,282 @@
+use crate::data::fetch::{get_json, FetchError};
+use crate::data::transport::{HttpGet, ReqwestHttp};
+use serde_json::Value;
+use std::collections::HashSet;
+
+/// Price and order size rules for one symbol, from its exchangeInfo filters.
+/// A zero means the exchange sets no such limit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolInfo {
+    pub symbol: String,
+    /// Smallest price increment.
+    pub tick_size: f64,
+    pub min_qty: f64,
+    pub step_size: f64,
+    pub min_notional: f64,
+}
+
+impl SymbolInfo {
+    /// Decimal places needed to print a whole number of lot steps.
+    pub fn size_decimals(&self) -> usize {
+        step_decimals(self.step_size)
+    }
+}
+
+/// Decimal places needed to print a whole number of `step`s; 8 when the step
+/// is unknown.
+pub fn step_decimals(step: f64) -> usize {
+    if step > 0.0 && step.is_finite() {
+        (-step.log10()).ceil().max(0.0) as usize
+    } else {
+        8
+    }
+}
+
+/// Rounds `price` to the nearest multiple of `tick_size`, or returns it
+/// unchanged when the tick size is unknown.
+pub fn snap_to_tick(price: f64, tick_size: f64) -> f64 {
+    if tick_size > 0.0 && tick_size.is_finite() {
+        (price / tick_size).round() * tick_size
+    } else {
+        price
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LotIssue {
+    BelowMinQty,
+    NotStepMultiple,
+    BelowMinNotional,
+}
+
+impl LotIssue {
+    pub fn label(self) -> &'static str {
+        match self {
+            LotIssue::BelowMinQty => "below min qty",
+            LotIssue::NotStepMultiple => "not a lot step",
+            LotIssue::BelowMinNotional => "below min notional",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LotCheck {
+    /// The requested size rounded down to a whole number of lot steps.
+    pub valid_size: f64,
+    pub issues: Vec<LotIssue>,
+}
+
+/// Checks an order of `size` at roughly `price` against the symbol's lot
+/// size and minimum notional.
+pub fn check_order_size(info: &SymbolInfo, size: f64, price: f64) -> LotCheck {
+    let mut issues = Vec::new();
+    let valid_size = if info.step_size > 0.0 {
+        // Tolerate float noise so 0.3 with a 0.1 step stays 3 steps.
+        let steps = (size / info.step_size + 1e-9).floor();
+        let rounded = steps * info.step_size;
+        if (size - rounded).abs() > info.step_size * 1e-6 {
+            issues.push(LotIssue::NotStepMultiple);
+        }
+        rounded
+    } else {
+        size
+    };
+    if size < info.min_qty {
+        issues.push(LotIssue::BelowMinQty);
+    }
+    if price > 0.0 && size * price < info.min_notional {
+        issues.push(LotIssue::BelowMinNotional);
+    }
+    LotCheck { valid_size, issues }
+}
+
+const EXCHANGE_INFO_URL: &str = "https://api.binance.com/api/v3/exchangeInfo";
+
+/// All symbols currently trading on Binance spot, sorted.
+pub async fn fetch_symbols() -> Result<Vec<SymbolInfo>, FetchError> {
+    fetch_symbols_with(&ReqwestHttp::default()).await
+}
+
+/// [`fetch_symbols`] over any [`HttpGet`].
+pub async fn fetch_symbols_with(http: &dyn HttpGet) -> Result<Vec<SymbolInfo>, FetchError> {
+    let query = [("symbolStatus", "TRADING".to_string())];
+    let json = get_json(http, EXCHANGE_INFO_URL, &query, "exchangeInfo").await?;
+    parse_exchange_info(&json).ok_or(FetchError::Malformed("exchangeInfo"))
+}
+
+/// The exchange's trading status for one symbol, e.g. `TRADING`, `BREAK` or
+/// `HALT`.
+pub async fn fetch_symbol_status(symbol: &str) -> Result<String, FetchError> {
+    fetch_symbol_status_with(&ReqwestHttp::default(), symbol).await
+}
+
+/// [`fetch_symbol_status`] over any [`HttpGet`].
+pub async fn fetch_symbol_status_with(
+    http: &dyn HttpGet,
+    symbol: &str,
+) -> Result<String, FetchError> {
+    let query = [("symbol", symbol.to_string())];
+    let json = get_json(http, EXCHANGE_INFO_URL, &query, "exchangeInfo").await?;
+    parse_symbol_status(&json, symbol).ok_or(FetchError::Malformed("exchangeInfo"))
+}
+
+fn parse_symbol_status(json: &Value, symbol: &str) -> Option<String> {
+    json.get("symbols")?
+        .as_array()?
+        .iter()
+        .find(|entry| entry.get("symbol").and_then(Value::as_str) == Some(symbol))?
+        .get("status")?
+        .as_str()
+        .map(str::to_string)
+}
+
+fn parse_exchange_info(json: &Value) -> Option<Vec<SymbolInfo>> {
+    let mut symbols: Vec<SymbolInfo> = json
+        .get("symbols")?
+        .as_array()?
+        .iter()
+        .filter(|entry| entry.get("status").and_then(Value::as_str) == Some("TRADING"))
+        .filter_map(parse_symbol)
+        .collect();
+    symbols.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+    Some(symbols)
+}
+
+fn parse_symbol(entry: &Value) -> Option<SymbolInfo> {
+    let mut info = SymbolInfo {
+        symbol: entry.get("symbol")?.as_str()?.to_string(),
+        tick_size: 0.0,
+        min_qty: 0.0,
+        step_size: 0.0,
+        min_notional: 0.0,
+    };
+    let filters = entry.get("filters").and_then(Value::as_array);
+    for filter in filters.into_iter().flatten() {
+        let number = |key: &str| {
+            filter
+                .get(key)
+                .and_then(Value::as_str)
+                .and_then(|s| s.parse::<f64>().ok())
+                .unwrap_or(0.0)
+        };
+        match filter.get("filterType").and_then(Value::as_str) {
+            Some("PRICE_FILTER") => info.tick_size = number("tickSize"),
+            Some("LOT_SIZE") => {
+                info.min_qty = number("minQty");
+                info.step_size = number("stepSize");
+            }
+            // Older symbols use MIN_NOTIONAL, newer ones NOTIONAL.
+            Some("MIN_NOTIONAL") | Some("NOTIONAL") => info.min_notional = number("minNotional"),
+            _ => {}
+        }
+    }
+    Some(info)
+}
+
+/// Watchlist symbols that were trading in `previous` but are missing from
+/// `current`, i.e. delisted or halted since the last refresh.
+pub fn delisted(watchlist: &[String], previous: &[String], current: &[String]) -> Vec<String> {
+    let current: HashSet<&str> = current.iter().map(String::as_str).collect();
+    watchlist
+        .iter()
+        .filter(|symbol| previous.contains(symbol) && !current.contains(symbol.as_str()))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_only_trading_symbols() {
+        let json: Value = serde_json::from_str(
+            r#"{"timezone":"UTC","symbols":[
+            {"symbol":"ETHUSDT","status":"TRADING"},
+            {"symbol":"LUNAUSDT","status":"BREAK"},
+            {"symbol":"BTCUSDT","status":"TRADING","filters":[
+                {"filterType":"PRICE_FILTER","tickSize":"0.01000000"},
+                {"filterType":"LOT_SIZE","minQty":"0.00001000","stepSize":"0.00001000"},
+                {"filterType":"NOTIONAL","minNotional":"5.00000000"}]}]}"#,
+        )
+        .unwrap();
+
+        let symbols = parse_exchange_info(&json).unwrap();
+        let names: Vec<&str> = symbols.iter().map(|s| s.symbol.as_str()).collect();
+        assert_eq!(names, vec!["BTCUSDT", "ETHUSDT"]);
+        assert_eq!(symbols[0].tick_size, 0.01);
+        assert_eq!(symbols[0].min_qty, 0.00001);
+        assert_eq!(symbols[0].step_size, 0.00001);
+        assert_eq!(symbols[0].min_notional, 5.0);
+        assert_eq!(symbols[1].step_size, 0.0);
+        assert_eq!(parse_exchange_info(&Value::Null), None);
+    }
+
+    #[test]
+    fn reads_one_symbols_status() {
+        let json: Value =
+            serde_json::from_str(r#"{"symbols":[{"symbol":"LUNAUSDT","status":"BREAK"}]}"#)
+                .unwrap();
+        assert_eq!(
+            parse_symbol_status(&json, "LUNAUSDT").as_deref(),
+            Some("BREAK")
+        );
+        assert_eq!(parse_symbol_status(&json, "BTCUSDT"), None);
+    }
+
+    fn rules() -> SymbolInfo {
+        SymbolInfo {
+            symbol: "ETHUSDT".to_string(),
+            tick_size: 0.01,
+            min_qty: 0.01,
+            step_size: 0.01,
+            min_notional: 5.0,
+        }
+    }
+
+    #[test]
+    fn valid_size_passes_unchanged() {
+        let check = check_order_size(&rules(), 0.3, 3000.0);
+        assert!(check.issues.is_empty());
+        assert!((check.valid_size - 0.3).abs() < 1e-12);
+    }
+
+    #[test]
+    fn flags_sizes_below_minimums() {
+        let check = check_order_size(&rules(), 0.001, 3000.0);
+        assert!(check.issues.contains(&LotIssue::BelowMinQty));
+        assert!(check.issues.contains(&LotIssue::BelowMinNotional));
+        assert_eq!(check.valid_size, 0.0);
+    }
+
+    #[test]
+    fn rounds_non_increments_down_to_a_step() {
+        let check = check_order_size(&rules(), 1.234, 3000.0);
+        assert_eq!(check.issues, vec![LotIssue::NotStepMultiple]);
+        assert!((check.valid_size - 1.23).abs() < 1e-12);
+        assert_eq!(rules().size_decimals(), 2);
+    }
+
+    #[test]
+    fn snaps_prices_to_the_nearest_tick() {
+        assert!((snap_to_tick(69412.3712, 0.01) - 69412.37).abs() < 1e-9);
+        assert!((snap_to_tick(0.123456, 0.0005) - 0.1235).abs() < 1e-12);
+        assert_eq!(snap_to_tick(101.7, 0.0), 101.7);
+        assert_eq!(step_decimals(0.01), 2);
+        assert_eq!(step_decimals(0.0005), 4);
+        assert_eq!(step_decimals(10.0), 0);
+        assert_eq!(step_decimals(0.0), 8);
+    }
+
+    #[test]
+    fn flags_watchlist_symbols_that_stop_trading() {
+        let names = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let watchlist = names(&["BTCUSDT", "LUNAUSDT", "MYCOIN"]);
+        let previous = names(&["BTCUSDT", "ETHUSDT", "LUNAUSDT"]);
+        let current = names(&["BTCUSDT", "ETHUSDT", "NEWUSDT"]);
+
+        // MYCOIN was never listed, so it isn't reported as delisted.
+        assert_eq!(delisted(&watchlist, &previous, &current), vec!["LUNAUSDT"]);
+        assert!(delisted(&watchlist, &current, &current).is_empty());
+    }
+}
@@ -0,0 +1,193 @@
+use crate::data::fetch::{get_json, FetchError};
+use crate::data::transport::{HttpGet, ReqwestHttp};
+use serde_json::Value;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ticker24h {
+    pub symbol: String,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub last: f64,
+    pub price_change: f64,
+    pub price_change_pct: f64,
+    pub weighted_avg_price: f64,
+    pub volume: f64,
+    pub quote_volume: f64,
+    pub trade_count: u64,
+}
+
+const TICKER_URL: &str = "https://api.binance.com/api/v3/ticker/24hr";
+
+pub async fn fetch_ticker_24h(symbol: &str) -> Result<Ticker24h, FetchError> {
+    fetch_ticker_24h_with(&ReqwestHttp::default(), symbol).await
+}
+
+/// [`fetch_ticker_24h`] over any [`HttpGet`].
+pub async fn fetch_ticker_24h_with(
+    http: &dyn HttpGet,
+    symbol: &str,
+) -> Result<Ticker24h, FetchError> {
+    let query = [("symbol", symbol.to_string())];
+    let json = get_json(http, TICKER_URL, &query, "ticker/24hr").await?;
+    parse_ticker_24h(&json).ok_or(FetchError::Malformed("ticker/24hr"))
+}
+
+/// 24h quote volume of every symbol, from a single all-symbols request.
+pub async fn fetch_quote_volumes() -> Result<HashMap<String, f64>, FetchError> {
+    fetch_quote_volumes_with(&ReqwestHttp::default()).await
+}
+
+/// [`fetch_quote_volumes`] over any [`HttpGet`].
+pub async fn fetch_quote_volumes_with(
+    http: &dyn HttpGet,
+) -> Result<HashMap<String, f64>, FetchError> {
+    let query = [("type", "MINI".to_string())];
+    let json = get_json(http, TICKER_URL, &query, "ticker/24hr").await?;
+    parse_quote_volumes(&json).ok_or(FetchError::Malformed("ticker/24hr"))
+}
+
+/// 24h percent change of each of `symbols`, from a single request.
+pub async fn fetch_change_pcts(symbols: &[String]) -> Result<HashMap<String, f64>, FetchError> {
+    fetch_change_pcts_with(&ReqwestHttp::default(), symbols).await
+}
+
+/// [`fetch_change_pcts`] over any [`HttpGet`].
+pub async fn fetch_change_pcts_with(
+    http: &dyn HttpGet,
+    symbols: &[String],
+) -> Result<HashMap<String, f64>, FetchError> {
+    let query = [(
+        "symbols",
+        serde_json::to_string(symbols).unwrap_or_default(),
+    )];
+    let json = get_json(http, TICKER_URL, &query, "ticker/24hr").await?;
+    parse_change_pcts(&json).ok_or(FetchError::Malformed("ticker/24hr"))
+}
+
+fn parse_change_pcts(json: &Value) -> Option<HashMap<String, f64>> {
+    Some(
+        json.as_array()?
+            .iter()
+            .filter_map(|ticker| {
+                let symbol = ticker.get("symbol")?.as_str()?.to_string();
+                let change = ticker.get("priceChangePercent")?.as_str()?.parse().ok()?;
+                Some((symbol, change))
+            })
+            .collect(),
+    )
+}
+
+fn parse_quote_volumes(json: &Value) -> Option<HashMap<String, f64>> {
+    Some(
+        json.as_array()?
+            .iter()
+            .filter_map(|ticker| {
+                let symbol = ticker.get("symbol")?.as_str()?.to_string();
+                let volume = ticker.get("quoteVolume")?.as_str()?.parse().ok()?;
+                Some((symbol, volume))
+            })
+            .collect(),
+    )
+}
+
+fn parse_ticker_24h(json: &Value) -> Option<Ticker24h> {
+    let number = |key: &str| -> Option<f64> { json.get(key)?.as_str()?.parse().ok() };
+
+    Some(Ticker24h {
+        symbol: json.get("symbol")?.as_str()?.to_string(),
+        open: number("openPrice")?,
+        high: number("highPrice")?,
+        low: number("lowPrice")?,
+        last: number("lastPrice")?,
+        price_change: number("priceChange")?,
+        price_change_pct: number("priceChangePercent")?,
+        weighted_avg_price: number("weightedAvgPrice")?,
+        volume: number("volume")?,
+        quote_volume: number("quoteVolume")?,
+        trade_count: json.get("count")?.as_u64()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::transport::mock::MockHttp;
+
+    #[tokio::test]
+    async fn ticker_fetches_go_through_the_http_seam() {
+        let http = MockHttp::default()
+            .respond(
+                200,
+                r#"[{"symbol":"BTCUSDT","priceChangePercent":"2.5"},{"symbol":"ETHUSDT"}]"#,
+            )
+            .respond(200, "<html>maintenance</html>")
+            .rate_limited("30");
+        let symbols = vec!["BTCUSDT".to_string(), "ETHUSDT".to_string()];
+
+        let changes = fetch_change_pcts_with(&http, &symbols).await.unwrap();
+        assert_eq!(changes, HashMap::from([("BTCUSDT".to_string(), 2.5)]));
+        assert_eq!(
+            http.queries.lock().unwrap()[0],
+            vec![(
+                "symbols".to_string(),
+                r#"["BTCUSDT","ETHUSDT"]"#.to_string()
+            )]
+        );
+        assert!(matches!(
+            fetch_ticker_24h_with(&http, "BTCUSDT").await,
+            Err(FetchError::Malformed("ticker/24hr"))
+        ));
+        assert!(matches!(
+            fetch_quote_volumes_with(&http).await,
+            Err(FetchError::RateLimited { .. })
+        ));
+    }
+
+    #[test]
+    fn parses_ticker_24hr_payload() {
+        let json: Value = serde_json::from_str(
+            r#"{"symbol":"BTCUSDT","priceChange":"-94.99999800","priceChangePercent":"-95.960",
+            "weightedAvgPrice":"0.29628482","prevClosePrice":"0.10002000","lastPrice":"4.00000200",
+            "openPrice":"99.00000000","highPrice":"100.00000000","lowPrice":"0.10000000",
+            "volume":"8913.30000000","quoteVolume":"15.30000000","count":76}"#,
+        )
+        .unwrap();
+
+        let ticker = parse_ticker_24h(&json).unwrap();
+        assert_eq!(ticker.symbol, "BTCUSDT");
+        assert_eq!(ticker.open, 99.0);
+        assert_eq!(ticker.last, 4.000002);
+        assert_eq!(ticker.price_change_pct, -95.96);
+        assert_eq!(ticker.trade_count, 76);
+    }
+
+    #[test]
+    fn parses_all_symbol_quote_volumes() {
+        let json: Value = serde_json::from_str(
+            r#"[{"symbol":"BTCUSDT","quoteVolume":"1500.5"},
+            {"symbol":"ETHUSDT","quoteVolume":"bad"},
+            {"symbol":"BNBUSDT","quoteVolume":"20"}]"#,
+        )
+        .unwrap();
+
+        let volumes = parse_quote_volumes(&json).unwrap();
+        assert_eq!(volumes.len(), 2);
+        assert_eq!(volumes["BTCUSDT"], 1500.5);
+        assert!(parse_quote_volumes(&serde_json::json!({"code": -1})).is_none());
+    }
+
+    #[test]
+    fn parses_change_percents() {
+        let json: Value = serde_json::from_str(
+            r#"[{"symbol":"BTCUSDT","priceChangePercent":"-2.514"},
+            {"symbol":"ETHUSDT","priceChangePercent":"4.1"}]"#,
+        )
+        .unwrap();
+
+        let changes = parse_change_pcts(&json).unwrap();
+        assert_eq!(changes["BTCUSDT"], -2.514);
+        assert_eq!(changes["ETHUSDT"], 4.1);
+    }
+}
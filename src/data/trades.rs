@@ -1,5 +1,9 @@
-use futures_util::StreamExt;
+use crate::data::reconnect::{forward_frames, ReconnectPolicy};
+use crate::data::stats::StreamStats;
+use crate::data::transport::WsConnect;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::sync::Arc;
 use tokio::task::JoinHandle;
 
 #[derive(Debug, Clone)]
@@ -7,53 +11,164 @@ pub struct Trade {
     pub price: f64,
     pub quantity: f64,
     pub is_buyer_maker: bool,
-    #[allow(dead_code)]
     pub timestamp: u64,
+    /// First and last exchange trade IDs covered by this event. Equal for raw
+    /// trades; an aggTrade spans every fill merged into it.
+    #[allow(dead_code)]
+    pub first_trade_id: u64,
+    #[allow(dead_code)]
+    pub last_trade_id: u64,
+}
+
+/// Which Binance trade stream feeds the tape. `Aggregate` (`@aggTrade`) merges
+/// fills at the same price and side, which is much quieter on busy pairs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TradeStreamKind {
+    #[default]
+    Raw,
+    Aggregate,
+}
+
+pub fn stream_trade_feed(
+    connector: Arc<dyn WsConnect>,
+    kind: TradeStreamKind,
+    symbol: &str,
+    policy: ReconnectPolicy,
+    stats: Arc<StreamStats>,
+) -> (tokio::sync::mpsc::Receiver<Trade>, JoinHandle<()>) {
+    match kind {
+        TradeStreamKind::Raw => stream_trades(connector, symbol, policy, stats),
+        TradeStreamKind::Aggregate => stream_agg_trades(connector, symbol, policy, stats),
+    }
 }
 
-pub fn stream_trades(symbol: &str) -> (tokio::sync::mpsc::Receiver<Trade>, JoinHandle<()>) {
+pub fn stream_trades(
+    connector: Arc<dyn WsConnect>,
+    symbol: &str,
+    policy: ReconnectPolicy,
+    stats: Arc<StreamStats>,
+) -> (tokio::sync::mpsc::Receiver<Trade>, JoinHandle<()>) {
+    let url = format!(
+        "wss://stream.binance.com:9443/ws/{}@trade",
+        symbol.to_lowercase()
+    );
+    spawn_trade_stream(connector, url, parse_trade, policy, stats)
+}
+
+pub fn stream_agg_trades(
+    connector: Arc<dyn WsConnect>,
+    symbol: &str,
+    policy: ReconnectPolicy,
+    stats: Arc<StreamStats>,
+) -> (tokio::sync::mpsc::Receiver<Trade>, JoinHandle<()>) {
+    let url = format!(
+        "wss://stream.binance.com:9443/ws/{}@aggTrade",
+        symbol.to_lowercase()
+    );
+    spawn_trade_stream(connector, url, parse_agg_trade, policy, stats)
+}
+
+fn spawn_trade_stream(
+    connector: Arc<dyn WsConnect>,
+    url: String,
+    parse: fn(&Value) -> Option<Trade>,
+    policy: ReconnectPolicy,
+    stats: Arc<StreamStats>,
+) -> (tokio::sync::mpsc::Receiver<Trade>, JoinHandle<()>) {
     let (tx, rx) = tokio::sync::mpsc::channel(1000);
-    let symbol_lower = symbol.to_lowercase();
-    let url = format!("wss://stream.binance.com:9443/ws/{}@trade", symbol_lower);
+    stats.set_endpoints(vec![url.clone()]);
 
     let handle = tokio::spawn(async move {
-        loop {
-            match tokio_tungstenite::connect_async(&url).await {
-                Ok((ws_stream, _)) => {
-                    let (mut _write, mut read) = ws_stream.split();
-
-                    while let Some(msg) = read.next().await {
-                        match msg {
-                            Ok(tokio_tungstenite::tungstenite::Message::Text(text)) => {
-                                if let Ok(json) = serde_json::from_str::<Value>(&text) {
-                                    if let Some(trade) = parse_trade(&json) {
-                                        if tx.send(trade).await.is_err() {
-                                            return;
-                                        }
-                                    }
-                                }
-                            }
-                            Ok(tokio_tungstenite::tungstenite::Message::Close(_)) => break,
-                            Err(_) => break,
-                            _ => {}
-                        }
-                    }
-                }
-                Err(_) => {
-                    tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
-                }
-            }
-        }
+        forward_frames(&*connector, &url, policy, &stats, &tx, |json| {
+            parse(json).into()
+        })
+        .await;
     });
 
     (rx, handle)
 }
 
 fn parse_trade(json: &Value) -> Option<Trade> {
+    let trade_id = json.get("t")?.as_u64()?;
     Some(Trade {
         price: json.get("p")?.as_str()?.parse().ok()?,
         quantity: json.get("q")?.as_str()?.parse().ok()?,
         is_buyer_maker: json.get("m")?.as_bool()?,
         timestamp: json.get("T")?.as_u64()?,
+        first_trade_id: trade_id,
+        last_trade_id: trade_id,
     })
 }
+
+fn parse_agg_trade(json: &Value) -> Option<Trade> {
+    Some(Trade {
+        price: json.get("p")?.as_str()?.parse().ok()?,
+        quantity: json.get("q")?.as_str()?.parse().ok()?,
+        is_buyer_maker: json.get("m")?.as_bool()?,
+        timestamp: json.get("T")?.as_u64()?,
+        first_trade_id: json.get("f")?.as_u64()?,
+        last_trade_id: json.get("l")?.as_u64()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::transport::mock::{MockWs, Session};
+    use std::time::Duration;
+    use tokio_tungstenite::tungstenite::Message;
+
+    #[tokio::test]
+    async fn agg_trade_stream_reads_a_scripted_session() {
+        let connector = MockWs::new(vec![Session::Messages(vec![Message::Text(
+            r#"{"e":"aggTrade","s":"BTCUSDT","a":7,"p":"43000.5","q":"0.25","f":100,"l":104,"T":1700000000000,"m":true}"#
+                .to_string(),
+        )])]);
+        let policy = ReconnectPolicy {
+            max_attempts: 1,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(1),
+        };
+        let (mut rx, handle) = stream_trade_feed(
+            Arc::new(connector),
+            TradeStreamKind::Aggregate,
+            "BTCUSDT",
+            policy,
+            Arc::default(),
+        );
+
+        let trade = rx.recv().await.unwrap();
+        assert_eq!((trade.price, trade.quantity), (43_000.5, 0.25));
+        assert_eq!((trade.first_trade_id, trade.last_trade_id), (100, 104));
+        assert!(trade.is_buyer_maker);
+        assert!(rx.recv().await.is_none());
+        handle.await.unwrap();
+    }
+
+    #[test]
+    fn agg_trade_maps_fields_and_trade_id_range() {
+        let json: Value = serde_json::from_str(
+            r#"{"e":"aggTrade","E":1672515782136,"s":"BTCUSDT","a":12345,"p":"0.001","q":"100","f":100,"l":105,"T":1672515782136,"m":true,"M":true}"#,
+        )
+        .unwrap();
+
+        let trade = parse_agg_trade(&json).unwrap();
+        assert_eq!(trade.price, 0.001);
+        assert_eq!(trade.quantity, 100.0);
+        assert!(trade.is_buyer_maker);
+        assert_eq!(trade.timestamp, 1672515782136);
+        assert_eq!((trade.first_trade_id, trade.last_trade_id), (100, 105));
+    }
+
+    #[test]
+    fn raw_trade_uses_single_trade_id() {
+        let json: Value = serde_json::from_str(
+            r#"{"e":"trade","E":1,"s":"BTCUSDT","t":42,"p":"10.5","q":"2","T":2,"m":false,"M":true}"#,
+        )
+        .unwrap();
+
+        let trade = parse_trade(&json).unwrap();
+        assert_eq!((trade.first_trade_id, trade.last_trade_id), (42, 42));
+        assert!(!trade.is_buyer_maker);
+    }
+}
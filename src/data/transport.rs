@@ -0,0 +1,168 @@
+use crate::data::fetch::FetchError;
+use futures_util::future::BoxFuture;
+use futures_util::stream::BoxStream;
+use futures_util::{FutureExt, StreamExt};
+use reqwest::Client;
+use tokio_tungstenite::tungstenite::{Error as WsError, Message};
+
+/// What a GET came back with, before any status or body checks, so the
+/// checks can run the same on canned responses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpResponse {
+    pub status: u16,
+    /// The `Retry-After` header, if sent.
+    pub retry_after: Option<String>,
+    pub body: String,
+}
+
+/// The HTTP GET the REST fetches go through; tests swap in canned
+/// responses instead of reaching the exchange.
+pub trait HttpGet: Send + Sync {
+    fn get<'a>(
+        &'a self,
+        url: &'a str,
+        query: &'a [(&'a str, String)],
+    ) -> BoxFuture<'a, Result<HttpResponse, FetchError>>;
+}
+
+/// [`HttpGet`] over a real `reqwest` client.
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestHttp {
+    client: Client,
+}
+
+impl HttpGet for ReqwestHttp {
+    fn get<'a>(
+        &'a self,
+        url: &'a str,
+        query: &'a [(&'a str, String)],
+    ) -> BoxFuture<'a, Result<HttpResponse, FetchError>> {
+        async move {
+            let res = self.client.get(url).query(query).send().await?;
+            let status = res.status().as_u16();
+            let retry_after = res
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string);
+            let body = res.text().await?;
+            Ok(HttpResponse {
+                status,
+                retry_after,
+                body,
+            })
+        }
+        .boxed()
+    }
+}
+
+/// Incoming messages on an open socket.
+pub type WsMessages = BoxStream<'static, Result<Message, WsError>>;
+
+/// Opens the WebSocket a stream task reads from; tests swap in scripted
+/// sessions to drive parsing and reconnects.
+pub trait WsConnect: Send + Sync + 'static {
+    fn connect<'a>(&'a self, url: &'a str) -> BoxFuture<'a, Result<WsMessages, WsError>>;
+}
+
+/// [`WsConnect`] over `tokio_tungstenite`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TungsteniteConnect;
+
+impl WsConnect for TungsteniteConnect {
+    fn connect<'a>(&'a self, url: &'a str) -> BoxFuture<'a, Result<WsMessages, WsError>> {
+        async move {
+            let (ws_stream, _) = tokio_tungstenite::connect_async(url).await?;
+            Ok(ws_stream.boxed())
+        }
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+pub mod mock {
+    use super::*;
+    use std::collections::VecDeque;
+    use std::sync::Mutex;
+
+    /// Answers each GET with the next canned response and records the
+    /// queries it was sent.
+    #[derive(Default)]
+    pub struct MockHttp {
+        responses: Mutex<VecDeque<HttpResponse>>,
+        pub queries: Mutex<Vec<Vec<(String, String)>>>,
+    }
+
+    impl MockHttp {
+        pub fn respond(self, status: u16, body: &str) -> Self {
+            self.responses.lock().unwrap().push_back(HttpResponse {
+                status,
+                retry_after: None,
+                body: body.to_string(),
+            });
+            self
+        }
+
+        pub fn rate_limited(self, retry_after: &str) -> Self {
+            self.responses.lock().unwrap().push_back(HttpResponse {
+                status: 429,
+                retry_after: Some(retry_after.to_string()),
+                body: String::new(),
+            });
+            self
+        }
+    }
+
+    impl HttpGet for MockHttp {
+        fn get<'a>(
+            &'a self,
+            _url: &'a str,
+            query: &'a [(&'a str, String)],
+        ) -> BoxFuture<'a, Result<HttpResponse, FetchError>> {
+            self.queries.lock().unwrap().push(
+                query
+                    .iter()
+                    .map(|(key, value)| (key.to_string(), value.clone()))
+                    .collect(),
+            );
+            let next = self.responses.lock().unwrap().pop_front();
+            async move { next.ok_or(FetchError::Malformed("mock (no response left)")) }.boxed()
+        }
+    }
+
+    /// One scripted connect attempt: refused, or accepted and then sending
+    /// these messages before the socket ends.
+    pub enum Session {
+        Refused,
+        Messages(Vec<Message>),
+    }
+
+    /// Plays scripted sessions in order, refusing once they run out.
+    #[derive(Default)]
+    pub struct MockWs {
+        sessions: Mutex<VecDeque<Session>>,
+    }
+
+    impl MockWs {
+        pub fn new(sessions: Vec<Session>) -> Self {
+            Self {
+                sessions: Mutex::new(sessions.into()),
+            }
+        }
+    }
+
+    impl WsConnect for MockWs {
+        fn connect<'a>(&'a self, _url: &'a str) -> BoxFuture<'a, Result<WsMessages, WsError>> {
+            let session = self.sessions.lock().unwrap().pop_front();
+            async move {
+                match session {
+                    Some(Session::Messages(messages)) => {
+                        Ok(futures_util::stream::iter(messages.into_iter().map(Ok)).boxed())
+                    }
+                    Some(Session::Refused) | None => Err(WsError::ConnectionClosed),
+                }
+            }
+            .boxed()
+        }
+    }
+}
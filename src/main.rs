@@ -1,31 +1,69 @@
+mod backtest;
+mod clipboard;
 mod config;
 mod data;
+mod report;
 mod ui;
 
 use config::{config_path, load_config, save_config, AppConfig};
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, KeyModifiers,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use data::orderbook::stream_orderbook;
-use data::prices::stream_watchlist_prices;
-use data::trades::stream_trades;
-use data::{fetch_klines, stream_klines};
+use data::coordinator::{
+    BinanceFeeds, ConnectionEvent, Feed, StreamCoordinator, StreamEvent, Subscription,
+};
+use data::orderbook::Side;
+use data::prices::{ChangeBaseline, DailyAnchors, PriceSource};
+use data::reconnect::ReconnectPolicy;
+use data::server::{measure_clock_skew, ping_server, MAX_CLOCK_SKEW};
+use data::stats::FeedStats;
+use data::stream::{backfill_limit, coarser_interval, interval_duration, validate_interval};
+use data::symbols::{delisted, fetch_symbol_status, fetch_symbols, SymbolInfo};
+use data::ticker::{fetch_change_pcts, fetch_quote_volumes, fetch_ticker_24h};
+use data::trades::TradeStreamKind;
+use data::{fetch_kline_batch, fetch_klines, FetchError, KlineBatch, KlineWindow};
 use ratatui::{backend::CrosstermBackend, Terminal};
+use std::collections::HashMap;
+use std::future::Future;
 use std::io;
 use std::io::IsTerminal;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
 use tokio::task::JoinHandle;
-use ui::{Chart, ConnectionMode, LayoutManager};
+use ui::events::{ConnectionLogEntry, EventKind};
+use ui::grid::Grid;
+use ui::indicators::IndicatorPrefs;
+use ui::mtf::{change_from_candles, MTF_INTERVALS};
+use ui::outage::OutageWatch;
+use ui::preset::{LayoutPreset, PanelLayout};
+use ui::quiet::QuietMode;
+use ui::tradetape::TapeFormat;
+use ui::warmup::Warmup;
+use ui::watchlist::WatchlistOrder;
+use ui::{Chart, ConnectionMode, LayoutManager, MarketStatsModal};
 
 const FETCH_TIMEOUT: Duration = Duration::from_secs(8);
 const FETCH_RETRIES: usize = 2;
 const FETCH_RETRY_DELAY: Duration = Duration::from_millis(500);
 const HEALTH_TICK_INTERVAL: Duration = Duration::from_secs(1);
+const RATE_LIMIT_DEGRADE_AFTER: u32 = 3;
+const PREFLIGHT_TIMEOUT: Duration = Duration::from_secs(3);
+/// How often watchlist symbols missing today's daily anchors are refetched.
+const ANCHOR_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+/// How long the watchlist selection must rest before its symbol loads.
+const SYMBOL_SWITCH_DEBOUNCE: Duration = Duration::from_millis(250);
+/// Redraw rate while idle; streamed prices and candles still show, just
+/// once a second.
+const IDLE_FPS: u32 = 1;
+/// Most candles one replay loads; Binance's cap per klines request.
+const REPLAY_MAX_CANDLES: u32 = 1000;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum FeedState {
@@ -39,7 +77,13 @@ struct HealthUpdate {
     kline: FeedState,
     orderbook: FeedState,
     trades: FeedState,
+    retries_exhausted: bool,
     last_error: Option<String>,
+    /// Trades skipped over the last second because the tape fell behind.
+    dropped_trades: usize,
+    /// Feeds that stopped delivering data; flagged on their own panels
+    /// rather than in the overall mode.
+    stale: Vec<Feed>,
 }
 
 impl HealthUpdate {
@@ -60,53 +104,197 @@ impl HealthUpdate {
     }
 }
 
+/// Connection lifecycle of one feed. A connected feed that goes quiet is
+/// reported as stale on its panel instead (see `StreamCoordinator::stale_feeds`).
 struct FeedTracker {
-    last_message: Option<Instant>,
     state: FeedState,
-    reconnect_after: Duration,
-    degrade_after: Duration,
+    exhausted: bool,
 }
 
 impl FeedTracker {
-    fn new(reconnect_after: Duration, degrade_after: Duration) -> Self {
+    fn new() -> Self {
         Self {
-            last_message: None,
             state: FeedState::Reconnecting,
-            reconnect_after,
-            degrade_after,
+            exhausted: false,
         }
     }
 
-    fn mark_live(&mut self, now: Instant) {
-        self.last_message = Some(now);
+    fn mark_live(&mut self) {
         self.state = FeedState::Live;
+        self.exhausted = false;
     }
 
     fn mark_reconnecting(&mut self) {
-        self.last_message = None;
         self.state = FeedState::Reconnecting;
+        self.exhausted = false;
+    }
+
+    /// The stream task ran out of reconnect attempts; stays degraded until a
+    /// manual refresh restarts it.
+    fn mark_exhausted(&mut self) {
+        self.state = FeedState::Degraded;
+        self.exhausted = true;
+    }
+}
+
+struct FrameLimiter {
+    frame_interval: Duration,
+    last_frame: Option<Instant>,
+}
+
+impl FrameLimiter {
+    fn new(max_fps: u32) -> Self {
+        Self {
+            frame_interval: Duration::from_secs(1) / max_fps.max(1),
+            last_frame: None,
+        }
+    }
+
+    fn ready(&self, now: Instant) -> bool {
+        match self.last_frame {
+            Some(last) => now.saturating_duration_since(last) >= self.frame_interval,
+            None => true,
+        }
+    }
+
+    fn mark_drawn(&mut self, now: Instant) {
+        self.last_frame = Some(now);
+    }
+
+    fn poll_timeout(&self, now: Instant) -> Duration {
+        let remaining = match self.last_frame {
+            Some(last) => self
+                .frame_interval
+                .saturating_sub(now.saturating_duration_since(last)),
+            None => Duration::ZERO,
+        };
+
+        if remaining.is_zero() {
+            self.frame_interval
+        } else {
+            remaining
+        }
+    }
+}
+
+/// Paces chart redraws caused by streamed candles to at most one per
+/// `interval` (zero means every update). Other panels and user actions are
+/// not held back by it.
+struct ChartCadence {
+    interval: Duration,
+    last_drawn: Option<Instant>,
+}
+
+impl ChartCadence {
+    fn new(interval_ms: u64) -> Self {
+        Self {
+            interval: Duration::from_millis(interval_ms),
+            last_drawn: None,
+        }
+    }
+
+    fn due(&self, now: Instant) -> bool {
+        self.last_drawn
+            .is_none_or(|last| now.saturating_duration_since(last) >= self.interval)
+    }
+
+    fn mark_drawn(&mut self, now: Instant) {
+        self.last_drawn = Some(now);
+    }
+}
+
+/// Holds back the symbol switch while the watchlist selection is still
+/// moving, so scrolling through it loads only the symbol it stops on.
+struct SwitchDebounce {
+    delay: Duration,
+    due: Option<Instant>,
+}
+
+impl SwitchDebounce {
+    fn new(delay: Duration) -> Self {
+        Self { delay, due: None }
+    }
+
+    /// Restarts the wait; a switch already pending is pushed back.
+    fn schedule(&mut self, now: Instant) {
+        self.due = Some(now + self.delay);
+    }
+
+    fn cancel(&mut self) {
+        self.due = None;
     }
 
-    fn refresh(&mut self, now: Instant) -> bool {
-        let previous = self.state;
-        if let Some(last) = self.last_message {
-            let elapsed = now.saturating_duration_since(last);
-            self.state = if elapsed >= self.degrade_after {
-                FeedState::Degraded
-            } else if elapsed >= self.reconnect_after {
-                FeedState::Reconnecting
-            } else {
-                FeedState::Live
-            };
+    /// True once, when the selection has settled for `delay`.
+    fn fire(&mut self, now: Instant) -> bool {
+        if self.due.is_some_and(|due| now >= due) {
+            self.due = None;
+            true
         } else {
-            self.state = FeedState::Reconnecting;
+            false
+        }
+    }
+}
+
+/// Tracks time since the last keypress; after `timeout` the app goes idle
+/// until the next one. A zero timeout never idles.
+struct IdleWatch {
+    timeout: Duration,
+    last_input: Instant,
+    idle: bool,
+}
+
+impl IdleWatch {
+    fn new(timeout: Duration, now: Instant) -> Self {
+        Self {
+            timeout,
+            last_input: now,
+            idle: false,
         }
+    }
+
+    /// Records input; returns true when it woke the app up.
+    fn touch(&mut self, now: Instant) -> bool {
+        self.last_input = now;
+        std::mem::replace(&mut self.idle, false)
+    }
+
+    /// Returns true when the app just went idle.
+    fn update(&mut self, now: Instant) -> bool {
+        if self.idle || self.timeout.is_zero() {
+            return false;
+        }
+        self.idle = now.saturating_duration_since(self.last_input) >= self.timeout;
+        self.idle
+    }
+
+    fn is_idle(&self) -> bool {
+        self.idle
+    }
+}
 
-        previous != self.state
+/// The window a replay from `start_ms` loads: `limit` candles of
+/// `interval`, capped at `REPLAY_MAX_CANDLES` and cut off at `now_ms`.
+fn replay_window(
+    start_ms: u64,
+    interval: &str,
+    limit: u32,
+    now_ms: u64,
+) -> Result<KlineWindow, String> {
+    let step = interval_duration(interval)
+        .ok_or_else(|| format!("can't replay the {interval} interval"))?;
+    if start_ms >= now_ms {
+        return Err("Replay start is in the future".to_string());
     }
+    let span = step.as_millis() as u64 * u64::from(limit.clamp(1, REPLAY_MAX_CANDLES));
+    Ok(KlineWindow {
+        start_ms,
+        end_ms: (start_ms + span - 1).min(now_ms),
+    })
 }
 
-fn health_reason(update: &HealthUpdate) -> Option<String> {
+/// Status-bar text for feeds that aren't live, ending with `socket_error`
+/// (why one of them last failed) when there is one.
+fn health_reason(update: &HealthUpdate, socket_error: Option<&str>) -> Option<String> {
     if update.overall_mode() == ConnectionMode::Live {
         return None;
     }
@@ -126,13 +314,34 @@ fn health_reason(update: &HealthUpdate) -> Option<String> {
         }
     }
 
-    if !degraded.is_empty() {
-        Some(format!("degraded: {}", degraded.join(",")))
+    let reason = if !degraded.is_empty() && update.retries_exhausted {
+        format!(
+            "offline: {} gave up reconnecting; press F5 to retry",
+            degraded.join(",")
+        )
+    } else if !degraded.is_empty() {
+        format!("degraded: {}", degraded.join(","))
     } else if !reconnecting.is_empty() {
-        Some(format!("reconnecting: {}", reconnecting.join(",")))
+        format!("reconnecting: {}", reconnecting.join(","))
     } else {
-        None
-    }
+        return None;
+    };
+    Some(match socket_error {
+        Some(err) => format!("{reason} ({err})"),
+        None => reason,
+    })
+}
+
+/// Why the first chart feed that isn't live last failed, if it said.
+fn socket_error(stats: &FeedStats, update: &HealthUpdate) -> Option<String> {
+    [
+        (Feed::Kline, update.kline),
+        (Feed::OrderBook, update.orderbook),
+        (Feed::Trades, update.trades),
+    ]
+    .into_iter()
+    .filter(|(_, state)| *state != FeedState::Live)
+    .find_map(|(feed, _)| stats.get(feed).snapshot().last_error)
 }
 
 fn push_health_update(
@@ -146,10 +355,142 @@ fn push_health_update(
     }
 }
 
+/// Refetches the candles a kline socket missed while it was down and merges
+/// them into the chart, sized to the downtime.
+fn spawn_kline_backfill(
+    chart: Arc<Mutex<Chart>>,
+    dirty: Arc<AtomicBool>,
+    symbol: String,
+    interval: String,
+    downtime: Duration,
+) {
+    tokio::spawn(async move {
+        let limit = backfill_limit(downtime, &interval);
+        let fetch = fetch_klines(&symbol, &interval, limit);
+        let Ok(Ok(candles)) = tokio::time::timeout(FETCH_TIMEOUT, fetch).await else {
+            return;
+        };
+        let mut chart = chart.lock().await;
+        if chart.symbol == symbol && chart.interval == interval && chart.review.is_none() {
+            chart.merge_candles(candles);
+            dirty.store(true, Ordering::Relaxed);
+        }
+    });
+}
+
+fn unix_now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
+}
+
 fn should_apply_fetch_result(pending_request_id: Option<u64>, incoming_request_id: u64) -> bool {
     pending_request_id == Some(incoming_request_id)
 }
 
+fn no_data_message(symbol: &str) -> String {
+    format!("no data for {symbol} (invalid symbol?)")
+}
+
+/// Outcome of the startup connectivity check.
+struct Preflight {
+    online: bool,
+    clock_warning: Option<String>,
+}
+
+fn clock_skew_warning(skew_ms: i64) -> Option<String> {
+    (u128::from(skew_ms.unsigned_abs()) > MAX_CLOCK_SKEW.as_millis()).then(|| {
+        format!(
+            "system clock is {:.1}s {} Binance; candle times may be misleading",
+            skew_ms.unsigned_abs() as f64 / 1000.0,
+            if skew_ms > 0 { "behind" } else { "ahead of" }
+        )
+    })
+}
+
+/// `--backtest [SYMBOL [INTERVAL]]`: runs the SMA crossover backtest over the
+/// latest candles and prints the summary, without starting the UI. Defaults
+/// to the configured symbol and timeframe.
+async fn run_backtest(args: &[String]) -> io::Result<()> {
+    let config = load_config(&config_path());
+    let symbol = args
+        .first()
+        .map(|symbol| symbol.to_uppercase())
+        .unwrap_or(config.symbol);
+    let interval = args
+        .get(1)
+        .map(String::as_str)
+        .unwrap_or_else(|| config.timeframe.binance_interval());
+    let interval = match validate_interval(interval) {
+        Ok(interval) => interval,
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(2);
+        }
+    };
+
+    let candles = match fetch_klines(&symbol, interval, 1000).await {
+        Ok(candles) => candles,
+        Err(err) => {
+            eprintln!("Couldn't fetch {} {}: {}", symbol, interval, err);
+            std::process::exit(1);
+        }
+    };
+    let report = backtest::run_crossover(
+        backtest::closed_candles(&candles),
+        backtest::FAST_PERIOD,
+        backtest::SLOW_PERIOD,
+    );
+    println!("{} {}", symbol, interval);
+    println!("{}", report);
+    Ok(())
+}
+
+/// Pings Binance and measures clock skew before the UI opens. When the server
+/// can't be reached, asks whether to start offline; `None` means quit.
+async fn preflight() -> io::Result<Option<Preflight>> {
+    use std::io::Write;
+
+    println!("Checking connection to Binance...");
+    let reachable = match tokio::time::timeout(PREFLIGHT_TIMEOUT, ping_server()).await {
+        Ok(Ok(())) => Ok(()),
+        Ok(Err(err)) => Err(err.to_string()),
+        Err(_) => Err("timed out".to_string()),
+    };
+    if let Err(reason) = reachable {
+        print!("Binance is unreachable ({reason}). Continue offline? [Y/n] ");
+        io::stdout().flush()?;
+        let mut answer = String::new();
+        io::stdin().read_line(&mut answer)?;
+        if answer.trim().to_lowercase().starts_with('n') {
+            return Ok(None);
+        }
+        return Ok(Some(Preflight {
+            online: false,
+            clock_warning: None,
+        }));
+    }
+
+    let clock_warning = match tokio::time::timeout(PREFLIGHT_TIMEOUT, measure_clock_skew()).await {
+        Ok(Ok((round_trip, skew_ms))) => {
+            println!(
+                "Binance reachable ({} ms round trip, clock skew {skew_ms:+} ms)",
+                round_trip.as_millis()
+            );
+            clock_skew_warning(skew_ms)
+        }
+        _ => None,
+    };
+    if let Some(warning) = &clock_warning {
+        println!("Warning: {warning}");
+    }
+    Ok(Some(Preflight {
+        online: true,
+        clock_warning,
+    }))
+}
+
 fn should_restart_stream(
     current_symbol: &str,
     current_interval: &str,
@@ -159,11 +500,82 @@ fn should_restart_stream(
     current_symbol != new_symbol || current_interval != new_interval
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ConfirmAction {
+    ResetConfig,
+    Quit,
+}
+
+impl ConfirmAction {
+    fn message(self) -> &'static str {
+        match self {
+            ConfirmAction::ResetConfig => "Reset all settings to defaults? (y/n)",
+            ConfirmAction::Quit => "Quit? (y/n)",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InputKind {
+    OrderSize(Side),
+    Note,
+    Symbol,
+    ReplayStart,
+}
+
+struct InputState {
+    kind: InputKind,
+    buffer: String,
+    /// Highlighted row in the symbol dropdown.
+    selected: usize,
+}
+
+impl InputState {
+    fn new(kind: InputKind) -> Self {
+        Self {
+            kind,
+            buffer: String::new(),
+            selected: 0,
+        }
+    }
+
+    fn prompt(&self) -> String {
+        match self.kind {
+            InputKind::OrderSize(side) => format!(
+                "{} size [b/s side, Enter apply, Esc cancel]: {}",
+                side.label(),
+                self.buffer
+            ),
+            InputKind::Note => format!("Note [Enter save, Esc cancel]: {}", self.buffer),
+            InputKind::Symbol => {
+                format!("Symbol [↑/↓ pick, Enter open, Esc cancel]: {}", self.buffer)
+            }
+            InputKind::ReplayStart => format!(
+                "Replay from UTC YYYY-MM-DD [HH:MM] [Enter load, Esc cancel]: {}",
+                self.buffer
+            ),
+        }
+    }
+}
+
+type MtfUpdate = (String, Vec<(&'static str, Option<f64>)>);
+/// Benchmark klines fetched for an interval, for the relative-strength strip.
+type RelativeUpdate = (String, Vec<data::Candle>);
+
+/// Stream settings the feed task subscribes with, sent when they change.
+struct FeedSettings {
+    trade_stream: TradeStreamKind,
+    price_source: PriceSource,
+    max_streams: usize,
+}
+
 struct FetchResult {
     request_id: u64,
     symbol: String,
     interval: String,
-    candles: Result<Vec<data::Candle>, String>,
+    /// Set for a historical replay rather than the latest candles.
+    window: Option<KlineWindow>,
+    candles: Result<KlineBatch, FetchFailure>,
 }
 
 struct AppState {
@@ -171,29 +583,137 @@ struct AppState {
     layout: Arc<Mutex<LayoutManager>>,
     config_path: PathBuf,
     stream_restart_tx: tokio::sync::mpsc::Sender<(String, String)>,
+    watchlist_restart_tx: tokio::sync::mpsc::Sender<Vec<String>>,
+    feed_settings_tx: tokio::sync::mpsc::Sender<FeedSettings>,
+    stream_refresh_tx: tokio::sync::mpsc::Sender<()>,
     fetch_result_tx: tokio::sync::mpsc::UnboundedSender<FetchResult>,
+    rate_limit_tx: tokio::sync::mpsc::UnboundedSender<Duration>,
+    rate_limit_streak: u32,
     fetch_task: Option<JoinHandle<()>>,
     next_request_id: u64,
     pending_request_id: Option<u64>,
     is_loading: bool,
     connection_mode: ConnectionMode,
     connection_error: Option<String>,
+    data_error: Option<String>,
     show_help: bool,
+    /// Screen blanked by the hide key; streams keep updating underneath.
+    hidden: bool,
+    hide_key: char,
+    /// Per-stream counters overlay, toggled with Ctrl+D.
+    show_debug: bool,
+    feed_stats: Arc<FeedStats>,
+    auto_timeframe: bool,
+    max_candles_per_column: usize,
+    /// Keep the fitted overview when the pending fetch lands; set when
+    /// zooming out switched to a longer interval.
+    fit_after_fetch: bool,
+    market_stats: Option<MarketStatsModal>,
+    /// Checks the exchange's status for the symbol when its candles stop.
+    outage: OutageWatch,
+    outage_tx: tokio::sync::mpsc::UnboundedSender<(String, Result<String, String>)>,
+    stats_tx: tokio::sync::mpsc::UnboundedSender<(String, Result<data::Ticker24h, String>)>,
+    /// Tradable symbols from exchangeInfo, once loaded; search falls back to
+    /// raw entry until then.
+    symbols: Option<Vec<String>>,
+    /// Lot size and notional rules by symbol, for the order preview.
+    symbol_rules: HashMap<String, SymbolInfo>,
+    symbols_tx: tokio::sync::mpsc::UnboundedSender<Vec<SymbolInfo>>,
+    /// How often the symbol list is refetched; zero keeps the first one.
+    symbols_refresh: Duration,
+    /// Last request for the symbol list, failed or not.
+    symbols_fetched_at: Option<Instant>,
+    mtf_tx: tokio::sync::mpsc::UnboundedSender<MtfUpdate>,
+    anchors_tx: tokio::sync::mpsc::UnboundedSender<(String, DailyAnchors)>,
+    /// Last look for symbols missing today's anchors.
+    anchors_checked_at: Option<Instant>,
+    relative_tx: tokio::sync::mpsc::UnboundedSender<RelativeUpdate>,
+    input: Option<InputState>,
+    confirm: Option<ConfirmAction>,
+    notice: Option<String>,
+    preflight: bool,
+    startup_splash: bool,
+    /// Startup progress, drawn over the UI until the streams deliver.
+    warmup: Option<Warmup>,
+    confirm_quit: bool,
+    max_fps: u32,
+    max_reconnect_attempts: u32,
+    frame_limiter: FrameLimiter,
+    /// Used instead of `frame_limiter` while idle.
+    idle_limiter: FrameLimiter,
+    idle: IdleWatch,
+    /// Shared with the stream task, which drops trades while it is set.
+    trades_paused: Arc<AtomicBool>,
+    palette: ui::Palette,
+    trade_stream: TradeStreamKind,
+    price_source: PriceSource,
+    max_streams_per_connection: usize,
+    max_trades_per_drain: usize,
+    /// Trades the stream task skipped over the last second.
+    dropped_trades: usize,
+    layout_preset: LayoutPreset,
+    watchlist_order: WatchlistOrder,
+    start_on_top_mover: bool,
+    custom_layout: PanelLayout,
+    quiet: QuietMode,
+    render_dirty: Arc<AtomicBool>,
+    /// Set by the stream task when chart data changed; redrawn on
+    /// `chart_cadence`.
+    chart_updates: Arc<AtomicBool>,
+    /// The chart must be rendered on the next frame rather than reused.
+    chart_dirty: AtomicBool,
+    chart_cadence: ChartCadence,
+    /// Pending switch to the watchlist selection after arrow navigation.
+    symbol_debounce: SwitchDebounce,
 }
 
-async fn fetch_klines_with_retry(
-    symbol: &str,
-    interval: &str,
-    limit: u32,
-) -> Result<Vec<data::Candle>, String> {
+/// Why `retry_fetch` gave up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FetchFailure {
+    message: String,
+    /// Binance turned the request down (see `FetchError::is_rejected`), so
+    /// it wasn't retried.
+    rejected: bool,
+}
+
+impl std::fmt::Display for FetchFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// Runs `fetch` up to `FETCH_RETRIES` times. Rate-limited attempts wait for the
+/// server's `Retry-After` instead of the fixed retry delay and are reported
+/// through `on_rate_limit` so the UI can show the backoff.
+async fn retry_fetch<T, F, Fut>(
+    mut fetch: F,
+    mut on_rate_limit: impl FnMut(Duration),
+) -> Result<T, FetchFailure>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, FetchError>>,
+{
     let mut last_error = String::from("unknown error");
 
     for attempt in 1..=FETCH_RETRIES {
-        let fetch_result =
-            tokio::time::timeout(FETCH_TIMEOUT, fetch_klines(symbol, interval, limit)).await;
+        let mut delay = FETCH_RETRY_DELAY;
 
-        match fetch_result {
-            Ok(Ok(candles)) => return Ok(candles),
+        match tokio::time::timeout(FETCH_TIMEOUT, fetch()).await {
+            Ok(Ok(value)) => return Ok(value),
+            Ok(Err(FetchError::RateLimited { retry_after })) => {
+                last_error = format!(
+                    "attempt {attempt}/{FETCH_RETRIES} rate limited (retry after {}s)",
+                    retry_after.as_secs()
+                );
+                delay = retry_after;
+                on_rate_limit(retry_after);
+            }
+            Ok(Err(err)) if err.is_rejected() => {
+                return Err(FetchFailure {
+                    message: err.to_string(),
+                    rejected: true,
+                });
+            }
             Ok(Err(err)) => {
                 last_error = format!("attempt {attempt}/{FETCH_RETRIES} failed: {err}");
             }
@@ -206,23 +726,126 @@ async fn fetch_klines_with_retry(
         }
 
         if attempt < FETCH_RETRIES {
-            tokio::time::sleep(FETCH_RETRY_DELAY).await;
+            tokio::time::sleep(delay).await;
         }
     }
 
-    Err(last_error)
+    Err(FetchFailure {
+        message: last_error,
+        rejected: false,
+    })
+}
+
+async fn fetch_klines_with_retry(
+    symbol: &str,
+    interval: &str,
+    limit: u32,
+    window: Option<KlineWindow>,
+    rate_limit_tx: &tokio::sync::mpsc::UnboundedSender<Duration>,
+) -> Result<KlineBatch, FetchFailure> {
+    retry_fetch(
+        || fetch_kline_batch(symbol, interval, limit, window),
+        |retry_after| {
+            let _ = rate_limit_tx.send(retry_after);
+        },
+    )
+    .await
 }
 
 impl AppState {
+    fn mark_dirty(&self) {
+        self.render_dirty.store(true, Ordering::Relaxed);
+        self.chart_dirty.store(true, Ordering::Relaxed);
+    }
+
+    fn active_limiter(&mut self) -> &mut FrameLimiter {
+        if self.idle.is_idle() {
+            &mut self.idle_limiter
+        } else {
+            &mut self.frame_limiter
+        }
+    }
+
     async fn snapshot_config(&self) -> AppConfig {
         let chart_guard = self.chart.lock().await;
         let layout_guard = self.layout.lock().await;
         AppConfig {
             watchlist: layout_guard.watchlist.clone(),
+            watchlist_sections: layout_guard.sections.clone(),
             selected_symbol: layout_guard.selected_symbol,
             symbol: chart_guard.symbol.clone(),
             timeframe: layout_guard.timeframe.current(),
             zoom: chart_guard.zoom,
+            zoom_preset: chart_guard.preset,
+            max_fps: self.max_fps,
+            chart_refresh_ms: self.chart_cadence.interval.as_millis() as u64,
+            idle_after_secs: self.idle.timeout.as_secs(),
+            max_reconnect_attempts: self.max_reconnect_attempts,
+            symbols_refresh_hours: self.symbols_refresh.as_secs() / 3600,
+            preflight: self.preflight,
+            startup_splash: self.startup_splash,
+            confirm_quit: self.confirm_quit,
+            flash_updates: layout_guard.flash_enabled,
+            half_block: chart_guard.half_block,
+            braille: chart_guard.braille,
+            palette: self.palette,
+            trade_stream: self.trade_stream,
+            price_source: self.price_source,
+            change_baseline: layout_guard.change_baseline,
+            max_streams_per_connection: self.max_streams_per_connection,
+            max_trades_per_drain: self.max_trades_per_drain,
+            quiet_hours: self.quiet.hours,
+            candle_emphasis: chart_guard.emphasis,
+            live_candle: chart_guard.live_candle,
+            candle_width: chart_guard.candle_width,
+            candle_gap: chart_guard.candle_gap,
+            candle_style: chart_guard.candle_style,
+            tape_format: layout_guard.tradetape.format.spec().to_string(),
+            tape_direction: layout_guard.tradetape.direction,
+            show_mtf: layout_guard.mtf.visible,
+            show_relative: chart_guard.relative.visible,
+            show_roc: chart_guard.show_roc,
+            roc_period: chart_guard.roc_period,
+            show_ribbon: layout_guard.ribbon.visible,
+            show_returns: layout_guard.returns.visible,
+            show_events: layout_guard.events.visible,
+            event_log_size: layout_guard.events.capacity(),
+            book_view: layout_guard.orderbook.view,
+            book_follow_trades: layout_guard.orderbook.follow_trades,
+            wide_spread_pct: layout_guard.orderbook.spread.threshold_pct,
+            layout_preset: self.layout_preset,
+            watchlist_order: self.watchlist_order,
+            start_on_top_mover: self.start_on_top_mover,
+            custom_layout: self.custom_layout.clone(),
+            notes: chart_guard.notes.clone(),
+            indicators: chart_guard.indicator_prefs.clone(),
+            active_indicators: chart_guard.default_indicators.active(),
+            line_styles: chart_guard.line_styles,
+            invert_y: chart_guard.invert_y,
+            price_axis: chart_guard.price_axis,
+            price_hud: chart_guard.price_hud,
+            round_price_labels: chart_guard.round_price_labels,
+            snap_crosshair: chart_guard.snap_to_tick,
+            infer_price_precision: chart_guard.infer_precision,
+            chart_padding: chart_guard.padding_factor,
+            show_heatmap: chart_guard.heatmap.visible,
+            show_squeeze: chart_guard.show_squeeze,
+            activity_tint: chart_guard.activity_tint,
+            highlight_recent: chart_guard.highlight_recent,
+            recent_candles: chart_guard.recent_candles,
+            indicator_basis: chart_guard.indicator_basis,
+            candle_time_basis: chart_guard.time_basis,
+            grid: chart_guard.grid.mode,
+            grid_style: chart_guard.grid.style,
+            grid_spacing: chart_guard.grid.spacing,
+            fit_on_load: chart_guard.fit_on_load,
+            smooth_catch_up: chart_guard.smooth_catch_up,
+            hide_key: self.hide_key,
+            auto_timeframe: self.auto_timeframe,
+            max_candles_per_column: self.max_candles_per_column,
+            volume_format: layout_guard.theme.volume_format,
+            max_price_decimals: layout_guard.theme.max_price_decimals,
+            dense: layout_guard.theme.dense,
         }
         .sanitized()
     }
@@ -234,7 +857,253 @@ impl AppState {
         }
     }
 
+    async fn export_config(&mut self) {
+        let config = self.snapshot_config().await;
+        match save_config(&self.config_path, &config) {
+            Ok(()) => {
+                self.notice = Some(format!("Config saved to {}", self.config_path.display()));
+            }
+            Err(err) => {
+                self.data_error = Some(format!("config save failed: {}", err));
+            }
+        }
+    }
+
+    async fn copy_symbol_price(&mut self) {
+        let chart = self.chart.lock().await;
+        let price = chart.candles.back().and_then(|c| c.close.parse().ok());
+        let symbol = chart.symbol.clone();
+        let price_precision = chart.price_precision();
+        drop(chart);
+        let theme = ui::Theme {
+            price_precision,
+            ..self.layout.lock().await.theme
+        };
+        let text = clipboard::format_copy_text(&symbol, price, &theme);
+
+        match clipboard::copy_text(&text) {
+            Ok(clipboard::CopyMethod::System) => {
+                self.notice = Some(format!("Copied \"{}\"", text));
+            }
+            Ok(clipboard::CopyMethod::Osc52) => {
+                self.notice = Some(format!("Copied \"{}\" via terminal (OSC 52)", text));
+            }
+            Err(err) => {
+                self.data_error = Some(format!("copy failed: {}", err));
+            }
+        }
+    }
+
+    /// Writes a Markdown snapshot of the chart, book, tape and 24h stats to
+    /// a timestamped file in the working directory.
+    async fn export_dashboard(&mut self) {
+        let chart = self.chart.lock().await;
+        let layout = self.layout.lock().await;
+        let mut indicators = Vec::new();
+        if chart.show_sma {
+            if let Some(value) = chart.indicators.latest_sma() {
+                indicators.push((format!("SMA{}", chart.indicators.sma_period()), value));
+            }
+        }
+        if chart.show_rsi {
+            if let Some(value) = chart.indicators.latest_rsi() {
+                indicators.push((format!("RSI{}", chart.indicators.rsi_period()), value));
+            }
+        }
+        let trades: Vec<data::Trade> = layout.tradetape.trades.iter().cloned().collect();
+        let taken_at_ms = unix_now_secs() * 1000;
+        let dashboard = report::Dashboard {
+            symbol: &chart.symbol,
+            interval: &chart.interval,
+            taken_at_ms,
+            last_price: chart.candles.back().and_then(|c| c.close.parse().ok()),
+            change_pct: layout
+                .watch_prices
+                .get(&chart.symbol)
+                .map(|price| price.change_pct),
+            indicators,
+            book: layout.orderbook.orderbook.as_ref(),
+            trades: &trades,
+            stats: self
+                .market_stats
+                .as_ref()
+                .filter(|modal| modal.symbol == chart.symbol)
+                .and_then(|modal| modal.stats.as_ref()),
+            max_decimals: layout.theme.max_price_decimals,
+            price_precision: chart.price_precision(),
+            volume_format: layout.theme.volume_format,
+        };
+        let text = report::render_markdown(&dashboard);
+        let path = std::env::current_dir()
+            .unwrap_or_else(|_| PathBuf::from("."))
+            .join(report::report_file_name(&chart.symbol, taken_at_ms));
+        drop(layout);
+        drop(chart);
+
+        match std::fs::write(&path, text) {
+            Ok(()) => self.notice = Some(format!("Dashboard saved to {}", path.display())),
+            Err(err) => self.data_error = Some(format!("dashboard export failed: {}", err)),
+        }
+    }
+
+    async fn reset_config(&mut self) {
+        let mut config = AppConfig::default().sanitized();
+
+        let mut layout = self.layout.lock().await;
+        layout.watchlist = config.watchlist.clone();
+        layout.sections = config.watchlist_sections.clone();
+        layout.selected_symbol = config.selected_symbol;
+        layout.watch_prices.clear();
+        layout.change_baseline = config.change_baseline;
+        self.anchors_checked_at = None;
+        layout.timeframe.set_current(config.timeframe);
+        layout.flash_enabled = config.flash_updates;
+        layout.flashes.clear();
+        layout.theme = ui::Theme::new(config.palette, layout.theme.capability);
+        layout.theme.volume_format = config.volume_format;
+        layout.theme.max_price_decimals = config.max_price_decimals;
+        layout.theme.dense = config.dense;
+        layout.mtf.visible = config.show_mtf;
+        layout.ribbon.visible = config.show_ribbon;
+        layout.returns.visible = config.show_returns;
+        layout.events.visible = config.show_events;
+        layout.events.set_capacity(config.event_log_size);
+        layout.orderbook.view = config.book_view;
+        layout.orderbook.follow_trades = config.book_follow_trades;
+        layout.orderbook.spread.threshold_pct = config.wide_spread_pct;
+        layout.tradetape.format = TapeFormat::parse(&config.tape_format).unwrap_or_default();
+        layout.tradetape.direction = config.tape_direction;
+        layout.apply_panels(&config.layout_preset.panels(&config.custom_layout));
+        drop(layout);
+
+        let mut chart = self.chart.lock().await;
+        chart.zoom = config.zoom;
+        chart.preset = config.zoom_preset;
+        chart.offset = 0;
+        chart.crosshair = None;
+        chart.half_block = config.half_block;
+        chart.braille = config.braille;
+        chart.emphasis = config.candle_emphasis;
+        chart.live_candle = config.live_candle;
+        chart.candle_width = config.candle_width;
+        chart.candle_gap = config.candle_gap;
+        chart.candle_style = config.candle_style;
+        chart.invert_y = config.invert_y;
+        chart.price_axis = config.price_axis;
+        chart.price_hud = config.price_hud;
+        chart.round_price_labels = config.round_price_labels;
+        chart.snap_to_tick = config.snap_crosshair;
+        chart.infer_precision = config.infer_price_precision;
+        chart.padding_factor = config.chart_padding;
+        chart.heatmap.visible = config.show_heatmap;
+        chart.show_squeeze = config.show_squeeze;
+        chart.activity_tint = config.activity_tint;
+        chart.highlight_recent = config.highlight_recent;
+        chart.recent_candles = config.recent_candles;
+        chart.indicator_basis = config.indicator_basis;
+        chart.time_basis = config.candle_time_basis;
+        chart.grid = Grid {
+            mode: config.grid,
+            style: config.grid_style,
+            spacing: config.grid_spacing,
+        };
+        chart.fit_on_load = config.fit_on_load;
+        chart.smooth_catch_up = config.smooth_catch_up;
+        chart.relative.visible = config.show_relative;
+        chart.show_roc = config.show_roc;
+        chart.roc_period = config.roc_period;
+        chart.indicator_prefs = config.indicators.clone();
+        chart.default_indicators = IndicatorPrefs::from_active(&config.active_indicators);
+        chart.line_styles = config.line_styles;
+        chart.apply_indicator_prefs();
+        // Notes are user data rather than settings, so a reset keeps them.
+        config.notes = chart.notes.clone();
+        drop(chart);
+
+        self.max_fps = config.max_fps;
+        self.max_reconnect_attempts = config.max_reconnect_attempts;
+        self.symbols_refresh = Duration::from_secs(config.symbols_refresh_hours * 3600);
+        self.preflight = config.preflight;
+        self.startup_splash = config.startup_splash;
+        self.confirm_quit = config.confirm_quit;
+        self.frame_limiter = FrameLimiter::new(config.max_fps);
+        self.chart_cadence = ChartCadence::new(config.chart_refresh_ms);
+        self.idle = IdleWatch::new(Duration::from_secs(config.idle_after_secs), Instant::now());
+        self.trades_paused.store(false, Ordering::Relaxed);
+        self.palette = config.palette;
+        self.trade_stream = config.trade_stream;
+        self.price_source = config.price_source;
+        self.max_streams_per_connection = config.max_streams_per_connection;
+        self.max_trades_per_drain = config.max_trades_per_drain;
+        self.layout_preset = config.layout_preset;
+        self.watchlist_order = config.watchlist_order;
+        self.start_on_top_mover = config.start_on_top_mover;
+        self.custom_layout = config.custom_layout.clone();
+        self.hide_key = config.hide_key;
+        self.auto_timeframe = config.auto_timeframe;
+        self.max_candles_per_column = config.max_candles_per_column;
+        self.quiet = QuietMode::new(config.quiet_hours);
+
+        let _ = self
+            .watchlist_restart_tx
+            .send(config.watchlist.clone())
+            .await;
+        let _ = self
+            .feed_settings_tx
+            .send(FeedSettings {
+                trade_stream: config.trade_stream,
+                price_source: config.price_source,
+                max_streams: config.max_streams_per_connection,
+            })
+            .await;
+        self.queue_fetch(
+            config.symbol.clone(),
+            config.timeframe.binance_interval().to_string(),
+            config.timeframe.limit(),
+        );
+
+        match save_config(&self.config_path, &config) {
+            Ok(()) => self.notice = Some("Settings reset to defaults".to_string()),
+            Err(err) => self.data_error = Some(format!("config save failed: {}", err)),
+        }
+    }
+
+    /// Answers the open confirmation. Returns true when the app should exit.
+    async fn handle_confirm_key(&mut self, code: KeyCode) -> bool {
+        let Some(action) = self.confirm else {
+            return false;
+        };
+
+        match code {
+            KeyCode::Char('y') | KeyCode::Char('Y') => {
+                self.confirm = None;
+                match action {
+                    ConfirmAction::ResetConfig => self.reset_config().await,
+                    ConfirmAction::Quit => {
+                        self.persist_config().await;
+                        return true;
+                    }
+                }
+            }
+            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                self.confirm = None;
+            }
+            _ => {}
+        }
+        false
+    }
+
     fn queue_fetch(&mut self, symbol: String, interval: String, limit: u32) {
+        self.queue_window_fetch(symbol, interval, limit, None);
+    }
+
+    fn queue_window_fetch(
+        &mut self,
+        symbol: String,
+        interval: String,
+        limit: u32,
+        window: Option<KlineWindow>,
+    ) {
         self.next_request_id = self.next_request_id.wrapping_add(1);
         let request_id = self.next_request_id;
         self.pending_request_id = Some(request_id);
@@ -245,13 +1114,16 @@ impl AppState {
         }
 
         let tx = self.fetch_result_tx.clone();
+        let rate_limit_tx = self.rate_limit_tx.clone();
         let handle = tokio::spawn(async move {
-            let candles = fetch_klines_with_retry(&symbol, &interval, limit).await;
+            let candles =
+                fetch_klines_with_retry(&symbol, &interval, limit, window, &rate_limit_tx).await;
 
             let _ = tx.send(FetchResult {
                 request_id,
                 symbol,
                 interval,
+                window,
                 candles,
             });
         });
@@ -286,6 +1158,74 @@ impl AppState {
         }
     }
 
+    /// After zooming out, checks whether the overview merges more candles
+    /// per column than configured and either refetches the same span at a
+    /// longer interval or suggests one in the status bar.
+    async fn check_zoom_resolution(&mut self) {
+        let chart_width = self.layout.lock().await.chart_width;
+        let chart = self.chart.lock().await;
+        let per_column = chart.candles_per_column(chart_width);
+        if per_column <= self.max_candles_per_column {
+            return;
+        }
+        let Some(step) = interval_duration(&chart.interval) else {
+            return;
+        };
+        let span = step * chart.candles.len() as u32;
+        let columns = chart.plot_width(chart_width).max(1);
+        let Some(coarser) =
+            coarser_interval(&chart.interval, span, columns * self.max_candles_per_column)
+        else {
+            return;
+        };
+        let symbol = chart.symbol.clone();
+        let interval = chart.interval.clone();
+        drop(chart);
+
+        if self.auto_timeframe {
+            let limit = interval_duration(coarser)
+                .map(|longer| (span.as_secs_f64() / longer.as_secs_f64()).ceil() as u32)
+                .unwrap_or(1)
+                .clamp(1, 1000);
+            self.fit_after_fetch = true;
+            self.queue_fetch(symbol, coarser.to_string(), limit);
+            self.notice = Some(format!(
+                "Switched {interval} to {coarser}: {per_column} candles per column"
+            ));
+        } else {
+            self.notice = Some(format!(
+                "{per_column} {interval} candles per column; try {coarser} (Tab)"
+            ));
+        }
+    }
+
+    /// Loads the candles from `start_ms` onward at the current interval and
+    /// holds them, without live updates, until the review is left.
+    async fn start_replay(&mut self, start_ms: u64) -> Result<(), String> {
+        let chart = self.chart.lock().await;
+        let symbol = chart.symbol.clone();
+        let interval = chart.interval.clone();
+        drop(chart);
+        let limit = self.layout.lock().await.timeframe.current().limit();
+        let window = replay_window(start_ms, &interval, limit, unix_now_secs() * 1000)?;
+        let limit = limit.clamp(1, REPLAY_MAX_CANDLES);
+        self.queue_window_fetch(symbol, interval, limit, Some(window));
+        Ok(())
+    }
+
+    /// Leaves a historical replay by reloading the latest candles.
+    async fn exit_replay(&mut self) {
+        let chart = self.chart.lock().await;
+        if chart.review.is_none() {
+            return;
+        }
+        let symbol = chart.symbol.clone();
+        let interval = chart.interval.clone();
+        drop(chart);
+        let limit = self.layout.lock().await.timeframe.current().limit();
+        self.queue_fetch(symbol, interval, limit);
+    }
+
     async fn switch_timeframe(&mut self, timeframe: ui::Timeframe) {
         let interval = timeframe.binance_interval().to_string();
         let limit = timeframe.limit();
@@ -300,102 +1240,654 @@ impl AppState {
         self.pending_request_id = None;
         self.fetch_task = None;
         self.is_loading = false;
+        let keep_fit = std::mem::take(&mut self.fit_after_fetch);
+        self.mark_dirty();
 
         match result.candles {
-            Ok(initial_candles) => {
+            Ok(batch) => {
+                self.rate_limit_streak = 0;
+                self.data_error = match (batch.candles.is_empty(), result.window) {
+                    (false, _) => None,
+                    (true, None) => Some(no_data_message(&result.symbol)),
+                    (true, Some(_)) => Some("No candles in that range".to_string()),
+                };
+
                 let mut chart_guard = self.chart.lock().await;
+                if chart_guard.symbol != result.symbol {
+                    chart_guard.heatmap.clear();
+                }
                 chart_guard.symbol = result.symbol.clone();
                 chart_guard.interval = result.interval.clone();
                 chart_guard.candles.clear();
+                chart_guard.dropped_candles.clear();
                 chart_guard.offset = 0;
-                chart_guard.update_candles(initial_candles);
+                chart_guard.fit = chart_guard.fit_on_load || keep_fit;
+                chart_guard.crosshair = None;
+                chart_guard.review = result.window;
+                chart_guard.update_candles(batch.candles);
+                chart_guard.reject_candles(batch.rejected);
+                chart_guard.apply_indicator_prefs();
                 drop(chart_guard);
-                let _ = self
-                    .stream_restart_tx
-                    .send((result.symbol, result.interval))
-                    .await;
+                self.refresh_lot_rules().await;
+                if self.data_error.is_none() {
+                    self.adopt_into_empty_watchlist(&result.symbol).await;
+                }
+                // A replay keeps the streams on the same symbol and interval;
+                // their updates are just held back until it ends.
+                if result.window.is_none() {
+                    let _ = self
+                        .stream_restart_tx
+                        .send((result.symbol, result.interval))
+                        .await;
+                }
                 self.persist_config().await;
             }
             Err(err) => {
-                self.connection_error = Some(format!("fetch: {}", err));
-                eprintln!("Kline fetch failed: {}", err);
+                let mut chart_guard = self.chart.lock().await;
+                // Only a request Binance turned down means the symbol is bad;
+                // after a timeout or server error the previous chart and
+                // streams stay up.
+                if chart_guard.symbol == result.symbol || !err.rejected {
+                    self.data_error = Some(format!("fetch: {}", err));
+                    return;
+                }
+
+                self.data_error = Some(format!("{}: {}", no_data_message(&result.symbol), err));
+                chart_guard.symbol = result.symbol;
+                chart_guard.interval = result.interval;
+                chart_guard.candles.clear();
+                chart_guard.dropped_candles.clear();
+                chart_guard.offset = 0;
+                chart_guard.crosshair = None;
+                chart_guard.heatmap.clear();
+                chart_guard.indicators.reset();
+                chart_guard.apply_indicator_prefs();
             }
         }
     }
 
-    fn apply_health_update(&mut self, update: HealthUpdate) {
-        self.connection_mode = update.overall_mode();
-        self.connection_error = update.last_error;
+    /// Seeds an empty watchlist with the first symbol that loads, so
+    /// navigation and the price feed come back.
+    async fn adopt_into_empty_watchlist(&self, symbol: &str) {
+        let mut layout = self.layout.lock().await;
+        if !layout.watchlist.is_empty() || !layout.add_symbol(symbol) {
+            return;
+        }
+        let watchlist = layout.watchlist.clone();
+        drop(layout);
+        let _ = self.watchlist_restart_tx.send(watchlist).await;
     }
-}
 
-#[tokio::main]
-async fn main() -> io::Result<()> {
-    if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
-        eprintln!("TickerTUI needs an interactive terminal (TTY). Run it in a normal shell.");
-        return Ok(());
+    /// Fetches the exchange's symbol list at startup and then every
+    /// `symbols_refresh`. A failed fetch leaves the last list in place.
+    fn refresh_symbols(&mut self, now: Instant) {
+        if let Some(at) = self.symbols_fetched_at {
+            if self.symbols_refresh.is_zero()
+                || now.saturating_duration_since(at) < self.symbols_refresh
+            {
+                return;
+            }
+        }
+        self.symbols_fetched_at = Some(now);
+        let tx = self.symbols_tx.clone();
+        tokio::spawn(async move {
+            if let Ok(Ok(symbols)) = tokio::time::timeout(FETCH_TIMEOUT, fetch_symbols()).await {
+                let _ = tx.send(symbols);
+            }
+        });
     }
 
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
-
-    let config_path = config_path();
-    let initial_config = load_config(&config_path).sanitized();
-
-    let symbol = initial_config.symbol.clone();
-    let timeframe = initial_config.timeframe;
-    let interval = timeframe.binance_interval().to_string();
-    let limit = timeframe.limit();
+    /// Swaps in a fresh symbol list for search and the lot rules, flagging
+    /// watchlist symbols that have stopped trading since the last one.
+    async fn apply_symbols(&mut self, symbols: Vec<SymbolInfo>) {
+        let names: Vec<String> = symbols.iter().map(|info| info.symbol.clone()).collect();
+        if let Some(previous) = &self.symbols {
+            let watchlist = self.layout.lock().await.watchlist.clone();
+            let gone = delisted(&watchlist, previous, &names);
+            if !gone.is_empty() {
+                self.notice = Some(format!("No longer trading: {}", gone.join(", ")));
+                self.mark_dirty();
+            }
+        }
+        self.symbols = Some(names);
+        self.symbol_rules = symbols
+            .into_iter()
+            .map(|info| (info.symbol.clone(), info))
+            .collect();
+        self.refresh_lot_rules().await;
+    }
 
-    let initial_candles = fetch_klines(&symbol, &interval, limit)
-        .await
-        .unwrap_or_default();
+    /// Points the order preview and crosshair at the charted symbol's rules.
+    async fn refresh_lot_rules(&self) {
+        let mut chart = self.chart.lock().await;
+        let rules = self.symbol_rules.get(&chart.symbol).cloned();
+        chart.tick_size = rules.as_ref().map_or(0.0, |rules| rules.tick_size);
+        drop(chart);
+        self.layout.lock().await.orderbook.lot_rules = rules;
+    }
 
-    let chart = Arc::new(Mutex::new(Chart::new(symbol.clone(), interval.clone())));
-    {
-        let mut chart_guard = chart.lock().await;
-        chart_guard.zoom = initial_config.zoom;
-        chart_guard.update_candles(initial_candles);
+    /// Dropdown entries for the symbol search being typed, if any.
+    fn search_matches(&self) -> Vec<String> {
+        match (&self.input, &self.symbols) {
+            (Some(input), Some(symbols)) if input.kind == InputKind::Symbol => {
+                ui::search::symbol_matches(symbols, &input.buffer, ui::search::MAX_MATCHES)
+            }
+            _ => Vec::new(),
+        }
     }
 
-    let (restart_tx, mut restart_rx) = tokio::sync::mpsc::channel::<(String, String)>(10);
-    let (fetch_result_tx, mut fetch_result_rx) = tokio::sync::mpsc::unbounded_channel();
-    let (health_tx, mut health_rx) = tokio::sync::mpsc::unbounded_channel();
-    let chart_clone = chart.clone();
-    let layout_clone = Arc::new(Mutex::new(LayoutManager::new(
-        initial_config.watchlist.clone(),
+    async fn handle_input_key(&mut self, code: KeyCode) {
+        let matches = self.search_matches();
+        let Some(input) = self.input.as_mut() else {
+            return;
+        };
+
+        match (input.kind, code) {
+            (_, KeyCode::Esc) => {
+                self.input = None;
+            }
+            (_, KeyCode::Backspace) => {
+                input.buffer.pop();
+                input.selected = 0;
+            }
+            (InputKind::OrderSize(_), KeyCode::Char('b') | KeyCode::Char('B')) => {
+                input.kind = InputKind::OrderSize(Side::Buy);
+            }
+            (InputKind::OrderSize(_), KeyCode::Char('s') | KeyCode::Char('S')) => {
+                input.kind = InputKind::OrderSize(Side::Sell);
+            }
+            (InputKind::OrderSize(_), KeyCode::Char(c)) if c.is_ascii_digit() || c == '.' => {
+                input.buffer.push(c);
+            }
+            (InputKind::OrderSize(side), KeyCode::Enter) => {
+                let preview = if input.buffer.is_empty() {
+                    None
+                } else {
+                    match input.buffer.parse::<f64>() {
+                        Ok(size) if size > 0.0 => Some((side, size)),
+                        _ => return,
+                    }
+                };
+                self.layout.lock().await.orderbook.preview = preview;
+                self.input = None;
+            }
+            (InputKind::Note, KeyCode::Char(c)) => {
+                input.buffer.push(c);
+            }
+            (InputKind::Symbol, KeyCode::Char(c)) if c.is_ascii_alphanumeric() => {
+                input.buffer.push(c.to_ascii_uppercase());
+                input.selected = 0;
+            }
+            (InputKind::Symbol, KeyCode::Up) => {
+                input.selected = input.selected.saturating_sub(1);
+            }
+            (InputKind::Symbol, KeyCode::Down) => {
+                input.selected = (input.selected + 1).min(matches.len().saturating_sub(1));
+            }
+            (InputKind::Symbol, KeyCode::Enter) => {
+                let symbol = matches
+                    .get(input.selected)
+                    .cloned()
+                    .unwrap_or_else(|| input.buffer.clone());
+                self.input = None;
+                if !symbol.is_empty() {
+                    self.switch_symbol(symbol).await;
+                }
+            }
+            (InputKind::ReplayStart, KeyCode::Char(c))
+                if c.is_ascii_digit() || matches!(c, '-' | ':' | ' ') =>
+            {
+                input.buffer.push(c);
+            }
+            (InputKind::ReplayStart, KeyCode::Enter) => {
+                let parsed = ui::timeaxis::parse_utc(&input.buffer);
+                self.input = None;
+                let started = match parsed {
+                    Ok(start_ms) => self.start_replay(start_ms).await,
+                    Err(err) => Err(err),
+                };
+                if let Err(err) = started {
+                    self.notice = Some(format!("Replay: {err}"));
+                }
+            }
+            (InputKind::Note, KeyCode::Enter) => {
+                let text = input.buffer.trim().to_string();
+                self.input = None;
+                if text.is_empty() {
+                    return;
+                }
+                if self.chart.lock().await.add_note(text) {
+                    self.persist_config().await;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    async fn open_market_stats(&mut self) {
+        let symbol = self.chart.lock().await.symbol.clone();
+        self.market_stats = Some(MarketStatsModal::new(symbol));
+        self.refresh_market_stats(Instant::now());
+    }
+
+    fn refresh_market_stats(&mut self, now: Instant) {
+        let Some(modal) = self.market_stats.as_mut() else {
+            return;
+        };
+        if !modal.needs_refresh(now) {
+            return;
+        }
+
+        modal.last_requested = Some(now);
+        let symbol = modal.symbol.clone();
+        let tx = self.stats_tx.clone();
+        let rate_limit_tx = self.rate_limit_tx.clone();
+        tokio::spawn(async move {
+            let result = retry_fetch(
+                || fetch_ticker_24h(&symbol),
+                |retry_after| {
+                    let _ = rate_limit_tx.send(retry_after);
+                },
+            )
+            .await
+            .map_err(|err| err.to_string());
+            let _ = tx.send((symbol, result));
+        });
+    }
+
+    /// Polls the charted symbol's exchange status, more often while its
+    /// candles are stale.
+    async fn refresh_exchange_status(&mut self, now: Instant) {
+        let symbol = self.chart.lock().await.symbol.clone();
+        if !self.outage.needs_check(&symbol, now) {
+            return;
+        }
+        self.outage.begin_check(&symbol, now);
+        let tx = self.outage_tx.clone();
+        tokio::spawn(async move {
+            let result = tokio::time::timeout(FETCH_TIMEOUT, fetch_symbol_status(&symbol))
+                .await
+                .map_err(|_| "timed out".to_string())
+                .and_then(|result| result.map_err(|err| err.to_string()));
+            let _ = tx.send((symbol, result));
+        });
+    }
+
+    fn apply_market_stats(&mut self, symbol: String, result: Result<data::Ticker24h, String>) {
+        if let Some(modal) = self.market_stats.as_mut() {
+            if modal.symbol == symbol {
+                modal.apply(result);
+                self.mark_dirty();
+            }
+        }
+    }
+
+    /// Refetches the benchmark klines for the relative-strength strip when
+    /// the interval changed or the last fetch is old.
+    async fn refresh_relative(&mut self, now: Instant) {
+        let mut chart = self.chart.lock().await;
+        let (symbol, interval) = (chart.symbol.clone(), chart.interval.clone());
+        if !chart.relative.needs_refresh(&symbol, &interval, now) {
+            return;
+        }
+        chart.relative.begin_refresh(&interval, now);
+        drop(chart);
+
+        let limit = self.layout.lock().await.timeframe.current().limit();
+        let tx = self.relative_tx.clone();
+        tokio::spawn(async move {
+            let fetched = tokio::time::timeout(
+                FETCH_TIMEOUT,
+                fetch_klines(ui::relative::BENCHMARK, &interval, limit),
+            )
+            .await;
+            if let Ok(Ok(candles)) = fetched {
+                let _ = tx.send((interval, candles));
+            }
+        });
+    }
+
+    /// Fetches the daily open and previous close of watchlist symbols that
+    /// lack them for the current UTC day, when the change baseline needs them.
+    async fn refresh_daily_anchors(&mut self, now: Instant) {
+        if self
+            .anchors_checked_at
+            .is_some_and(|at| now.saturating_duration_since(at) < ANCHOR_CHECK_INTERVAL)
+        {
+            return;
+        }
+        let layout = self.layout.lock().await;
+        if layout.change_baseline == ChangeBaseline::Open24h {
+            return;
+        }
+        self.anchors_checked_at = Some(now);
+        let today = unix_now_secs() / 86_400;
+        let missing: Vec<String> = layout
+            .watchlist
+            .iter()
+            .filter(|symbol| {
+                layout
+                    .daily_anchors
+                    .get(*symbol)
+                    .is_none_or(|anchors| anchors.day != today)
+            })
+            .cloned()
+            .collect();
+        drop(layout);
+        if missing.is_empty() {
+            return;
+        }
+
+        let tx = self.anchors_tx.clone();
+        tokio::spawn(async move {
+            let fetches = missing.into_iter().map(|symbol| async move {
+                let anchors = tokio::time::timeout(FETCH_TIMEOUT, fetch_klines(&symbol, "1d", 2))
+                    .await
+                    .ok()
+                    .and_then(Result::ok)
+                    .and_then(|candles| DailyAnchors::from_daily_candles(&candles));
+                (symbol, anchors)
+            });
+            for (symbol, anchors) in futures_util::future::join_all(fetches).await {
+                if let Some(anchors) = anchors {
+                    let _ = tx.send((symbol, anchors));
+                }
+            }
+        });
+    }
+
+    async fn refresh_mtf(&mut self, now: Instant) {
+        let symbol = self.chart.lock().await.symbol.clone();
+        let mut layout = self.layout.lock().await;
+        if !layout.mtf.needs_refresh(&symbol, now) {
+            return;
+        }
+        layout.mtf.begin_refresh(&symbol, now);
+        drop(layout);
+
+        let tx = self.mtf_tx.clone();
+        tokio::spawn(async move {
+            let fetches = MTF_INTERVALS.iter().map(|interval| {
+                let symbol = symbol.clone();
+                async move {
+                    let change =
+                        tokio::time::timeout(FETCH_TIMEOUT, fetch_klines(&symbol, interval, 2))
+                            .await
+                            .ok()
+                            .and_then(Result::ok)
+                            .and_then(|candles| change_from_candles(&candles));
+                    (*interval, change)
+                }
+            });
+            let changes = futures_util::future::join_all(fetches).await;
+            let _ = tx.send((symbol, changes));
+        });
+    }
+
+    fn apply_rate_limit(&mut self, retry_after: Duration) {
+        self.rate_limit_streak = self.rate_limit_streak.saturating_add(1);
+        self.notice = Some(format!(
+            "rate limited, retrying in {}s",
+            retry_after.as_secs().max(1)
+        ));
+        self.mark_dirty();
+    }
+
+    fn effective_connection_mode(&self) -> ConnectionMode {
+        if self.rate_limit_streak >= RATE_LIMIT_DEGRADE_AFTER {
+            ConnectionMode::Degraded
+        } else {
+            self.connection_mode
+        }
+    }
+
+    /// Restarts every stream with a fresh attempt budget and reloads the
+    /// chart, for when the feeds gave up on a dead network.
+    async fn refresh_streams(&mut self) {
+        let _ = self.stream_refresh_tx.send(()).await;
+        let chart = self.chart.lock().await;
+        let (symbol, interval) = (chart.symbol.clone(), chart.interval.clone());
+        drop(chart);
+        let limit = self.layout.lock().await.timeframe.current().limit();
+        self.queue_fetch(symbol, interval, limit);
+        self.notice = Some("Reconnecting streams".to_string());
+    }
+
+    async fn apply_health_update(&mut self, update: HealthUpdate) {
+        self.connection_mode = update.overall_mode();
+        self.dropped_trades = update.dropped_trades;
+        if let Some(warmup) = &mut self.warmup {
+            warmup.set_streams(
+                update.kline == FeedState::Live,
+                update.orderbook == FeedState::Live,
+                update.trades == FeedState::Live,
+            );
+        }
+        self.connection_error = update.last_error;
+        let stale = |feed| update.stale.contains(&feed);
+
+        let mut layout = self.layout.lock().await;
+        layout.orderbook.stale = stale(Feed::OrderBook);
+        layout.tradetape.stale = stale(Feed::Trades);
+        layout.watchlist_stale = stale(Feed::Prices);
+        drop(layout);
+        self.chart.lock().await.stale = stale(Feed::Kline);
+        self.outage.set_stale(stale(Feed::Kline), Instant::now());
+        self.mark_dirty();
+    }
+}
+
+#[tokio::main]
+async fn main() -> io::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("--backtest") {
+        return run_backtest(&args[1..]).await;
+    }
+
+    if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+        eprintln!("TickerTUI needs an interactive terminal (TTY). Run it in a normal shell.");
+        return Ok(());
+    }
+
+    let config_path = config_path();
+    let mut initial_config = load_config(&config_path).sanitized();
+
+    let preflight = if initial_config.preflight {
+        match preflight().await? {
+            Some(preflight) => preflight,
+            None => return Ok(()),
+        }
+    } else {
+        Preflight {
+            online: true,
+            clock_warning: None,
+        }
+    };
+
+    if preflight.online && initial_config.watchlist_order == WatchlistOrder::QuoteVolume {
+        // Config order stands if the tickers can't be fetched.
+        if let Ok(Ok(volumes)) = tokio::time::timeout(FETCH_TIMEOUT, fetch_quote_volumes()).await {
+            initial_config.sort_watchlist_by_volume(&volumes);
+        }
+    }
+    if preflight.online && initial_config.start_on_top_mover {
+        // The saved symbol stands if the changes can't be fetched.
+        let fetch = fetch_change_pcts(&initial_config.watchlist);
+        if let Ok(Ok(changes)) = tokio::time::timeout(FETCH_TIMEOUT, fetch).await {
+            initial_config.select_top_mover(&changes);
+        }
+    }
+
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend)?;
+
+    let symbol = initial_config.symbol.clone();
+    let timeframe = initial_config.timeframe;
+    let interval = timeframe.binance_interval().to_string();
+    let limit = timeframe.limit();
+
+    let mut warmup = (initial_config.startup_splash && preflight.online)
+        .then(|| Warmup::new(&symbol, Instant::now()));
+    if let Some(warmup) = &warmup {
+        terminal.draw(|f| warmup.render(f))?;
+    }
+
+    let (initial_candles, initial_data_error) = if !preflight.online {
+        (
+            KlineBatch::default(),
+            Some("offline: Binance was unreachable at startup; press F5 to retry".to_string()),
+        )
+    } else {
+        match fetch_kline_batch(&symbol, &interval, limit, None).await {
+            Ok(batch) if batch.candles.is_empty() => (batch, Some(no_data_message(&symbol))),
+            Ok(batch) => (batch, None),
+            Err(err) => (
+                KlineBatch::default(),
+                Some(format!("{}: {}", no_data_message(&symbol), err)),
+            ),
+        }
+    };
+
+    if let Some(warmup) = &mut warmup {
+        warmup.set_candles(initial_data_error.is_none());
+    }
+
+    let chart = Arc::new(Mutex::new(Chart::new(symbol.clone(), interval.clone())));
+    {
+        let mut chart_guard = chart.lock().await;
+        chart_guard.zoom = initial_config.zoom;
+        chart_guard.preset = initial_config.zoom_preset;
+        chart_guard.half_block = initial_config.half_block;
+        chart_guard.braille = initial_config.braille;
+        chart_guard.emphasis = initial_config.candle_emphasis;
+        chart_guard.live_candle = initial_config.live_candle;
+        chart_guard.candle_width = initial_config.candle_width;
+        chart_guard.candle_gap = initial_config.candle_gap;
+        chart_guard.candle_style = initial_config.candle_style;
+        chart_guard.notes = initial_config.notes.clone();
+        chart_guard.invert_y = initial_config.invert_y;
+        chart_guard.price_axis = initial_config.price_axis;
+        chart_guard.price_hud = initial_config.price_hud;
+        chart_guard.round_price_labels = initial_config.round_price_labels;
+        chart_guard.snap_to_tick = initial_config.snap_crosshair;
+        chart_guard.infer_precision = initial_config.infer_price_precision;
+        chart_guard.padding_factor = initial_config.chart_padding;
+        chart_guard.heatmap.visible = initial_config.show_heatmap;
+        chart_guard.show_squeeze = initial_config.show_squeeze;
+        chart_guard.activity_tint = initial_config.activity_tint;
+        chart_guard.highlight_recent = initial_config.highlight_recent;
+        chart_guard.recent_candles = initial_config.recent_candles;
+        chart_guard.indicator_basis = initial_config.indicator_basis;
+        chart_guard.time_basis = initial_config.candle_time_basis;
+        chart_guard.grid = Grid {
+            mode: initial_config.grid,
+            style: initial_config.grid_style,
+            spacing: initial_config.grid_spacing,
+        };
+        chart_guard.fit_on_load = initial_config.fit_on_load;
+        chart_guard.smooth_catch_up = initial_config.smooth_catch_up;
+        chart_guard.relative.visible = initial_config.show_relative;
+        chart_guard.show_roc = initial_config.show_roc;
+        chart_guard.roc_period = initial_config.roc_period;
+        chart_guard.indicator_prefs = initial_config.indicators.clone();
+        chart_guard.default_indicators =
+            IndicatorPrefs::from_active(&initial_config.active_indicators);
+        chart_guard.line_styles = initial_config.line_styles;
+        chart_guard.apply_indicator_prefs();
+        chart_guard.update_candles(initial_candles.candles);
+        chart_guard.reject_candles(initial_candles.rejected);
+    }
+
+    let (restart_tx, mut restart_rx) = tokio::sync::mpsc::channel::<(String, String)>(10);
+    let (stream_refresh_tx, mut stream_refresh_rx) = tokio::sync::mpsc::channel::<()>(4);
+    let (watchlist_restart_tx, mut watchlist_restart_rx) =
+        tokio::sync::mpsc::channel::<Vec<String>>(10);
+    let (feed_settings_tx, mut feed_settings_rx) = tokio::sync::mpsc::channel::<FeedSettings>(4);
+    let (fetch_result_tx, mut fetch_result_rx) = tokio::sync::mpsc::unbounded_channel();
+    let (rate_limit_tx, mut rate_limit_rx) = tokio::sync::mpsc::unbounded_channel();
+    let (stats_tx, mut stats_rx) = tokio::sync::mpsc::unbounded_channel();
+    let (outage_tx, mut outage_rx) = tokio::sync::mpsc::unbounded_channel();
+    let (symbols_tx, mut symbols_rx) = tokio::sync::mpsc::unbounded_channel();
+    let (mtf_tx, mut mtf_rx) = tokio::sync::mpsc::unbounded_channel::<MtfUpdate>();
+    let (anchors_tx, mut anchors_rx) = tokio::sync::mpsc::unbounded_channel();
+    let (relative_tx, mut relative_rx) = tokio::sync::mpsc::unbounded_channel::<RelativeUpdate>();
+    let (health_tx, mut health_rx) = tokio::sync::mpsc::unbounded_channel();
+    let chart_clone = chart.clone();
+    let mut layout = LayoutManager::new(
+        initial_config.watchlist.clone(),
         initial_config.selected_symbol,
         timeframe,
-    )));
-
-    let layout_for_orderbook = layout_clone.clone();
-    let layout_for_trades = layout_clone.clone();
-    let layout_for_prices = layout_clone.clone();
-    let watchlist_for_prices = initial_config.watchlist.clone();
+    );
+    layout.sections = initial_config.watchlist_sections.clone();
+    layout.flash_enabled = initial_config.flash_updates;
+    let color_capability = ui::theme::detect_color_capability();
+    layout.theme = ui::Theme::new(initial_config.palette, color_capability);
+    layout.theme.volume_format = initial_config.volume_format;
+    layout.theme.max_price_decimals = initial_config.max_price_decimals;
+    layout.theme.dense = initial_config.dense;
+    layout.mtf.visible = initial_config.show_mtf;
+    layout.ribbon.visible = initial_config.show_ribbon;
+    layout.returns.visible = initial_config.show_returns;
+    layout.change_baseline = initial_config.change_baseline;
+    layout.events.visible = initial_config.show_events;
+    layout.events.set_capacity(initial_config.event_log_size);
+    layout.orderbook.view = initial_config.book_view;
+    layout.orderbook.follow_trades = initial_config.book_follow_trades;
+    layout.orderbook.spread.threshold_pct = initial_config.wide_spread_pct;
+    layout.tradetape.format = TapeFormat::parse(&initial_config.tape_format).unwrap_or_default();
+    layout.tradetape.direction = initial_config.tape_direction;
+    let panels = initial_config
+        .layout_preset
+        .panels(&initial_config.custom_layout);
+    layout.apply_panels(&panels);
+    if let Some(view) = panels.book_view {
+        layout.orderbook.view = view;
+    }
+    if let Some(heatmap) = panels.show_heatmap {
+        chart.lock().await.heatmap.visible = heatmap;
+    }
+    let layout_clone = Arc::new(Mutex::new(layout));
+
+    let layout_for_stream = layout_clone.clone();
+    let mut watchlist_for_prices = initial_config.watchlist.clone();
+    let mut trade_stream = initial_config.trade_stream;
+    let mut price_source = initial_config.price_source;
+    let mut max_streams = initial_config.max_streams_per_connection;
+    let max_trades_per_drain = initial_config.max_trades_per_drain;
+    let reconnect_policy = ReconnectPolicy::new(initial_config.max_reconnect_attempts);
+    let render_dirty = Arc::new(AtomicBool::new(true));
+    let dirty_for_stream = render_dirty.clone();
+    let chart_updates = Arc::new(AtomicBool::new(true));
+    let chart_updates_for_stream = chart_updates.clone();
+    let trades_paused = Arc::new(AtomicBool::new(false));
+    let trades_paused_for_stream = trades_paused.clone();
+
+    let mut streams = StreamCoordinator::new(BinanceFeeds::default(), reconnect_policy);
+    let feed_stats = streams.stats();
 
     tokio::spawn(async move {
         let mut current_symbol = symbol.clone();
         let mut current_interval = interval.clone();
-        let (mut rx, mut kline_handle) = stream_klines(&current_symbol, &current_interval);
-        let (mut orderbook_rx, mut orderbook_handle) = stream_orderbook(&current_symbol);
-        let (mut trades_rx, mut trades_handle) = stream_trades(&current_symbol);
-        let (mut watch_prices_rx, mut watch_prices_handle) =
-            stream_watchlist_prices(&watchlist_for_prices);
+        streams.switch_market(&current_symbol, &current_interval, trade_stream);
+        streams.set_watchlist(watchlist_for_prices.clone(), price_source, max_streams);
 
-        let mut kline_tracker = FeedTracker::new(Duration::from_secs(12), Duration::from_secs(40));
-        let mut orderbook_tracker =
-            FeedTracker::new(Duration::from_secs(3), Duration::from_secs(10));
-        let mut trades_tracker = FeedTracker::new(Duration::from_secs(3), Duration::from_secs(10));
+        let mut kline_tracker = FeedTracker::new();
+        let mut orderbook_tracker = FeedTracker::new();
+        let mut trades_tracker = FeedTracker::new();
 
         let mut health = HealthUpdate {
             kline: FeedState::Reconnecting,
             orderbook: FeedState::Reconnecting,
             trades: FeedState::Reconnecting,
+            retries_exhausted: false,
             last_error: Some("reconnecting: kline,orderbook,trades".to_string()),
+            dropped_trades: 0,
+            stale: Vec::new(),
         };
+        let mut dropped_since_tick = 0;
         let mut last_sent = None;
         push_health_update(&health_tx, &mut last_sent, &health);
 
@@ -404,80 +1896,171 @@ async fn main() -> io::Result<()> {
 
         loop {
             tokio::select! {
-                candle_opt = rx.recv() => {
-                    if let Some(candle) = candle_opt {
-                        let mut chart = chart_clone.lock().await;
-                        if chart.symbol == current_symbol {
-                            chart.add_candle(candle);
+                event = streams.recv() => {
+                    let feed = match event {
+                        StreamEvent::CandleUpdate(candle) => {
+                            let mut chart = chart_clone.lock().await;
+                            // Until the restart lands the chart may already be on
+                            // a new timeframe, so a candle is only taken when both
+                            // sides agree. The coordinator has already dropped any
+                            // whose span doesn't match the subscribed interval.
+                            if chart.symbol == current_symbol
+                                && chart.interval == current_interval
+                                && chart.review.is_none()
+                            {
+                                chart.add_candle(candle);
+                                chart_updates_for_stream.store(true, Ordering::Relaxed);
+                            }
+                            kline_tracker.mark_live();
+                            Feed::Kline
+                        }
+                        StreamEvent::CandleRejected(open_time) => {
+                            let mut chart = chart_clone.lock().await;
+                            if chart.symbol == current_symbol
+                                && chart.interval == current_interval
+                                && chart.review.is_none()
+                                && chart.reject_candles([open_time])
+                            {
+                                chart_updates_for_stream.store(true, Ordering::Relaxed);
+                            }
+                            kline_tracker.mark_live();
+                            Feed::Kline
+                        }
+                        StreamEvent::BookUpdate(book) => {
+                            // Depth arrives every 100ms; only the newest queued
+                            // book matters, so skip the ones behind it.
+                            let book = streams.newest_book(book);
+                            let mut layout = layout_for_stream.lock().await;
+                            if layout.orderbook.update(book) {
+                                dirty_for_stream.store(true, Ordering::Relaxed);
+                            }
+                            drop(layout);
+                            orderbook_tracker.mark_live();
+                            Feed::OrderBook
+                        }
+                        StreamEvent::TradeUpdate(trade) => {
+                            // Take the whole burst at once; past the cap only
+                            // the newest trades are kept.
+                            let (trades, skipped) = streams.drain_trades(trade, max_trades_per_drain);
+                            dropped_since_tick += skipped;
+                            trades_tracker.mark_live();
+                            if !trades_paused_for_stream.load(Ordering::Relaxed) {
+                                {
+                                    let mut chart = chart_clone.lock().await;
+                                    if chart.symbol == current_symbol {
+                                        let now = Instant::now();
+                                        for trade in &trades {
+                                            chart.heatmap.record(trade.price, trade.quantity, now);
+                                        }
+                                        if chart.heatmap.visible {
+                                            chart_updates_for_stream.store(true, Ordering::Relaxed);
+                                        }
+                                    }
+                                }
+                                let mut layout = layout_for_stream.lock().await;
+                                for trade in trades {
+                                    layout.orderbook.last_trade = Some(trade.price);
+                                    layout.tradetape.add_trade(trade);
+                                }
+                                dirty_for_stream.store(true, Ordering::Relaxed);
+                            }
+                            Feed::Trades
+                        }
+                        StreamEvent::PriceUpdate(watch_price) => {
+                            let mut layout = layout_for_stream.lock().await;
+                            layout.update_watch_price(watch_price);
+                            dirty_for_stream.store(true, Ordering::Relaxed);
+                            Feed::Prices
+                        }
+                        StreamEvent::Connection(feed, status) => {
+                            let kind = match status {
+                                ConnectionEvent::Connecting => EventKind::Connecting,
+                                ConnectionEvent::GaveUp => EventKind::GaveUp,
+                                ConnectionEvent::Reconnected { downtime } => {
+                                    EventKind::Reconnected(downtime)
+                                }
+                            };
+                            layout_for_stream.lock().await.events.push(ConnectionLogEntry {
+                                timestamp_ms: unix_now_secs() * 1000,
+                                feed,
+                                kind,
+                            });
+                            dirty_for_stream.store(true, Ordering::Relaxed);
+                            let tracker = match feed {
+                                Feed::Kline => &mut kline_tracker,
+                                Feed::OrderBook => &mut orderbook_tracker,
+                                Feed::Trades => &mut trades_tracker,
+                                Feed::Prices => continue,
+                            };
+                            match status {
+                                ConnectionEvent::Connecting => tracker.mark_reconnecting(),
+                                ConnectionEvent::GaveUp => tracker.mark_exhausted(),
+                                ConnectionEvent::Reconnected { downtime } => spawn_kline_backfill(
+                                    chart_clone.clone(),
+                                    chart_updates_for_stream.clone(),
+                                    current_symbol.clone(),
+                                    current_interval.clone(),
+                                    downtime,
+                                ),
+                            }
+                            feed
+                        }
+                    };
+
+                    if feed != Feed::Prices {
+                        let became_live: Vec<Feed> = [
+                            (Feed::Kline, health.kline, kline_tracker.state),
+                            (Feed::OrderBook, health.orderbook, orderbook_tracker.state),
+                            (Feed::Trades, health.trades, trades_tracker.state),
+                        ]
+                        .into_iter()
+                        .filter(|(_, before, after)| {
+                            *before != FeedState::Live && *after == FeedState::Live
+                        })
+                        .map(|(feed, _, _)| feed)
+                        .collect();
+                        if !became_live.is_empty() {
+                            let mut layout = layout_for_stream.lock().await;
+                            for feed in became_live {
+                                layout.events.push(ConnectionLogEntry {
+                                    timestamp_ms: unix_now_secs() * 1000,
+                                    feed,
+                                    kind: EventKind::Live,
+                                });
+                            }
                         }
-
-                        kline_tracker.mark_live(Instant::now());
-                        health.kline = kline_tracker.state;
-                        health.last_error = health_reason(&health);
-                        push_health_update(&health_tx, &mut last_sent, &health);
-                    } else {
-                        kline_handle.abort();
-                        let (new_rx, new_handle) = stream_klines(&current_symbol, &current_interval);
-                        rx = new_rx;
-                        kline_handle = new_handle;
-
-                        kline_tracker.mark_reconnecting();
                         health.kline = kline_tracker.state;
-                        health.last_error = Some("kline stream dropped; reconnecting".to_string());
+                        health.orderbook = orderbook_tracker.state;
+                        health.trades = trades_tracker.state;
+                        health.retries_exhausted = kline_tracker.exhausted
+                            || orderbook_tracker.exhausted
+                            || trades_tracker.exhausted;
+                        let socket_error = socket_error(&streams.stats(), &health);
+                        health.last_error = health_reason(&health, socket_error.as_deref());
                         push_health_update(&health_tx, &mut last_sent, &health);
                     }
                 }
-                orderbook_opt = orderbook_rx.recv() => {
-                    if let Some(book) = orderbook_opt {
-                        let mut layout = layout_for_orderbook.lock().await;
-                        layout.orderbook.update(book);
-
-                        orderbook_tracker.mark_live(Instant::now());
-                        health.orderbook = orderbook_tracker.state;
-                        health.last_error = health_reason(&health);
-                        push_health_update(&health_tx, &mut last_sent, &health);
-                    } else {
-                        orderbook_handle.abort();
-                        let (new_rx, new_handle) = stream_orderbook(&current_symbol);
-                        orderbook_rx = new_rx;
-                        orderbook_handle = new_handle;
-
-                        orderbook_tracker.mark_reconnecting();
-                        health.orderbook = orderbook_tracker.state;
-                        health.last_error = Some("orderbook stream dropped; reconnecting".to_string());
-                        push_health_update(&health_tx, &mut last_sent, &health);
+                watchlist_opt = watchlist_restart_rx.recv() => {
+                    if let Some(watchlist) = watchlist_opt {
+                        watchlist_for_prices = watchlist;
+                        streams.set_watchlist(watchlist_for_prices.clone(), price_source, max_streams);
                     }
                 }
-                trade_opt = trades_rx.recv() => {
-                    if let Some(trade) = trade_opt {
-                        let mut layout = layout_for_trades.lock().await;
-                        layout.tradetape.add_trade(trade);
-
-                        trades_tracker.mark_live(Instant::now());
-                        health.trades = trades_tracker.state;
-                        health.last_error = health_reason(&health);
-                        push_health_update(&health_tx, &mut last_sent, &health);
-                    } else {
-                        trades_handle.abort();
-                        let (new_rx, new_handle) = stream_trades(&current_symbol);
-                        trades_rx = new_rx;
-                        trades_handle = new_handle;
-
-                        trades_tracker.mark_reconnecting();
-                        health.trades = trades_tracker.state;
-                        health.last_error = Some("trade stream dropped; reconnecting".to_string());
-                        push_health_update(&health_tx, &mut last_sent, &health);
+                settings_opt = feed_settings_rx.recv() => {
+                    if let Some(settings) = settings_opt {
+                        trade_stream = settings.trade_stream;
+                        price_source = settings.price_source;
+                        max_streams = settings.max_streams;
+                        streams.subscribe(Subscription::Trades {
+                            kind: trade_stream,
+                            symbol: current_symbol.clone(),
+                        });
+                        streams.set_watchlist(watchlist_for_prices.clone(), price_source, max_streams);
                     }
                 }
-                watch_price_opt = watch_prices_rx.recv() => {
-                    if let Some(watch_price) = watch_price_opt {
-                        let mut layout = layout_for_prices.lock().await;
-                        layout.update_watch_price(watch_price);
-                    } else {
-                        watch_prices_handle.abort();
-                        let (new_rx, new_handle) = stream_watchlist_prices(&watchlist_for_prices);
-                        watch_prices_rx = new_rx;
-                        watch_prices_handle = new_handle;
+                refresh_opt = stream_refresh_rx.recv() => {
+                    if refresh_opt.is_some() {
+                        streams.restart_all();
                     }
                 }
                 restart_opt = restart_rx.recv() => {
@@ -488,49 +2071,41 @@ async fn main() -> io::Result<()> {
                             &new_symbol,
                             &new_interval,
                         ) {
+                            if current_symbol != new_symbol {
+                                let mut layout = layout_for_stream.lock().await;
+                                layout.tradetape.reset();
+                                layout.orderbook.last_trade = None;
+                                layout.orderbook.spread.reset();
+                            }
                             current_symbol = new_symbol;
                             current_interval = new_interval;
-
-                            kline_handle.abort();
-                            orderbook_handle.abort();
-                            trades_handle.abort();
-
-                            let (new_rx, new_kline_handle) = stream_klines(&current_symbol, &current_interval);
-                            let (new_orderbook_rx, new_orderbook_handle) = stream_orderbook(&current_symbol);
-                            let (new_trades_rx, new_trades_handle) = stream_trades(&current_symbol);
-
-                            rx = new_rx;
-                            orderbook_rx = new_orderbook_rx;
-                            trades_rx = new_trades_rx;
-                            kline_handle = new_kline_handle;
-                            orderbook_handle = new_orderbook_handle;
-                            trades_handle = new_trades_handle;
-
-                            kline_tracker.mark_reconnecting();
-                            orderbook_tracker.mark_reconnecting();
-                            trades_tracker.mark_reconnecting();
-                            health.kline = kline_tracker.state;
-                            health.orderbook = orderbook_tracker.state;
-                            health.trades = trades_tracker.state;
-                            health.last_error = Some("reconnecting: kline,orderbook,trades".to_string());
-                            push_health_update(&health_tx, &mut last_sent, &health);
+                            streams.switch_market(&current_symbol, &current_interval, trade_stream);
                         }
                     }
                 }
                 _ = health_tick.tick() => {
-                    let now = Instant::now();
-                    let mut changed = false;
-                    changed |= kline_tracker.refresh(now);
-                    changed |= orderbook_tracker.refresh(now);
-                    changed |= trades_tracker.refresh(now);
-
-                    if changed {
-                        health.kline = kline_tracker.state;
-                        health.orderbook = orderbook_tracker.state;
-                        health.trades = trades_tracker.state;
-                        health.last_error = health_reason(&health);
-                        push_health_update(&health_tx, &mut last_sent, &health);
+                    // A socket that dropped and is backing off sends nothing
+                    // until it reconnects, so its stats are the only sign.
+                    let feed_stats = streams.stats();
+                    for (feed, tracker) in [
+                        (Feed::Kline, &mut kline_tracker),
+                        (Feed::OrderBook, &mut orderbook_tracker),
+                        (Feed::Trades, &mut trades_tracker),
+                    ] {
+                        if tracker.state == FeedState::Live
+                            && feed_stats.get(feed).snapshot().failures > 0
+                        {
+                            tracker.mark_reconnecting();
+                        }
                     }
+                    health.kline = kline_tracker.state;
+                    health.orderbook = orderbook_tracker.state;
+                    health.trades = trades_tracker.state;
+                    let socket_error = socket_error(&feed_stats, &health);
+                    health.last_error = health_reason(&health, socket_error.as_deref());
+                    health.stale = streams.stale_feeds(Instant::now());
+                    health.dropped_trades = std::mem::take(&mut dropped_since_tick);
+                    push_health_update(&health_tx, &mut last_sent, &health);
                 }
             }
         }
@@ -541,14 +2116,72 @@ async fn main() -> io::Result<()> {
         layout: layout_clone,
         config_path,
         stream_restart_tx: restart_tx,
+        watchlist_restart_tx,
+        feed_settings_tx,
+        stream_refresh_tx,
         fetch_result_tx,
+        rate_limit_tx,
+        rate_limit_streak: 0,
         fetch_task: None,
         next_request_id: 0,
         pending_request_id: None,
         is_loading: false,
         connection_mode: ConnectionMode::Reconnecting,
         connection_error: Some("reconnecting: kline,orderbook,trades".to_string()),
+        data_error: initial_data_error,
         show_help: false,
+        hidden: false,
+        hide_key: initial_config.hide_key,
+        show_debug: false,
+        feed_stats,
+        auto_timeframe: initial_config.auto_timeframe,
+        max_candles_per_column: initial_config.max_candles_per_column,
+        fit_after_fetch: false,
+        market_stats: None,
+        outage: OutageWatch::new(),
+        outage_tx,
+        stats_tx,
+        symbols: None,
+        symbol_rules: HashMap::new(),
+        symbols_tx,
+        symbols_refresh: Duration::from_secs(initial_config.symbols_refresh_hours * 3600),
+        symbols_fetched_at: None,
+        mtf_tx,
+        anchors_tx,
+        anchors_checked_at: None,
+        relative_tx,
+        input: None,
+        confirm: None,
+        notice: preflight.clock_warning,
+        preflight: initial_config.preflight,
+        startup_splash: initial_config.startup_splash,
+        warmup,
+        confirm_quit: initial_config.confirm_quit,
+        max_fps: initial_config.max_fps,
+        max_reconnect_attempts: initial_config.max_reconnect_attempts,
+        frame_limiter: FrameLimiter::new(initial_config.max_fps),
+        idle_limiter: FrameLimiter::new(IDLE_FPS),
+        idle: IdleWatch::new(
+            Duration::from_secs(initial_config.idle_after_secs),
+            Instant::now(),
+        ),
+        trades_paused,
+        chart_cadence: ChartCadence::new(initial_config.chart_refresh_ms),
+        symbol_debounce: SwitchDebounce::new(SYMBOL_SWITCH_DEBOUNCE),
+        palette: initial_config.palette,
+        trade_stream,
+        price_source,
+        max_streams_per_connection: max_streams,
+        max_trades_per_drain,
+        dropped_trades: 0,
+        layout_preset: initial_config.layout_preset,
+        watchlist_order: initial_config.watchlist_order,
+        start_on_top_mover: initial_config.start_on_top_mover,
+        custom_layout: initial_config.custom_layout.clone(),
+        quiet: QuietMode::new(initial_config.quiet_hours),
+        render_dirty,
+        chart_updates,
+        chart_dirty: AtomicBool::new(true),
     };
 
     loop {
@@ -556,32 +2189,197 @@ async fn main() -> io::Result<()> {
             app.apply_fetch_result(result).await;
         }
 
+        while let Ok(retry_after) = rate_limit_rx.try_recv() {
+            app.apply_rate_limit(retry_after);
+        }
+
+        while let Ok(symbols) = symbols_rx.try_recv() {
+            app.apply_symbols(symbols).await;
+        }
+        app.refresh_symbols(Instant::now());
+
+        while let Ok((symbol, result)) = stats_rx.try_recv() {
+            app.apply_market_stats(symbol, result);
+        }
+        app.refresh_market_stats(Instant::now());
+
+        while let Ok((symbol, result)) = outage_rx.try_recv() {
+            app.outage.apply(&symbol, result, Instant::now());
+            app.mark_dirty();
+        }
+        app.refresh_exchange_status(Instant::now()).await;
+
+        while let Ok((symbol, changes)) = mtf_rx.try_recv() {
+            app.layout.lock().await.mtf.apply(&symbol, changes);
+            app.mark_dirty();
+        }
+        app.refresh_mtf(Instant::now()).await;
+
+        while let Ok((symbol, anchors)) = anchors_rx.try_recv() {
+            app.layout
+                .lock()
+                .await
+                .daily_anchors
+                .insert(symbol, anchors);
+        }
+        app.refresh_daily_anchors(Instant::now()).await;
+
+        while let Ok((interval, candles)) = relative_rx.try_recv() {
+            app.chart.lock().await.relative.apply(&interval, &candles);
+            app.mark_dirty();
+        }
+        app.refresh_relative(Instant::now()).await;
+
         while let Ok(update) = health_rx.try_recv() {
-            app.apply_health_update(update);
+            app.apply_health_update(update).await;
+        }
+        if app
+            .warmup
+            .as_ref()
+            .is_some_and(|warmup| warmup.is_done(Instant::now()))
+        {
+            app.warmup = None;
+            app.mark_dirty();
         }
 
-        let chart_guard = app.chart.lock().await;
-        let mut layout_guard = app.layout.lock().await;
-        layout_guard.statusbar.loading = app.is_loading;
-        layout_guard.statusbar.connection_mode = app.connection_mode;
-        layout_guard.statusbar.last_error = app.connection_error.clone();
-        terminal.draw(|f| {
-            if app.show_help {
-                render_help(f);
-            } else {
-                layout_guard.render(f, &chart_guard, f.area());
+        if app.symbol_debounce.fire(Instant::now()) {
+            let selected = app
+                .layout
+                .lock()
+                .await
+                .selected_symbol()
+                .map(str::to_string);
+            if let Some(symbol) = selected {
+                app.switch_symbol(symbol).await;
             }
-        })?;
-        drop(chart_guard);
-        drop(layout_guard);
+        }
 
-        if event::poll(std::time::Duration::from_millis(50))? {
-            if let Event::Key(key) = event::read()? {
+        if app.quiet.update(unix_now_secs()) {
+            app.mark_dirty();
+        }
+
+        let now = Instant::now();
+        if app.idle.update(now) {
+            app.trades_paused.store(true, Ordering::Relaxed);
+            app.mark_dirty();
+        }
+        if app.chart_updates.load(Ordering::Relaxed) && app.chart_cadence.due(now) {
+            app.mark_dirty();
+        }
+
+        if !app.hidden
+            && app.active_limiter().ready(now)
+            && app.render_dirty.swap(false, Ordering::Relaxed)
+        {
+            let redraw_chart = app.chart_dirty.swap(false, Ordering::Relaxed);
+            if redraw_chart {
+                app.chart_updates.store(false, Ordering::Relaxed);
+                app.chart_cadence.mark_drawn(now);
+            }
+            let mut chart_guard = app.chart.lock().await;
+            let catching_up = chart_guard.advance_catch_up(now);
+            let mut layout_guard = app.layout.lock().await;
+            layout_guard.redraw_chart = redraw_chart;
+            let search_matches = app.search_matches();
+            layout_guard.statusbar.loading = app.is_loading;
+            layout_guard.statusbar.connection_mode = app.effective_connection_mode();
+            layout_guard.statusbar.last_error = app
+                .data_error
+                .clone()
+                .or_else(|| app.connection_error.clone());
+            layout_guard.statusbar.input_prompt = app.input.as_ref().map(InputState::prompt);
+            layout_guard.statusbar.notice = app.notice.clone();
+            layout_guard.statusbar.idle = app.idle.is_idle();
+            layout_guard.statusbar.dropped_trades = app.dropped_trades;
+            terminal.draw(|f| {
+                if app.show_help {
+                    render_help(f);
+                } else {
+                    layout_guard.render(f, &chart_guard, f.area());
+                }
+                if let Some(modal) = &app.market_stats {
+                    let mut theme = layout_guard.theme;
+                    if modal.symbol != chart_guard.symbol {
+                        theme.price_precision = None;
+                    }
+                    modal.render(f, &theme);
+                }
+                if let Some(banner) = app.outage.banner() {
+                    ui::outage::render_banner(f, &banner);
+                }
+                if app.show_debug {
+                    ui::debug::render_debug_overlay(f, &app.feed_stats);
+                }
+                if let Some(input) = &app.input {
+                    ui::search::render_symbol_dropdown(f, &search_matches, input.selected);
+                }
+                if let Some(warmup) = &app.warmup {
+                    warmup.render(f);
+                }
+                if let Some(action) = app.confirm {
+                    render_confirm(f, action.message());
+                }
+                if app.quiet.is_dim() {
+                    ui::theme::dim_buffer(f.buffer_mut(), layout_guard.theme.capability);
+                }
+            })?;
+            if catching_up {
+                app.mark_dirty();
+            } else if app.show_debug || app.warmup.is_some() || layout_guard.is_animating(now) {
+                app.render_dirty.store(true, Ordering::Relaxed);
+            }
+            drop(chart_guard);
+            drop(layout_guard);
+            app.active_limiter().mark_drawn(now);
+        }
+
+        if event::poll(app.active_limiter().poll_timeout(Instant::now()))? {
+            let event = event::read()?;
+            if matches!(event, Event::Resize(_, _)) {
+                app.mark_dirty();
+            }
+            if let Event::Key(key) = event {
                 if key.kind == KeyEventKind::Press {
+                    if app.idle.touch(Instant::now()) {
+                        app.trades_paused.store(false, Ordering::Relaxed);
+                    }
+                    app.mark_dirty();
+                    app.notice = None;
+                    app.warmup = None;
+                    app.chart.lock().await.cancel_catch_up();
                     match key.code {
+                        code if app.confirm.is_some() => {
+                            let quit = app.handle_confirm_key(code).await;
+                            if quit {
+                                break;
+                            }
+                        }
+                        code if app.input.is_some() => {
+                            app.handle_input_key(code).await;
+                        }
+                        code if is_hide_key(code, key.modifiers, app.hide_key) => {
+                            // Clearing also resets ratatui's previous frame, so
+                            // unhiding repaints every cell.
+                            app.hidden = !app.hidden;
+                            terminal.clear()?;
+                        }
+                        _ if app.hidden => {}
+                        KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            app.show_debug = !app.show_debug;
+                        }
+                        KeyCode::Esc | KeyCode::Char('i') | KeyCode::Char('I')
+                            if app.market_stats.is_some() =>
+                        {
+                            app.market_stats = None;
+                        }
+                        KeyCode::Char('i') | KeyCode::Char('I') => {
+                            app.open_market_stats().await;
+                        }
                         KeyCode::Char('q') | KeyCode::Esc => {
                             if app.show_help {
                                 app.show_help = false;
+                            } else if app.confirm_quit {
+                                app.confirm = Some(ConfirmAction::Quit);
                             } else {
                                 app.persist_config().await;
                                 break;
@@ -596,6 +2394,19 @@ async fn main() -> io::Result<()> {
                         }
                         KeyCode::Char('-') | KeyCode::Char('_') => {
                             app.chart.lock().await.zoom_out();
+                            app.check_zoom_resolution().await;
+                            app.persist_config().await;
+                        }
+                        KeyCode::Char('*') => {
+                            let mut chart = app.chart.lock().await;
+                            chart.cycle_zoom_preset();
+                            let fit = chart.fit;
+                            drop(chart);
+                            // Only the whole-history step can call for a
+                            // longer interval; presets keep their count.
+                            if fit {
+                                app.check_zoom_resolution().await;
+                            }
                             app.persist_config().await;
                         }
                         KeyCode::Tab => {
@@ -616,37 +2427,189 @@ async fn main() -> io::Result<()> {
                         }
                         KeyCode::Char('s') | KeyCode::Char('S') => {
                             app.chart.lock().await.toggle_sma();
+                            app.persist_config().await;
                         }
                         KeyCode::Char('r') | KeyCode::Char('R') => {
                             app.chart.lock().await.toggle_rsi();
+                            app.persist_config().await;
+                        }
+                        KeyCode::Char('/') => {
+                            app.input = Some(InputState::new(InputKind::Symbol));
+                        }
+                        KeyCode::Char('@') => {
+                            if app.chart.lock().await.review.is_some() {
+                                app.exit_replay().await;
+                            } else {
+                                app.input = Some(InputState::new(InputKind::ReplayStart));
+                            }
+                        }
+                        KeyCode::Char('o') | KeyCode::Char('O') => {
+                            app.input = Some(InputState::new(InputKind::OrderSize(Side::Buy)));
+                        }
+                        KeyCode::Char('y') | KeyCode::Char('Y') => {
+                            app.copy_symbol_price().await;
+                        }
+                        KeyCode::Char('w') | KeyCode::Char('W') => {
+                            app.export_config().await;
+                        }
+                        KeyCode::Char('x') | KeyCode::Char('X') => {
+                            app.confirm = Some(ConfirmAction::ResetConfig);
+                        }
+                        KeyCode::Char('m') | KeyCode::Char('M') => {
+                            app.layout.lock().await.mtf.toggle();
+                            app.persist_config().await;
+                        }
+                        KeyCode::Char('l') | KeyCode::Char('L') => {
+                            app.layout.lock().await.orderbook.cycle_view();
+                            app.persist_config().await;
+                        }
+                        KeyCode::Char('b') | KeyCode::Char('B') => {
+                            app.chart.lock().await.relative.toggle();
+                            app.persist_config().await;
+                        }
+                        KeyCode::Char('%') => {
+                            let mut chart = app.chart.lock().await;
+                            chart.show_roc = !chart.show_roc;
+                            drop(chart);
+                            app.persist_config().await;
+                        }
+                        KeyCode::F(5) => {
+                            app.refresh_streams().await;
+                        }
+                        KeyCode::Char('z') | KeyCode::Char('Z') => {
+                            app.quiet.toggle();
+                        }
+                        KeyCode::Char('e') | KeyCode::Char('E') => {
+                            let mut chart = app.chart.lock().await;
+                            chart.activity_tint = !chart.activity_tint;
+                            drop(chart);
+                            app.persist_config().await;
+                        }
+                        KeyCode::Char('k') | KeyCode::Char('K') => {
+                            let mut chart = app.chart.lock().await;
+                            chart.highlight_recent = !chart.highlight_recent;
+                            drop(chart);
+                            app.persist_config().await;
+                        }
+                        KeyCode::Char('a') | KeyCode::Char('A') => {
+                            app.chart.lock().await.heatmap.toggle();
+                            app.persist_config().await;
+                        }
+                        KeyCode::Char('t') | KeyCode::Char('T') => {
+                            app.layout.lock().await.ribbon.toggle();
+                            app.persist_config().await;
+                        }
+                        KeyCode::Char('u') | KeyCode::Char('U') => {
+                            app.layout.lock().await.returns.toggle();
+                            app.persist_config().await;
+                        }
+                        KeyCode::Char('j') | KeyCode::Char('J') => {
+                            app.layout.lock().await.events.toggle();
+                            app.persist_config().await;
+                        }
+                        KeyCode::Char('f') | KeyCode::Char('F') => {
+                            app.layout.lock().await.toggle_flash();
+                            app.persist_config().await;
+                        }
+                        KeyCode::Char('v') | KeyCode::Char('V') => {
+                            app.chart.lock().await.toggle_invert_y();
+                            app.persist_config().await;
+                        }
+                        KeyCode::Char('|') => {
+                            app.chart.lock().await.cycle_price_axis();
+                            app.persist_config().await;
+                        }
+                        KeyCode::Char('$') => {
+                            app.export_dashboard().await;
+                        }
+                        KeyCode::Char('#') => {
+                            let mut layout = app.layout.lock().await;
+                            layout.theme.dense = !layout.theme.dense;
+                            drop(layout);
+                            app.persist_config().await;
+                        }
+                        KeyCode::Char('g') | KeyCode::Char('G') => {
+                            let toggled = app.layout.lock().await.toggle_selected_section();
+                            if toggled {
+                                app.persist_config().await;
+                            } else {
+                                app.notice = Some("No watchlist sections configured".into());
+                            }
+                        }
+                        KeyCode::Char('c') | KeyCode::Char('C') => {
+                            app.chart.lock().await.toggle_crosshair();
+                        }
+                        KeyCode::Char('n') | KeyCode::Char('N') => {
+                            if app.chart.lock().await.crosshair_candle().is_some() {
+                                app.input = Some(InputState::new(InputKind::Note));
+                            } else {
+                                app.notice = Some("Press C to place the crosshair first".into());
+                            }
+                        }
+                        KeyCode::Char('d') | KeyCode::Char('D') => {
+                            let removed = app.chart.lock().await.delete_note();
+                            if removed {
+                                app.persist_config().await;
+                            } else {
+                                app.notice = Some("No note at the crosshair".into());
+                            }
+                        }
+                        KeyCode::End => {
+                            app.chart.lock().await.jump_to_live(Instant::now());
+                            app.exit_replay().await;
                         }
                         KeyCode::Left => {
-                            app.chart.lock().await.pan_left();
+                            let mut chart = app.chart.lock().await;
+                            if chart.crosshair.is_some() {
+                                chart.crosshair_left();
+                            } else {
+                                chart.pan_left();
+                            }
                         }
                         KeyCode::Right => {
-                            app.chart.lock().await.pan_right();
+                            let mut chart = app.chart.lock().await;
+                            if chart.crosshair.is_some() {
+                                chart.crosshair_right();
+                            } else {
+                                chart.pan_right();
+                            }
                         }
                         KeyCode::Up => {
-                            let mut layout = app.layout.lock().await;
-                            if layout.selected_symbol > 0 {
-                                layout.selected_symbol -= 1;
+                            let mut chart = app.chart.lock().await;
+                            if chart.crosshair.is_some() {
+                                chart.crosshair_up();
+                            } else {
+                                drop(chart);
+                                app.layout.lock().await.move_selection(-1);
+                                app.symbol_debounce.schedule(Instant::now());
+                                app.persist_config().await;
                             }
-                            drop(layout);
-                            app.persist_config().await;
                         }
                         KeyCode::Down => {
-                            let mut layout = app.layout.lock().await;
-                            if layout.selected_symbol < layout.watchlist.len().saturating_sub(1) {
-                                layout.selected_symbol += 1;
+                            let mut chart = app.chart.lock().await;
+                            if chart.crosshair.is_some() {
+                                chart.crosshair_down();
+                            } else {
+                                drop(chart);
+                                app.layout.lock().await.move_selection(1);
+                                app.symbol_debounce.schedule(Instant::now());
+                                app.persist_config().await;
                             }
-                            drop(layout);
-                            app.persist_config().await;
                         }
                         KeyCode::Enter => {
-                            let layout = app.layout.lock().await;
-                            let new_symbol = layout.watchlist[layout.selected_symbol].clone();
-                            drop(layout);
-                            app.switch_symbol(new_symbol.clone()).await;
+                            let mut layout = app.layout.lock().await;
+                            if layout.selected_section_collapsed() {
+                                layout.toggle_selected_section();
+                                drop(layout);
+                                app.persist_config().await;
+                            } else {
+                                let new_symbol = layout.selected_symbol().map(str::to_string);
+                                drop(layout);
+                                app.symbol_debounce.cancel();
+                                if let Some(new_symbol) = new_symbol {
+                                    app.switch_symbol(new_symbol).await;
+                                }
+                            }
                         }
                         _ => {}
                     }
@@ -666,6 +2629,12 @@ async fn main() -> io::Result<()> {
     Ok(())
 }
 
+/// The configured hide key matches in either case.
+fn is_hide_key(code: KeyCode, modifiers: KeyModifiers, hide_key: char) -> bool {
+    !modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+        && matches!(code, KeyCode::Char(c) if c.eq_ignore_ascii_case(&hide_key))
+}
+
 fn render_help(frame: &mut ratatui::Frame) {
     use ratatui::{
         layout::Alignment,
@@ -690,15 +2659,49 @@ fn render_help(frame: &mut ratatui::Frame) {
         )]),
         Line::from(vec![
             Span::raw("  ↑/↓    "),
-            Span::styled("Navigate watchlist", Style::default().fg(Color::White)),
+            Span::styled(
+                "Navigate watchlist (moves crosshair price when shown)",
+                Style::default().fg(Color::White),
+            ),
         ]),
         Line::from(vec![
             Span::raw("  Enter  "),
-            Span::styled("Select symbol", Style::default().fg(Color::White)),
+            Span::styled(
+                "Select symbol / expand section",
+                Style::default().fg(Color::White),
+            ),
+        ]),
+        Line::from(vec![
+            Span::raw("  G      "),
+            Span::styled(
+                "Collapse/expand watchlist section",
+                Style::default().fg(Color::White),
+            ),
         ]),
         Line::from(vec![
             Span::raw("  ←/→    "),
-            Span::styled("Pan chart left/right", Style::default().fg(Color::White)),
+            Span::styled(
+                "Pan chart (moves crosshair when shown)",
+                Style::default().fg(Color::White),
+            ),
+        ]),
+        Line::from(vec![
+            Span::raw("  End    "),
+            Span::styled(
+                "Scroll back to the latest candle",
+                Style::default().fg(Color::White),
+            ),
+        ]),
+        Line::from(vec![
+            Span::raw("  C      "),
+            Span::styled("Toggle crosshair", Style::default().fg(Color::White)),
+        ]),
+        Line::from(vec![
+            Span::raw("  N/D    "),
+            Span::styled(
+                "Add/delete note at crosshair",
+                Style::default().fg(Color::White),
+            ),
         ]),
         Line::from(""),
         Line::from(vec![Span::styled(
@@ -711,6 +2714,38 @@ fn render_help(frame: &mut ratatui::Frame) {
             Span::raw("  +/-    "),
             Span::styled("Zoom in/out", Style::default().fg(Color::White)),
         ]),
+        Line::from(vec![
+            Span::raw("  *      "),
+            Span::styled(
+                "Show 50 / 100 / 200 / 500 / all candles",
+                Style::default().fg(Color::White),
+            ),
+        ]),
+        Line::from(vec![
+            Span::raw("  V      "),
+            Span::styled("Invert price axis", Style::default().fg(Color::White)),
+        ]),
+        Line::from(vec![
+            Span::raw("  |      "),
+            Span::styled(
+                "Price axis left / right / hidden",
+                Style::default().fg(Color::White),
+            ),
+        ]),
+        Line::from(vec![
+            Span::raw("  $      "),
+            Span::styled(
+                "Export a dashboard snapshot (Markdown)",
+                Style::default().fg(Color::White),
+            ),
+        ]),
+        Line::from(vec![
+            Span::raw("  #      "),
+            Span::styled(
+                "Dense mode (no panel borders)",
+                Style::default().fg(Color::White),
+            ),
+        ]),
         Line::from(""),
         Line::from(vec![Span::styled(
             "Timeframes:",
@@ -748,6 +2783,144 @@ fn render_help(frame: &mut ratatui::Frame) {
                 .fg(Color::Yellow)
                 .add_modifier(ratatui::style::Modifier::BOLD),
         )]),
+        Line::from(vec![
+            Span::raw("  O      "),
+            Span::styled(
+                "Preview order fill (empty size clears)",
+                Style::default().fg(Color::White),
+            ),
+        ]),
+        Line::from(vec![
+            Span::raw("  M      "),
+            Span::styled(
+                "Toggle multi-timeframe strip",
+                Style::default().fg(Color::White),
+            ),
+        ]),
+        Line::from(vec![
+            Span::raw("  B      "),
+            Span::styled(
+                "Toggle relative strength vs BTC",
+                Style::default().fg(Color::White),
+            ),
+        ]),
+        Line::from(vec![
+            Span::raw("  %      "),
+            Span::styled(
+                "Toggle rate-of-change strip",
+                Style::default().fg(Color::White),
+            ),
+        ]),
+        Line::from(vec![
+            Span::raw("  /      "),
+            Span::styled(
+                "Search for any symbol to chart",
+                Style::default().fg(Color::White),
+            ),
+        ]),
+        Line::from(vec![
+            Span::raw("  @      "),
+            Span::styled(
+                "Replay history from a UTC date (again to go live)",
+                Style::default().fg(Color::White),
+            ),
+        ]),
+        Line::from(vec![
+            Span::raw("  Z      "),
+            Span::styled(
+                "Dim the screen (overrides quiet hours until they next start/end)",
+                Style::default().fg(Color::White),
+            ),
+        ]),
+        Line::from(vec![
+            Span::raw("  P      "),
+            Span::styled(
+                "Blank the screen until pressed again (key set by hide_key)",
+                Style::default().fg(Color::White),
+            ),
+        ]),
+        Line::from(vec![
+            Span::raw("  L      "),
+            Span::styled(
+                "Cycle order book view (ladder, depth bars, heat)",
+                Style::default().fg(Color::White),
+            ),
+        ]),
+        Line::from(vec![
+            Span::raw("  E      "),
+            Span::styled(
+                "Tint candles by trade count",
+                Style::default().fg(Color::White),
+            ),
+        ]),
+        Line::from(vec![
+            Span::raw("  K      "),
+            Span::styled(
+                "Highlight recent candles, fade older ones",
+                Style::default().fg(Color::White),
+            ),
+        ]),
+        Line::from(vec![
+            Span::raw("  A      "),
+            Span::styled(
+                "Toggle trade activity heat bands",
+                Style::default().fg(Color::White),
+            ),
+        ]),
+        Line::from(vec![
+            Span::raw("  T      "),
+            Span::styled(
+                "Toggle scrolling ticker ribbon",
+                Style::default().fg(Color::White),
+            ),
+        ]),
+        Line::from(vec![
+            Span::raw("  U      "),
+            Span::styled(
+                "Toggle returns histogram panel",
+                Style::default().fg(Color::White),
+            ),
+        ]),
+        Line::from(vec![
+            Span::raw("  J      "),
+            Span::styled(
+                "Toggle connection events panel",
+                Style::default().fg(Color::White),
+            ),
+        ]),
+        Line::from(vec![
+            Span::raw("  F      "),
+            Span::styled(
+                "Toggle watchlist price flash",
+                Style::default().fg(Color::White),
+            ),
+        ]),
+        Line::from(vec![
+            Span::raw("  I      "),
+            Span::styled("24h market stats", Style::default().fg(Color::White)),
+        ]),
+        Line::from(vec![
+            Span::raw("  Y      "),
+            Span::styled("Copy symbol and price", Style::default().fg(Color::White)),
+        ]),
+        Line::from(vec![
+            Span::raw("  F5     "),
+            Span::styled(
+                "Reconnect streams and reload",
+                Style::default().fg(Color::White),
+            ),
+        ]),
+        Line::from(vec![
+            Span::raw("  W      "),
+            Span::styled("Save settings now", Style::default().fg(Color::White)),
+        ]),
+        Line::from(vec![
+            Span::raw("  X      "),
+            Span::styled(
+                "Reset settings to defaults",
+                Style::default().fg(Color::White),
+            ),
+        ]),
         Line::from(vec![
             Span::raw("  ?/h    "),
             Span::styled("Toggle help", Style::default().fg(Color::White)),
@@ -770,6 +2943,44 @@ fn render_help(frame: &mut ratatui::Frame) {
     frame.render_widget(paragraph, frame.area());
 }
 
+fn render_confirm(frame: &mut ratatui::Frame, message: &str) {
+    use ratatui::{
+        layout::{Alignment, Rect},
+        style::{Color, Modifier, Style},
+        text::{Line, Span},
+        widgets::{Block, Borders, Clear, Paragraph},
+    };
+
+    let area = frame.area();
+    let width = (message.chars().count() as u16 + 4).min(area.width);
+    let height = 3.min(area.height);
+    let popup = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    };
+
+    let block = Block::default()
+        .title("Confirm")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow));
+    let text = Line::from(Span::styled(
+        message,
+        Style::default()
+            .fg(Color::White)
+            .add_modifier(Modifier::BOLD),
+    ));
+
+    frame.render_widget(Clear, popup);
+    frame.render_widget(
+        Paragraph::new(text)
+            .block(block)
+            .alignment(Alignment::Center),
+        popup,
+    );
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -781,6 +2992,48 @@ mod tests {
         assert!(should_restart_stream("BTCUSDT", "1h", "BTCUSDT", "4h"));
     }
 
+    #[test]
+    fn hide_key_ignores_case() {
+        let none = KeyModifiers::NONE;
+        assert!(is_hide_key(KeyCode::Char('p'), none, 'p'));
+        assert!(is_hide_key(KeyCode::Char('P'), KeyModifiers::SHIFT, 'p'));
+        assert!(!is_hide_key(KeyCode::Char('o'), none, 'p'));
+        assert!(!is_hide_key(KeyCode::Esc, none, 'p'));
+        assert!(!is_hide_key(KeyCode::Char('p'), KeyModifiers::CONTROL, 'p'));
+        assert!(!is_hide_key(KeyCode::Char('p'), KeyModifiers::ALT, 'p'));
+    }
+
+    #[test]
+    fn bound_keys_cannot_be_the_hide_key() {
+        for key in ['q', 'x', 'd', 'H', '?', '*'] {
+            let config = AppConfig {
+                hide_key: key,
+                ..AppConfig::default()
+            }
+            .sanitized();
+            assert_eq!(config.hide_key, config::default_hide_key(), "{key}");
+        }
+        let config = AppConfig {
+            hide_key: '!',
+            ..AppConfig::default()
+        }
+        .sanitized();
+        assert_eq!(config.hide_key, '!');
+    }
+
+    #[test]
+    fn clock_skew_warns_past_a_second() {
+        assert_eq!(clock_skew_warning(800), None);
+        assert_eq!(clock_skew_warning(-1_000), None);
+        assert_eq!(
+            clock_skew_warning(2_500).as_deref(),
+            Some("system clock is 2.5s behind Binance; candle times may be misleading")
+        );
+        assert!(clock_skew_warning(-3_000)
+            .unwrap()
+            .contains("3.0s ahead of"));
+    }
+
     #[test]
     fn stale_fetch_results_are_rejected() {
         assert!(should_apply_fetch_result(Some(7), 7));
@@ -789,17 +3042,171 @@ mod tests {
     }
 
     #[test]
-    fn feed_tracker_transitions_live_reconnecting_degraded() {
-        let now = Instant::now();
-        let mut tracker = FeedTracker::new(Duration::from_secs(2), Duration::from_secs(5));
+    fn exhausted_feed_stays_degraded_until_restarted() {
+        let mut tracker = FeedTracker::new();
+        tracker.mark_exhausted();
+        assert_eq!(tracker.state, FeedState::Degraded);
 
-        tracker.mark_live(now);
+        let update = HealthUpdate {
+            kline: tracker.state,
+            orderbook: FeedState::Live,
+            trades: FeedState::Live,
+            retries_exhausted: true,
+            last_error: None,
+            dropped_trades: 0,
+            stale: Vec::new(),
+        };
+        assert_eq!(
+            health_reason(&update, None).as_deref(),
+            Some("offline: kline gave up reconnecting; press F5 to retry")
+        );
+        let reconnecting = HealthUpdate {
+            kline: FeedState::Reconnecting,
+            retries_exhausted: false,
+            ..update.clone()
+        };
+        assert_eq!(
+            health_reason(
+                &reconnecting,
+                Some("Connection reset without closing handshake")
+            )
+            .as_deref(),
+            Some("reconnecting: kline (Connection reset without closing handshake)")
+        );
+
+        tracker.mark_reconnecting();
+        tracker.mark_live();
         assert_eq!(tracker.state, FeedState::Live);
+    }
 
-        tracker.refresh(now + Duration::from_secs(3));
-        assert_eq!(tracker.state, FeedState::Reconnecting);
+    #[test]
+    fn frame_limiter_caps_redraw_rate() {
+        let now = Instant::now();
+        let mut limiter = FrameLimiter::new(20);
+        assert!(limiter.ready(now));
+
+        limiter.mark_drawn(now);
+        assert!(!limiter.ready(now + Duration::from_millis(10)));
+        assert_eq!(
+            limiter.poll_timeout(now + Duration::from_millis(10)),
+            Duration::from_millis(40)
+        );
+        assert!(limiter.ready(now + Duration::from_millis(50)));
+    }
 
-        tracker.refresh(now + Duration::from_secs(6));
-        assert_eq!(tracker.state, FeedState::Degraded);
+    #[test]
+    fn symbol_switch_waits_for_navigation_to_settle() {
+        let start = Instant::now();
+        let mut debounce = SwitchDebounce::new(Duration::from_millis(250));
+        assert!(!debounce.fire(start));
+
+        debounce.schedule(start);
+        debounce.schedule(start + Duration::from_millis(200));
+        assert!(!debounce.fire(start + Duration::from_millis(300)));
+        assert!(debounce.fire(start + Duration::from_millis(450)));
+        assert!(!debounce.fire(start + Duration::from_millis(900)));
+
+        debounce.schedule(start);
+        debounce.cancel();
+        assert!(!debounce.fire(start + Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn chart_cadence_spaces_streamed_redraws() {
+        let now = Instant::now();
+        let mut every_update = ChartCadence::new(0);
+        every_update.mark_drawn(now);
+        assert!(every_update.due(now));
+
+        let mut cadence = ChartCadence::new(1000);
+        assert!(cadence.due(now));
+        cadence.mark_drawn(now);
+        assert!(!cadence.due(now + Duration::from_millis(999)));
+        assert!(cadence.due(now + Duration::from_millis(1000)));
+    }
+
+    #[test]
+    fn replay_window_spans_the_limit_up_to_now() {
+        const HOUR: u64 = 3_600_000;
+        assert_eq!(
+            replay_window(0, "1h", 24, 1000 * HOUR),
+            Ok(KlineWindow {
+                start_ms: 0,
+                end_ms: 24 * HOUR - 1,
+            })
+        );
+        assert_eq!(
+            replay_window(0, "1h", 5000, 10_000 * HOUR).unwrap().end_ms,
+            1000 * HOUR - 1
+        );
+        assert_eq!(
+            replay_window(0, "1h", 24, 10 * HOUR).unwrap().end_ms,
+            10 * HOUR
+        );
+        assert!(replay_window(10 * HOUR, "1h", 24, 10 * HOUR).is_err());
+        assert!(replay_window(0, "1x", 24, 10 * HOUR).is_err());
+    }
+
+    #[test]
+    fn idle_watch_sleeps_after_the_timeout_and_wakes_on_input() {
+        let start = Instant::now();
+        let mut watch = IdleWatch::new(Duration::from_secs(60), start);
+        assert!(!watch.update(start + Duration::from_secs(59)));
+        assert!(watch.update(start + Duration::from_secs(60)));
+        assert!(watch.is_idle());
+        // Only the transition is reported.
+        assert!(!watch.update(start + Duration::from_secs(120)));
+
+        assert!(watch.touch(start + Duration::from_secs(130)));
+        assert!(!watch.is_idle());
+        assert!(!watch.touch(start + Duration::from_secs(131)));
+        assert!(!watch.update(start + Duration::from_secs(190)));
+
+        let mut never = IdleWatch::new(Duration::ZERO, start);
+        assert!(!never.update(start + Duration::from_secs(86_400)));
+    }
+
+    #[tokio::test]
+    async fn rate_limited_fetch_backs_off_then_succeeds() {
+        let calls = std::cell::Cell::new(0);
+        let mut backoffs = Vec::new();
+
+        let result = retry_fetch(
+            || {
+                let call = calls.get();
+                calls.set(call + 1);
+                async move {
+                    if call == 0 {
+                        Err(FetchError::RateLimited {
+                            retry_after: Duration::from_millis(5),
+                        })
+                    } else {
+                        Ok(42)
+                    }
+                }
+            },
+            |retry_after| backoffs.push(retry_after),
+        )
+        .await;
+
+        assert_eq!(result, Ok(42));
+        assert_eq!(calls.get(), 2);
+        assert_eq!(backoffs, vec![Duration::from_millis(5)]);
+    }
+
+    #[tokio::test]
+    async fn rejected_fetch_is_not_retried() {
+        let calls = std::cell::Cell::new(0);
+        let result: Result<(), _> = retry_fetch(
+            || {
+                calls.set(calls.get() + 1);
+                async { Err(FetchError::Status(400)) }
+            },
+            |_| {},
+        )
+        .await;
+
+        assert!(result.unwrap_err().rejected);
+        assert_eq!(calls.get(), 1);
     }
 }
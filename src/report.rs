@@ -0,0 +1,226 @@
+use crate::data::{OrderBook, Ticker24h, Trade};
+use crate::ui::format::{format_price, format_volume, symbol_price_decimals, VolumeFormat};
+use crate::ui::timeaxis::utc_parts;
+use std::fmt::Write;
+
+/// Book levels listed per side.
+const BOOK_LEVELS: usize = 5;
+/// Largest recent trades listed, by notional.
+const LARGE_TRADES: usize = 5;
+
+/// A point-in-time read of the charted market, gathered from the panels.
+pub struct Dashboard<'a> {
+    pub symbol: &'a str,
+    pub interval: &'a str,
+    pub taken_at_ms: u64,
+    pub last_price: Option<f64>,
+    /// Change as the watchlist shows it, in percent.
+    pub change_pct: Option<f64>,
+    /// Latest value of each overlay that is on, e.g. `("SMA20", 64_210.5)`.
+    pub indicators: Vec<(String, f64)>,
+    pub book: Option<&'a OrderBook>,
+    pub trades: &'a [Trade],
+    pub stats: Option<&'a Ticker24h>,
+    pub max_decimals: usize,
+    /// Decimals the symbol quotes in, when known.
+    pub price_precision: Option<usize>,
+    pub volume_format: VolumeFormat,
+}
+
+/// `tickertui-BTCUSDT-20241015-142530.md`, by UTC time.
+pub fn report_file_name(symbol: &str, taken_at_ms: u64) -> String {
+    let t = utc_parts(taken_at_ms);
+    format!(
+        "tickertui-{}-{:04}{:02}{:02}-{:02}{:02}{:02}.md",
+        symbol, t.year, t.month, t.day, t.hour, t.minute, t.second
+    )
+}
+
+/// The dashboard as a Markdown report.
+pub fn render_markdown(dashboard: &Dashboard) -> String {
+    let price = |value: f64| {
+        format_price(
+            value,
+            symbol_price_decimals(value, dashboard.price_precision, dashboard.max_decimals),
+        )
+    };
+    let size = |value: f64| format_volume(value, 4, dashboard.volume_format);
+    let t = utc_parts(dashboard.taken_at_ms);
+    let mut out = String::new();
+
+    let _ = writeln!(
+        out,
+        "# {} · {}\n\n{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC\n",
+        dashboard.symbol,
+        dashboard.interval.to_uppercase(),
+        t.year,
+        t.month,
+        t.day,
+        t.hour,
+        t.minute,
+        t.second
+    );
+
+    let _ = writeln!(out, "## Chart\n");
+    match dashboard.last_price {
+        Some(last) => {
+            let _ = writeln!(out, "- Last: {}", price(last));
+        }
+        None => {
+            let _ = writeln!(out, "- Last: n/a");
+        }
+    }
+    if let Some(change) = dashboard.change_pct {
+        let _ = writeln!(out, "- Change: {change:+.2}%");
+    }
+    for (name, value) in &dashboard.indicators {
+        let _ = writeln!(out, "- {name}: {}", price(*value));
+    }
+
+    let _ = writeln!(out, "\n## 24h\n");
+    match dashboard.stats {
+        Some(stats) => {
+            let _ = writeln!(
+                out,
+                "- Open {} · High {} · Low {}\n- Change {} ({:+.2}%)\n- Volume {} · Trades {}",
+                price(stats.open),
+                price(stats.high),
+                price(stats.low),
+                price(stats.price_change),
+                stats.price_change_pct,
+                size(stats.volume),
+                stats.trade_count
+            );
+        }
+        None => {
+            let _ = writeln!(out, "Not loaded.");
+        }
+    }
+
+    let _ = writeln!(out, "\n## Order book\n");
+    match dashboard.book {
+        Some(book) => {
+            let _ = writeln!(
+                out,
+                "| Bid size | Bid | Ask | Ask size |\n|---:|---:|---:|---:|"
+            );
+            for level in 0..BOOK_LEVELS {
+                let (bid, ask) = (book.bids.get(level), book.asks.get(level));
+                if bid.is_none() && ask.is_none() {
+                    break;
+                }
+                let cell =
+                    |entry: Option<f64>, f: &dyn Fn(f64) -> String| entry.map_or(String::new(), f);
+                let _ = writeln!(
+                    out,
+                    "| {} | {} | {} | {} |",
+                    cell(bid.map(|e| e.quantity), &size),
+                    cell(bid.map(|e| e.price), &price),
+                    cell(ask.map(|e| e.price), &price),
+                    cell(ask.map(|e| e.quantity), &size)
+                );
+            }
+        }
+        None => {
+            let _ = writeln!(out, "No book yet.");
+        }
+    }
+
+    let _ = writeln!(out, "\n## Large trades\n");
+    let large = largest_trades(dashboard.trades, LARGE_TRADES);
+    if large.is_empty() {
+        let _ = writeln!(out, "No trades yet.");
+    } else {
+        let _ = writeln!(out, "| Time | Side | Price | Size |\n|---|---|---:|---:|");
+        for trade in large {
+            let t = utc_parts(trade.timestamp);
+            let _ = writeln!(
+                out,
+                "| {:02}:{:02}:{:02} | {} | {} | {} |",
+                t.hour,
+                t.minute,
+                t.second,
+                if trade.is_buyer_maker { "SELL" } else { "BUY" },
+                price(trade.price),
+                size(trade.quantity)
+            );
+        }
+    }
+    out
+}
+
+/// The `count` largest of `trades` by notional, biggest first.
+fn largest_trades(trades: &[Trade], count: usize) -> Vec<&Trade> {
+    let mut sorted: Vec<&Trade> = trades.iter().collect();
+    sorted.sort_by(|a, b| (b.price * b.quantity).total_cmp(&(a.price * a.quantity)));
+    sorted.truncate(count);
+    sorted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::orderbook::OrderBookEntry;
+
+    fn trade(price: f64, quantity: f64, is_buyer_maker: bool) -> Trade {
+        Trade {
+            price,
+            quantity,
+            is_buyer_maker,
+            timestamp: 1_709_214_330_000,
+            first_trade_id: 1,
+            last_trade_id: 1,
+        }
+    }
+
+    #[test]
+    fn names_the_file_by_symbol_and_utc_time() {
+        assert_eq!(
+            report_file_name("BTCUSDT", 1_709_214_330_000),
+            "tickertui-BTCUSDT-20240229-134530.md"
+        );
+    }
+
+    #[test]
+    fn report_gathers_every_panel() {
+        let book = OrderBook {
+            bids: vec![OrderBookEntry {
+                price: 100.0,
+                quantity: 2.0,
+            }],
+            asks: vec![OrderBookEntry {
+                price: 101.0,
+                quantity: 1.5,
+            }],
+            last_update: 0,
+        };
+        let trades = vec![
+            trade(100.0, 1.0, false),
+            trade(101.0, 5.0, true),
+            trade(100.5, 0.1, false),
+        ];
+        let report = render_markdown(&Dashboard {
+            symbol: "BTCUSDT",
+            interval: "1m",
+            taken_at_ms: 1_709_214_330_000,
+            last_price: Some(100.5),
+            change_pct: Some(-1.25),
+            indicators: vec![("SMA20".to_string(), 99.75)],
+            book: Some(&book),
+            trades: &trades,
+            stats: None,
+            max_decimals: 8,
+            price_precision: None,
+            volume_format: VolumeFormat::default(),
+        });
+
+        assert!(report.starts_with("# BTCUSDT · 1M\n\n2024-02-29 13:45:30 UTC\n"));
+        assert!(report.contains("- Last: 100.50\n- Change: -1.25%\n- SMA20: 99.75\n"));
+        assert!(report.contains("## 24h\n\nNot loaded."));
+        assert!(report.contains("| 2 | 100.00 | 101.00 | 1.5 |"));
+        // Biggest notional first.
+        let sell = report.find("| SELL | 101.00").unwrap();
+        let buy = report.find("| BUY | 100.00").unwrap();
+        assert!(sell < buy);
+    }
+}
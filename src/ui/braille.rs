@@ -0,0 +1,126 @@
+use ratatui::{buffer::Buffer, layout::Rect, style::Color};
+
+/// Bit for each dot of a braille cell, indexed `[row][column]`.
+const DOT_BITS: [[u8; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+const BRAILLE_BLANK: u32 = 0x2800;
+
+/// A grid of braille dots, two across and four down per terminal cell.
+/// Dot `(0, 0)` is the top-left of the top-left cell.
+#[derive(Debug, Clone)]
+pub struct BrailleCanvas {
+    width: u16,
+    height: u16,
+    cells: Vec<u8>,
+}
+
+impl BrailleCanvas {
+    /// A blank canvas `width` by `height` terminal cells.
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![0; usize::from(width) * usize::from(height)],
+        }
+    }
+
+    pub fn dot_width(&self) -> u32 {
+        u32::from(self.width) * 2
+    }
+
+    pub fn dot_height(&self) -> u32 {
+        u32::from(self.height) * 4
+    }
+
+    /// Raises one dot; dots off the canvas are ignored.
+    pub fn set(&mut self, x: u32, y: u32) {
+        if x >= self.dot_width() || y >= self.dot_height() {
+            return;
+        }
+        let idx = (y / 4) as usize * usize::from(self.width) + (x / 2) as usize;
+        self.cells[idx] |= DOT_BITS[(y % 4) as usize][(x % 2) as usize];
+    }
+
+    /// Raises every dot on the straight segment between two dots.
+    pub fn line(&mut self, (x0, y0): (u32, u32), (x1, y1): (u32, u32)) {
+        let (mut x, mut y) = (i64::from(x0), i64::from(y0));
+        let (x1, y1) = (i64::from(x1), i64::from(y1));
+        let dx = (x1 - x).abs();
+        let dy = -(y1 - y).abs();
+        let step_x = if x < x1 { 1 } else { -1 };
+        let step_y = if y < y1 { 1 } else { -1 };
+        let mut err = dx + dy;
+        loop {
+            self.set(x as u32, y as u32);
+            if x == x1 && y == y1 {
+                break;
+            }
+            let doubled = 2 * err;
+            if doubled >= dy {
+                err += dy;
+                x += step_x;
+            }
+            if doubled <= dx {
+                err += dx;
+                y += step_y;
+            }
+        }
+    }
+
+    /// The glyph for the cell at `(column, row)`, or `None` when it has no
+    /// dots raised.
+    pub fn glyph(&self, column: u16, row: u16) -> Option<char> {
+        if column >= self.width || row >= self.height {
+            return None;
+        }
+        let bits = self.cells[usize::from(row) * usize::from(self.width) + usize::from(column)];
+        (bits != 0).then(|| char::from_u32(BRAILLE_BLANK + u32::from(bits)).unwrap_or(' '))
+    }
+
+    /// Writes every non-blank cell into `buf` with its top-left at `area`'s,
+    /// leaving blank cells untouched so anything drawn underneath shows.
+    pub fn render(&self, buf: &mut Buffer, area: Rect, color: Color) {
+        for row in 0..self.height.min(area.height) {
+            for column in 0..self.width.min(area.width) {
+                if let Some(glyph) = self.glyph(column, row) {
+                    buf[(area.x + column, area.y + row)]
+                        .set_char(glyph)
+                        .set_fg(color);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dots_map_to_braille_bits() {
+        let mut canvas = BrailleCanvas::new(2, 1);
+        assert_eq!(canvas.glyph(0, 0), None);
+        canvas.set(0, 0);
+        assert_eq!(canvas.glyph(0, 0), Some('⠁'));
+        canvas.set(1, 3);
+        assert_eq!(canvas.glyph(0, 0), Some('⢁'));
+        // Second cell, left column, bottom row.
+        canvas.set(2, 3);
+        assert_eq!(canvas.glyph(1, 0), Some('⡀'));
+        // Off the canvas.
+        canvas.set(4, 0);
+        canvas.set(0, 4);
+        assert_eq!(canvas.glyph(2, 0), None);
+    }
+
+    #[test]
+    fn line_covers_both_ends_and_every_column() {
+        let mut canvas = BrailleCanvas::new(2, 2);
+        canvas.line((0, 7), (3, 0));
+        // The same steep stroke in both cells it crosses.
+        assert_eq!(canvas.glyph(0, 1), Some('⡜'));
+        assert_eq!(canvas.glyph(1, 0), Some('⡜'));
+        assert_eq!(canvas.glyph(0, 0), None);
+        let full = BrailleCanvas::new(1, 1);
+        assert_eq!((full.dot_width(), full.dot_height()), (2, 4));
+    }
+}
@@ -1,13 +1,162 @@
-use crate::data::Candle;
-use crate::ui::indicators::{calculate_rsi, calculate_sma};
+use crate::data::symbols::{snap_to_tick, step_decimals};
+use crate::data::{merge_candles, validate_candle, Candle, KlineWindow};
+use crate::ui::braille::BrailleCanvas;
+use crate::ui::format::{format_price, format_signed_price, format_volume, infer_precision};
+use crate::ui::grid::Grid;
+use crate::ui::heatmap::TradeHeatmap;
+use crate::ui::indicators::{
+    calculate_atr, calculate_bollinger, calculate_keltner, calculate_roc, is_squeeze,
+    percent_range, squeeze_releases, IndicatorBasis, IndicatorPrefs, IndicatorState,
+    SymbolIndicators, DEFAULT_ROC_PERIOD, DEFAULT_RSI_PERIOD, DEFAULT_SMA_PERIOD,
+};
+use crate::ui::notes::{remove_note, upsert_note, Note, SymbolNotes};
+use crate::ui::overlay::{self, LineStyle, LineStyles};
+use crate::ui::priceaxis::price_ticks;
+use crate::ui::relative::{relative_strength, RelativeStrength, BENCHMARK};
+use crate::ui::theme::{blend_color, mark_stale, scale_color};
+use crate::ui::timeaxis::{format_time_label, utc_parts, CandleTimeBasis};
+use crate::ui::Theme;
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Clear, Padding, Paragraph},
     Frame,
 };
-use std::collections::VecDeque;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::collections::{BTreeSet, VecDeque};
+use std::ops::RangeInclusive;
+use std::time::{Duration, Instant};
+
+/// Whether candle bodies are drawn one column wide (wick-forward, the
+/// original look) or widened to three columns when spacing allows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum CandleEmphasis {
+    #[default]
+    Wick,
+    Body,
+}
+
+/// How the still-forming newest candle is set apart from closed ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum LiveCandleStyle {
+    Off,
+    /// A marker just above the candle's high.
+    #[default]
+    Marker,
+    /// The candle drawn in a lighter shade of its color.
+    Bright,
+}
+
+/// How up candle bodies are filled: solid like down candles, or drawn as an
+/// outline (the hollow/filled convention). Half-block mode stays solid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum CandleStyle {
+    #[default]
+    Solid,
+    Hollow,
+}
+
+/// Which plot corner the heads-up price readout sits in, if any.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PriceHud {
+    #[default]
+    Off,
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+/// Where the price axis labels go. Hiding them gives their columns to the
+/// candles; the volume and relative-strength gutters follow the same side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PriceAxis {
+    #[default]
+    Left,
+    Right,
+    Hidden,
+}
+
+impl PriceAxis {
+    pub fn next(self) -> Self {
+        match self {
+            PriceAxis::Left => PriceAxis::Right,
+            PriceAxis::Right => PriceAxis::Hidden,
+            PriceAxis::Hidden => PriceAxis::Left,
+        }
+    }
+}
+
+/// Columns reserved beside the plot for price labels and pane gutters.
+const AXIS_WIDTH: u16 = 13;
+/// Width of the text in a pane gutter, one column short of `AXIS_WIDTH`.
+const GUTTER_WIDTH: u16 = 12;
+
+pub const DEFAULT_PADDING_FACTOR: f64 = 0.5;
+pub const DEFAULT_CANDLE_WIDTH: u16 = 1;
+pub const DEFAULT_CANDLE_GAP: u16 = 1;
+const ATR_PERIOD: usize = 14;
+/// Lookback shared by the squeeze's Bollinger Bands and Keltner Channels.
+const SQUEEZE_PERIOD: usize = 20;
+const SQUEEZE_BOLLINGER_STD_DEVS: f64 = 2.0;
+const SQUEEZE_KELTNER_ATR_MULTIPLE: f64 = 1.5;
+/// Background behind candles in a squeeze.
+const SQUEEZE_SHADE: Color = Color::Rgb(30, 30, 48);
+/// Visible candle counts the zoom presets step through before fitting the
+/// whole history.
+pub const ZOOM_PRESETS: [usize; 4] = [50, 100, 200, 500];
+/// Recent closes read when inferring the symbol's precision.
+const PRECISION_SAMPLE: usize = 50;
+const SMA_GLYPH: char = '•';
+const RSI_GLYPH: char = '·';
+const HEAT_COLOR: Color = Color::Rgb(255, 140, 0);
+/// How far the in-progress candle has moved from its open, as `(delta,
+/// percent)`. `None` once the stream has marked the candle closed, or when
+/// its prices don't parse.
+pub fn live_build(candle: &Candle) -> Option<(f64, f64)> {
+    if candle.is_closed {
+        return None;
+    }
+    let open: f64 = candle.open.parse().ok()?;
+    let close: f64 = candle.close.parse().ok()?;
+    let pct = if open > 0.0 {
+        (close - open) / open * 100.0
+    } else {
+        0.0
+    };
+    Some((close - open, pct))
+}
+
+/// Splits a candle's base volume into taker-buy and taker-sell (the rest).
+/// Unparseable or negative fields count as zero, and taker buy is capped at
+/// the candle's volume.
+pub fn taker_split(candle: &Candle) -> (f64, f64) {
+    let parse = |field: &str| {
+        field
+            .parse::<f64>()
+            .ok()
+            .filter(|value| value.is_finite() && *value > 0.0)
+            .unwrap_or(0.0)
+    };
+    let volume = parse(&candle.volume);
+    let buy = parse(&candle.taker_buy_base).min(volume);
+    (buy, volume - buy)
+}
+
+/// Parsed open, high, low, close, volume.
+type Ohlcv = (f64, f64, f64, f64, f64);
+
+/// Brightness of the least active candle when activity tinting is on.
+const MIN_ACTIVITY_BRIGHTNESS: f64 = 0.4;
+/// How far the oldest candle is pulled toward gray when recent candles are
+/// highlighted.
+const MAX_RECENCY_FADE: f64 = 0.75;
+pub const DEFAULT_RECENT_CANDLES: usize = 20;
+/// Padding as a share of the price range when the window is too short for ATR.
+const FALLBACK_PADDING_PCT: f64 = 0.05;
 
 #[derive(Debug, Clone)]
 pub struct Chart {
@@ -19,6 +168,140 @@ pub struct Chart {
     pub max_candles: usize,
     pub show_sma: bool,
     pub show_rsi: bool,
+    /// Shades candles where the Bollinger Bands sit inside the Keltner
+    /// Channels and marks where that squeeze releases.
+    pub show_squeeze: bool,
+    pub line_styles: LineStyles,
+    pub indicators: IndicatorState,
+    pub half_block: bool,
+    /// Draws the close series as a braille line instead of candles.
+    pub braille: bool,
+    pub emphasis: CandleEmphasis,
+    pub live_candle: LiveCandleStyle,
+    pub candle_style: CandleStyle,
+    /// Preferred body width in columns; the visible candle count is chosen
+    /// so each candle gets this plus `candle_gap`.
+    pub candle_width: u16,
+    /// Blank columns between neighbouring bodies.
+    pub candle_gap: u16,
+    /// Draws higher prices toward the bottom of the plot.
+    pub invert_y: bool,
+    pub price_axis: PriceAxis,
+    /// Corner for the large symbol, price and change readout, drawn over
+    /// everything else in the plot.
+    pub price_hud: PriceHud,
+    /// The historical window on show; live updates are held back meanwhile.
+    pub review: Option<KlineWindow>,
+    /// Places axis labels at round prices rather than evenly spaced rows.
+    pub round_price_labels: bool,
+    /// Headroom above the high and below the low, in multiples of the
+    /// visible window's ATR. Zero disables padding.
+    pub padding_factor: f64,
+    /// Candles back from the newest one; `None` when the crosshair is hidden.
+    pub crosshair: Option<usize>,
+    /// Plot row of the crosshair's price readout, from the top.
+    crosshair_row: u16,
+    /// Rounds the crosshair readout to the symbol's tick size.
+    pub snap_to_tick: bool,
+    /// The charted symbol's price increment; zero when unknown.
+    pub tick_size: f64,
+    /// Falls back to the precision in the candle data when the tick size
+    /// is unknown.
+    pub infer_precision: bool,
+    /// Height of the candle plot at the last render, for moving the
+    /// crosshair row.
+    plot_rows: Cell<u16>,
+    pub notes: SymbolNotes,
+    /// Saved overlay choices per symbol, applied when the symbol loads.
+    pub indicator_prefs: SymbolIndicators,
+    /// Overlays for symbols without saved choices of their own.
+    pub default_indicators: IndicatorPrefs,
+    pub heatmap: TradeHeatmap,
+    /// Optional strip comparing the symbol's price against BTC.
+    pub relative: RelativeStrength,
+    /// Rate-of-change strip under the volume bars.
+    pub show_roc: bool,
+    pub roc_period: usize,
+    /// Dims quiet candles and brightens busy ones by trade count within the
+    /// visible window.
+    pub activity_tint: bool,
+    /// Keeps the newest `recent_candles` in view at full brightness and fades
+    /// older ones toward gray the further left they are.
+    pub highlight_recent: bool,
+    pub recent_candles: usize,
+    /// Series the range readout and returns panel are computed on.
+    pub indicator_basis: IndicatorBasis,
+    /// Background gridlines under the candles.
+    pub grid: Grid,
+    /// Whether time labels show each candle's open or close time.
+    pub time_basis: CandleTimeBasis,
+    /// Shows the whole buffer, merging neighbouring candles when there are
+    /// more than columns. Zooming, panning or the crosshair leave it.
+    pub fit: bool,
+    /// Candle count picked from [`ZOOM_PRESETS`], overriding the width-based
+    /// count until zooming with `+`/`-`. Merges neighbours like `fit` when
+    /// there are more candles than columns.
+    pub preset: Option<usize>,
+    /// Start each newly loaded chart in `fit` mode.
+    pub fit_on_load: bool,
+    /// The kline feed has gone quiet for longer than expected.
+    pub stale: bool,
+    /// Ease back to the live edge instead of snapping there.
+    pub smooth_catch_up: bool,
+    /// Open times of candles rejected by [`validate_candle`] since the
+    /// symbol loaded. A live candle that stays bad across ticks counts once.
+    pub dropped_candles: BTreeSet<u64>,
+    catch_up: Option<CatchUp>,
+}
+
+const CATCH_UP_DURATION: Duration = Duration::from_millis(400);
+/// Brightness of a wick half sharing a half-block cell with the body.
+const HALF_BLOCK_WICK_SHADE: f64 = 0.5;
+
+/// An in-progress scroll from `from` candles back to the live edge.
+#[derive(Debug, Clone, Copy)]
+struct CatchUp {
+    from: usize,
+    started: Instant,
+}
+
+/// The candles one render draws, with the buffer index of the newest
+/// candle behind each (so overlays can look up indicator values).
+struct Window<'a> {
+    candles: Vec<Cow<'a, Candle>>,
+    buffer_idx: Vec<usize>,
+    /// Whether neighbouring candles were merged to fit the width.
+    merged: bool,
+    /// Buffered candles behind the window, before any merging.
+    span: usize,
+}
+
+/// Combines consecutive candles into one spanning all of them.
+fn merge_group(group: &[&Candle]) -> Candle {
+    let first = group[0];
+    let last = group[group.len() - 1];
+    let values = |field: fn(&Candle) -> &str| {
+        group
+            .iter()
+            .filter_map(move |candle| field(candle).parse::<f64>().ok())
+    };
+    let sum = |field: fn(&Candle) -> &str| values(field).sum::<f64>().to_string();
+
+    Candle {
+        open_time: first.open_time,
+        open: first.open.clone(),
+        high: values(|c| &c.high).fold(f64::MIN, f64::max).to_string(),
+        low: values(|c| &c.low).fold(f64::MAX, f64::min).to_string(),
+        close: last.close.clone(),
+        volume: sum(|c| &c.volume),
+        close_time: last.close_time,
+        quote_volume: sum(|c| &c.quote_volume),
+        number_of_trades: group.iter().map(|c| c.number_of_trades).sum(),
+        taker_buy_base: sum(|c| &c.taker_buy_base),
+        taker_buy_quote: sum(|c| &c.taker_buy_quote),
+        ignore: "0".to_string(),
+        is_closed: last.is_closed,
+    }
 }
 
 impl Chart {
@@ -32,87 +315,721 @@ impl Chart {
             max_candles: 200,
             show_sma: false,
             show_rsi: false,
+            show_squeeze: false,
+            line_styles: LineStyles::default(),
+            indicators: IndicatorState::new(DEFAULT_SMA_PERIOD, DEFAULT_RSI_PERIOD),
+            half_block: false,
+            braille: false,
+            emphasis: CandleEmphasis::default(),
+            live_candle: LiveCandleStyle::default(),
+            candle_style: CandleStyle::default(),
+            candle_width: DEFAULT_CANDLE_WIDTH,
+            candle_gap: DEFAULT_CANDLE_GAP,
+            invert_y: false,
+            price_axis: PriceAxis::default(),
+            price_hud: PriceHud::default(),
+            review: None,
+            round_price_labels: true,
+            padding_factor: DEFAULT_PADDING_FACTOR,
+            crosshair: None,
+            crosshair_row: 0,
+            snap_to_tick: true,
+            tick_size: 0.0,
+            infer_precision: true,
+            plot_rows: Cell::new(0),
+            notes: SymbolNotes::new(),
+            indicator_prefs: SymbolIndicators::new(),
+            default_indicators: IndicatorPrefs::default(),
+            relative: RelativeStrength::new(),
+            show_roc: false,
+            roc_period: DEFAULT_ROC_PERIOD,
+            heatmap: TradeHeatmap::new(),
+            activity_tint: false,
+            highlight_recent: false,
+            recent_candles: DEFAULT_RECENT_CANDLES,
+            indicator_basis: IndicatorBasis::default(),
+            grid: Grid::default(),
+            time_basis: CandleTimeBasis::default(),
+            fit: false,
+            preset: None,
+            fit_on_load: false,
+            stale: false,
+            smooth_catch_up: true,
+            dropped_candles: BTreeSet::new(),
+            catch_up: None,
         }
     }
 
     pub fn add_candle(&mut self, candle: Candle) {
-        if let Some(last) = self.candles.back() {
-            if candle.open_time == last.open_time {
-                *self.candles.back_mut().unwrap() = candle;
-                return;
-            }
+        if !validate_candle(&candle) {
+            self.dropped_candles.insert(candle.open_time);
+            return;
         }
-        self.candles.push_back(candle);
+        let at_back = self
+            .candles
+            .back()
+            .is_none_or(|last| candle.open_time >= last.open_time);
+        if !at_back {
+            // Out of order, e.g. a delayed message after a reconnect.
+            self.merge_candles(vec![candle]);
+            return;
+        }
+        merge_candles(&mut self.candles, [candle]);
         if self.candles.len() > self.max_candles {
             self.candles.pop_front();
         }
+        self.indicators.sync(&self.candles);
+    }
+
+    /// Records candles dropped before reaching the chart, e.g. by the fetch.
+    /// Returns whether any of them is new.
+    pub fn reject_candles(&mut self, open_times: impl IntoIterator<Item = u64>) -> bool {
+        let before = self.dropped_candles.len();
+        self.dropped_candles.extend(open_times);
+        self.dropped_candles.len() > before
+    }
+
+    pub fn update_candles(&mut self, new_candles: Vec<Candle>) {
+        self.merge_candles(new_candles);
     }
 
-    pub fn update_candles(&mut self, mut new_candles: Vec<Candle>) {
-        new_candles.sort_by_key(|c| c.open_time);
-        for candle in new_candles {
-            self.add_candle(candle);
+    /// Merges candles from another source into the buffer by `open_time`,
+    /// filling gaps and replacing what is there unless it is already closed
+    /// and the newcomer isn't. See [`merge_candles`].
+    pub fn merge_candles(&mut self, candles: Vec<Candle>) {
+        let (valid, rejected): (Vec<_>, Vec<_>) = candles.into_iter().partition(validate_candle);
+        self.reject_candles(rejected.iter().map(|c| c.open_time));
+        let changed = merge_candles(&mut self.candles, valid);
+        while self.candles.len() > self.max_candles {
+            self.candles.pop_front();
         }
+        if changed {
+            self.indicators.reset();
+        }
+        self.indicators.sync(&self.candles);
     }
 
+    /// From the fitted overview this first returns to the recent candles at
+    /// the current zoom.
     pub fn zoom_in(&mut self) {
+        if self.preset.take().is_some() {
+            return;
+        }
+        if self.fit {
+            self.fit = false;
+            return;
+        }
         self.zoom = (self.zoom * 2).min(32);
     }
 
+    /// Past the widest zoom this switches to the fitted overview.
     pub fn zoom_out(&mut self) {
+        if self.preset.take().is_some() {
+            return;
+        }
+        if self.zoom == 1 {
+            self.fit = true;
+        }
         self.zoom = (self.zoom / 2).max(1);
     }
 
+    /// Steps through [`ZOOM_PRESETS`] and then the fitted overview, back to
+    /// the smallest preset after that.
+    pub fn cycle_zoom_preset(&mut self) {
+        let next = match (self.fit, self.preset) {
+            (true, _) | (false, None) => Some(ZOOM_PRESETS[0]),
+            (false, Some(current)) => ZOOM_PRESETS.iter().copied().find(|n| *n > current),
+        };
+        self.preset = next;
+        self.fit = next.is_none();
+        self.crosshair = None;
+    }
+
+    /// The preset's candle count, clamped to what is buffered.
+    fn preset_count(&self) -> Option<usize> {
+        self.preset
+            .map(|count| count.min(self.candles.len()).max(1))
+    }
+
+    /// How many buffered candles are merged into each column when the chart
+    /// is drawn `area_width` wide; 1 unless fitting the whole history or a
+    /// preset wider than the plot.
+    pub fn candles_per_column(&self, area_width: u16) -> usize {
+        let chart_width = self.plot_width(area_width);
+        if self.fit {
+            Self::fit_stride(self.candles.len(), chart_width)
+        } else if let Some(count) = self.preset_count() {
+            Self::fit_stride(count, chart_width)
+        } else {
+            1
+        }
+    }
+
+    fn fit_stride(len: usize, chart_width: usize) -> usize {
+        len.div_ceil(chart_width.max(1)).max(1)
+    }
+
     pub fn pan_left(&mut self) {
+        self.fit = false;
         let max_offset = self.candles.len().saturating_sub(1);
         self.offset = (self.offset + 1).min(max_offset);
     }
 
     pub fn pan_right(&mut self) {
+        self.fit = false;
         self.offset = self.offset.saturating_sub(1);
     }
 
+    /// Returns the view to the newest candle, scrolling there over a few
+    /// frames when `smooth_catch_up` is on.
+    pub fn jump_to_live(&mut self, now: Instant) {
+        self.fit = false;
+        self.crosshair = None;
+        if self.smooth_catch_up && self.offset > 0 {
+            self.catch_up = Some(CatchUp {
+                from: self.offset,
+                started: now,
+            });
+        } else {
+            self.offset = 0;
+        }
+    }
+
+    /// Moves an in-progress catch-up along its ease-out curve. Returns
+    /// whether it is still running and needs more frames.
+    pub fn advance_catch_up(&mut self, now: Instant) -> bool {
+        let Some(catch_up) = self.catch_up else {
+            return false;
+        };
+        let t = now
+            .saturating_duration_since(catch_up.started)
+            .as_secs_f64()
+            / CATCH_UP_DURATION.as_secs_f64();
+        if t >= 1.0 {
+            self.offset = 0;
+            self.catch_up = None;
+            return false;
+        }
+        let remaining = (1.0 - t).powi(3);
+        self.offset = (catch_up.from as f64 * remaining).round() as usize;
+        true
+    }
+
+    /// Stops a catch-up wherever it has got to.
+    pub fn cancel_catch_up(&mut self) {
+        self.catch_up = None;
+    }
+
+    pub fn toggle_crosshair(&mut self) {
+        self.fit = false;
+        self.crosshair = match self.crosshair {
+            Some(_) => None,
+            None if self.candles.is_empty() => None,
+            None => Some(self.offset.min(self.candles.len() - 1)),
+        };
+        self.crosshair_row = self.plot_rows.get() / 2;
+    }
+
+    pub fn crosshair_up(&mut self) {
+        self.crosshair_row = self.crosshair_row.saturating_sub(1);
+    }
+
+    pub fn crosshair_down(&mut self) {
+        let last_row = self.plot_rows.get().saturating_sub(1);
+        self.crosshair_row = (self.crosshair_row + 1).min(last_row);
+    }
+
+    pub fn crosshair_left(&mut self) {
+        if let Some(pos) = self.crosshair {
+            let max_pos = self.candles.len().saturating_sub(1);
+            self.crosshair = Some((pos + 1).min(max_pos));
+        }
+    }
+
+    pub fn crosshair_right(&mut self) {
+        if let Some(pos) = self.crosshair {
+            let pos = pos.saturating_sub(1);
+            self.crosshair = Some(pos);
+            self.offset = self.offset.min(pos);
+        }
+    }
+
+    pub fn crosshair_candle(&self) -> Option<&Candle> {
+        let pos = self.crosshair?;
+        let idx = self.candles.len().checked_sub(pos + 1)?;
+        self.candles.get(idx)
+    }
+
+    /// Pins a note to the crosshair candle at its close price.
+    pub fn add_note(&mut self, text: String) -> bool {
+        let Some(candle) = self.crosshair_candle() else {
+            return false;
+        };
+        let Ok(price) = candle.close.parse::<f64>() else {
+            return false;
+        };
+        let note = Note {
+            open_time: candle.open_time,
+            price,
+            text,
+        };
+        upsert_note(&mut self.notes, &self.symbol, note);
+        true
+    }
+
+    pub fn delete_note(&mut self) -> bool {
+        let Some(open_time) = self.crosshair_candle().map(|c| c.open_time) else {
+            return false;
+        };
+        remove_note(&mut self.notes, &self.symbol, open_time)
+    }
+
+    fn symbol_notes(&self) -> &[Note] {
+        self.notes.get(&self.symbol).map_or(&[], Vec::as_slice)
+    }
+
+    /// Pan offset for rendering, pushed back far enough that the crosshair
+    /// stays inside the window.
+    fn view_offset(&self, visible_count: usize) -> usize {
+        match self.crosshair {
+            Some(pos) => self
+                .offset
+                .max((pos + 1).saturating_sub(visible_count))
+                .min(pos),
+            None => self.offset,
+        }
+    }
+
+    pub fn toggle_invert_y(&mut self) {
+        self.invert_y = !self.invert_y;
+    }
+
+    pub fn cycle_price_axis(&mut self) {
+        self.price_axis = self.price_axis.next();
+    }
+
+    fn axis_width(&self) -> u16 {
+        match self.price_axis {
+            PriceAxis::Hidden => 0,
+            PriceAxis::Left | PriceAxis::Right => AXIS_WIDTH,
+        }
+    }
+
+    /// The candle plot within the chart `area`: the plot columns, less the
+    /// info line below and a spare row above.
+    fn plot_area(&self, area: Rect) -> Rect {
+        Rect {
+            y: area.y + 1,
+            height: area.height.saturating_sub(3),
+            ..self.plot_columns(area)
+        }
+    }
+
+    /// Columns available for candles when the chart is `area_width` wide.
+    pub fn plot_width(&self, area_width: u16) -> usize {
+        area_width.saturating_sub(self.axis_width()) as usize
+    }
+
+    /// The columns of `area` left for plotting once the axis has its share.
+    fn plot_columns(&self, area: Rect) -> Rect {
+        let width = area.width.saturating_sub(self.axis_width());
+        let x = match self.price_axis {
+            PriceAxis::Left => area.x + AXIS_WIDTH.min(area.width),
+            PriceAxis::Right | PriceAxis::Hidden => area.x,
+        };
+        Rect { x, width, ..area }
+    }
+
+    /// Where a pane writes its labels: the axis side, or over the left of
+    /// the plot when the axis is hidden.
+    fn gutter(&self, area: Rect, y: u16, height: u16) -> Rect {
+        let width = GUTTER_WIDTH.min(area.width);
+        let x = match self.price_axis {
+            PriceAxis::Right => area.x + area.width - width,
+            PriceAxis::Left | PriceAxis::Hidden => area.x,
+        };
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
     pub fn toggle_sma(&mut self) {
         self.show_sma = !self.show_sma;
+        self.save_indicator_prefs();
     }
 
     pub fn toggle_rsi(&mut self) {
         self.show_rsi = !self.show_rsi;
+        self.save_indicator_prefs();
+    }
+
+    fn current_indicator_prefs(&self) -> IndicatorPrefs {
+        IndicatorPrefs {
+            show_sma: self.show_sma,
+            show_rsi: self.show_rsi,
+            sma_period: self.indicators.sma_period(),
+            rsi_period: self.indicators.rsi_period(),
+        }
+    }
+
+    /// Records the current overlays: into this symbol's own entry if it has
+    /// one, otherwise as the global default that every other symbol without
+    /// an entry picks up.
+    fn save_indicator_prefs(&mut self) {
+        let prefs = self.current_indicator_prefs();
+        if !self.indicator_prefs.contains_key(&self.symbol) {
+            self.default_indicators = prefs;
+        } else if prefs == self.default_indicators {
+            self.indicator_prefs.remove(&self.symbol);
+        } else {
+            self.indicator_prefs.insert(self.symbol.clone(), prefs);
+        }
+    }
+
+    /// Switches the overlays and their periods to this symbol's saved
+    /// preferences, recomputing the indicators if a period changed.
+    pub fn apply_indicator_prefs(&mut self) {
+        let prefs = self
+            .indicator_prefs
+            .get(&self.symbol)
+            .copied()
+            .unwrap_or(self.default_indicators)
+            .sanitized();
+        self.show_sma = prefs.show_sma;
+        self.show_rsi = prefs.show_rsi;
+        if prefs.sma_period != self.indicators.sma_period()
+            || prefs.rsi_period != self.indicators.rsi_period()
+        {
+            self.indicators = IndicatorState::new(prefs.sma_period, prefs.rsi_period);
+            self.indicators.sync(&self.candles);
+        }
     }
 
+    /// Decimals the symbol quotes in: from its tick size once the exchange
+    /// rules are in, otherwise inferred from the latest closes so offline
+    /// and replay sessions still read right. `None` before any candles.
+    pub fn price_precision(&self) -> Option<usize> {
+        if self.tick_size > 0.0 {
+            return Some(step_decimals(self.tick_size));
+        }
+        if !self.infer_precision || self.candles.is_empty() {
+            return None;
+        }
+        let closes: Vec<&str> = self
+            .candles
+            .iter()
+            .rev()
+            .take(PRECISION_SAMPLE)
+            .map(|candle| candle.close.as_str())
+            .collect();
+        Some(infer_precision(&closes))
+    }
+
+    /// The candles on screen when the chart is drawn `area_width` wide.
+    pub fn visible_candles(&self, area_width: u16) -> Vec<Cow<'_, Candle>> {
+        self.window(self.plot_width(area_width)).candles
+    }
+
+    fn window(&self, chart_width: usize) -> Window<'_> {
+        if self.fit {
+            return self.merged_window(0, self.candles.len(), chart_width);
+        }
+
+        let visible_count = self
+            .preset_count()
+            .unwrap_or_else(|| self.get_visible_count(chart_width));
+        let offset = self.view_offset(visible_count);
+        let start_idx = self.candles.len().saturating_sub(visible_count + offset);
+        let end_idx = self.candles.len().saturating_sub(offset);
+        if self.preset.is_some() && end_idx - start_idx > chart_width {
+            return self.merged_window(start_idx, end_idx, chart_width);
+        }
+        Window {
+            candles: self
+                .candles
+                .range(start_idx..end_idx)
+                .map(Cow::Borrowed)
+                .collect(),
+            buffer_idx: (start_idx..end_idx).collect(),
+            merged: false,
+            span: end_idx - start_idx,
+        }
+    }
+
+    /// Buffered candles `start..end`, with neighbours merged so they fit in
+    /// `chart_width` columns.
+    fn merged_window(&self, start: usize, end: usize, chart_width: usize) -> Window<'_> {
+        let stride = Self::fit_stride(end - start, chart_width);
+        // Group from the newest candle back so the live one is always the
+        // last of a full group; the oldest group may be partial.
+        let first = start + (end - start) % stride;
+        let mut bounds: Vec<(usize, usize)> = Vec::new();
+        if first > start {
+            bounds.push((start, first));
+        }
+        bounds.extend(
+            (first..end)
+                .step_by(stride)
+                .map(|group| (group, group + stride)),
+        );
+        Window {
+            candles: bounds
+                .iter()
+                .map(|&(from, to)| match to - from {
+                    1 => Cow::Borrowed(&self.candles[from]),
+                    _ => Cow::Owned(merge_group(
+                        &self.candles.range(from..to).collect::<Vec<_>>(),
+                    )),
+                })
+                .collect(),
+            buffer_idx: bounds.iter().map(|&(_, to)| to - 1).collect(),
+            merged: stride > 1,
+            span: end - start,
+        }
+    }
+
+    /// Preferred body width, widened to three columns for
+    /// `CandleEmphasis::Body`.
+    fn preferred_body_width(&self) -> usize {
+        let emphasis_width = match self.emphasis {
+            CandleEmphasis::Wick => 1,
+            CandleEmphasis::Body => 3,
+        };
+        usize::from(self.candle_width).max(emphasis_width)
+    }
+
+    /// As many candles as fit at the preferred body width and gap, fewer
+    /// when zoomed in.
     fn get_visible_count(&self, available_width: usize) -> usize {
-        let min_candle_width = 2;
-        let max_candles = available_width / min_candle_width;
-        let zoom_based = (100 / self.zoom).max(10);
-        zoom_based.min(max_candles).max(10)
+        let pitch = self.preferred_body_width() + usize::from(self.candle_gap);
+        (available_width / (pitch.max(1) * self.zoom)).max(10)
     }
 
-    pub fn render(&self, frame: &mut Frame, area: Rect) {
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
         let vertical = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(3),
+                Constraint::Length(theme.framed_height(1)),
                 Constraint::Min(10),
                 Constraint::Length(4),
-                Constraint::Length(3),
+                Constraint::Length(if self.relative.visible { 4 } else { 0 }),
+                Constraint::Length(if self.show_roc { 4 } else { 0 }),
+                Constraint::Length(theme.framed_height(1)),
             ])
             .split(area);
 
         let title = format!("{} / {}", self.symbol, self.interval.to_uppercase());
-        let title_block = Block::default()
-            .title(title)
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Cyan));
+        let title_block = mark_stale(
+            Block::default()
+                .title(title)
+                .borders(theme.borders())
+                .border_style(Style::default().fg(Color::Cyan)),
+            self.stale,
+        );
+        let banner_area = title_block.inner(vertical[0]);
         frame.render_widget(title_block, vertical[0]);
+        if let Some(window) = self.review {
+            self.render_review_banner(frame, banner_area, window);
+        }
 
         let chart_area = vertical[1];
-        self.render_candlesticks(frame, chart_area);
+        if self.candles.is_empty() {
+            self.render_empty_state(frame, chart_area);
+        } else {
+            self.render_candlesticks(frame, chart_area, theme);
+            self.draw_price_hud(frame, self.plot_area(chart_area), theme);
+        }
 
         let volume_area = vertical[2];
-        self.render_volume(frame, volume_area);
+        self.render_volume(frame, volume_area, theme);
+
+        if self.relative.visible {
+            self.render_relative(frame, vertical[3], theme);
+        }
+        if self.show_roc {
+            self.render_roc(frame, vertical[4], theme);
+        }
+
+        let stats_area = vertical[5];
+        self.render_stats(frame, stats_area, theme);
+    }
+
+    /// Plots price relative to BTC under the volume bars, one dot per
+    /// visible candle, with the latest reading in the left gutter.
+    fn render_relative(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        if area.width < 10 || area.height < 2 {
+            return;
+        }
+        let gutter = |frame: &mut Frame, lines: Vec<Line>| {
+            let rect = self.gutter(area, area.y, lines.len() as u16);
+            frame.render_widget(Paragraph::new(lines), rect);
+        };
+        let heading = Line::from(Span::styled("vs BTC", Style::default().fg(Color::Gray)));
+        let muted = |text: &str| {
+            Line::from(Span::styled(
+                text.to_string(),
+                Style::default().fg(Color::DarkGray),
+            ))
+        };
+
+        if self.symbol == BENCHMARK {
+            gutter(frame, vec![heading, muted("n/a")]);
+            return;
+        }
+
+        let inner = self.plot_columns(area);
+        let chart_width = inner.width as usize;
+        let window = self.window(chart_width);
+        let candles: Vec<&Candle> = window.candles.iter().map(AsRef::as_ref).collect();
+        let values = relative_strength(&candles, self.relative.closes());
+        let plotted: Vec<f64> = values.iter().flatten().copied().collect();
+        let (Some(&latest), true) = (plotted.last(), plotted.len() >= 2) else {
+            gutter(frame, vec![heading, muted("waiting…")]);
+            return;
+        };
+
+        let min = plotted.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = plotted.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let range = (max - min).max(f64::EPSILON);
+        let spacing = chart_width / values.len().max(1);
+        for (idx, value) in values.iter().enumerate() {
+            let Some(value) = value else {
+                continue;
+            };
+            let x = Self::clamp_x(inner, chart_width, idx * spacing + spacing / 2);
+            let y_offset = ((max - value) / range * f64::from(inner.height - 1)).round() as u16;
+            let cell = &mut frame.buffer_mut()[(x, inner.y + y_offset.min(inner.height - 1))];
+            cell.set_char('•').set_fg(theme.direction(*value >= 100.0));
+        }
+
+        let reading = Line::from(Span::styled(
+            format!("{:.1}", latest),
+            Style::default().fg(theme.direction(latest >= 100.0)),
+        ));
+        gutter(frame, vec![heading, reading]);
+    }
+
+    /// Plots the rate of change around a dotted zero line, one dot per
+    /// visible candle, with the latest reading in the left gutter.
+    fn render_roc(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        if area.width < 10 || area.height < 2 {
+            return;
+        }
+        let inner = self.plot_columns(area);
+        let chart_width = inner.width as usize;
+        let window = self.window(chart_width);
+        // Only the window and the lookback before it are needed.
+        let (Some(&first), Some(&last)) = (window.buffer_idx.first(), window.buffer_idx.last())
+        else {
+            return;
+        };
+        let start = first.saturating_sub(self.roc_period);
+        let candles: Vec<Candle> = self.candles.range(start..=last).cloned().collect();
+        let roc = calculate_roc(&candles, self.roc_period);
+        let values: Vec<Option<f64>> = window
+            .buffer_idx
+            .iter()
+            .map(|idx| roc[idx - start].filter(|v| v.is_finite()))
+            .collect();
+
+        // Symmetric about zero so the zero line stays put in the middle.
+        let reach = values
+            .iter()
+            .flatten()
+            .fold(0.0_f64, |reach, v| reach.max(v.abs()))
+            .max(f64::EPSILON);
+        let rows = f64::from(inner.height - 1);
+        let row_of = |value: f64| {
+            let offset = ((reach - value) / (2.0 * reach) * rows).round() as u16;
+            inner.y + offset.min(inner.height - 1)
+        };
+        let zero_y = row_of(0.0);
+        for x in inner.x..inner.x + inner.width {
+            frame.buffer_mut()[(x, zero_y)]
+                .set_char('┄')
+                .set_fg(Color::DarkGray);
+        }
+        let spacing = chart_width / values.len().max(1);
+        for (idx, value) in values.iter().enumerate() {
+            let Some(value) = value else {
+                continue;
+            };
+            let x = Self::clamp_x(inner, chart_width, idx * spacing + spacing / 2);
+            frame.buffer_mut()[(x, row_of(*value))]
+                .set_char('•')
+                .set_fg(theme.direction(*value >= 0.0));
+        }
+
+        let heading = Line::from(Span::styled(
+            format!("ROC{}", self.roc_period),
+            Style::default().fg(Color::Gray),
+        ));
+        let reading = match values.last().copied().flatten() {
+            Some(latest) => Line::from(Span::styled(
+                format!("{latest:+.2}%"),
+                Style::default().fg(theme.direction(latest >= 0.0)),
+            )),
+            None => Line::from(Span::styled(
+                "waiting…",
+                Style::default().fg(Color::DarkGray),
+            )),
+        };
+        let rect = self.gutter(area, area.y, 2);
+        frame.render_widget(Paragraph::new(vec![heading, reading]), rect);
+    }
+
+    fn render_review_banner(&self, frame: &mut Frame, area: Rect, window: KlineWindow) {
+        let stamp = |ms: u64| {
+            let parts = utc_parts(ms);
+            format!(
+                "{}-{:02}-{:02} {:02}:{:02}",
+                parts.year, parts.month, parts.day, parts.hour, parts.minute
+            )
+        };
+        let line = Line::from(vec![
+            Span::styled(
+                " HISTORICAL ",
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!(
+                    " {} → {} UTC · @ or End for live",
+                    stamp(window.start_ms),
+                    stamp(window.end_ms)
+                ),
+                Style::default().fg(Color::Yellow),
+            ),
+        ]);
+        frame.render_widget(Paragraph::new(line).alignment(Alignment::Right), area);
+    }
 
-        let stats_area = vertical[3];
-        self.render_stats(frame, stats_area);
+    fn render_empty_state(&self, frame: &mut Frame, area: Rect) {
+        if area.height == 0 {
+            return;
+        }
+
+        let message = Line::from(Span::styled(
+            format!("No data for {} — invalid symbol?", self.symbol),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ));
+        let para = Paragraph::new(message).alignment(Alignment::Center);
+        frame.render_widget(
+            para,
+            Rect {
+                x: area.x,
+                y: area.y + area.height / 2,
+                width: area.width,
+                height: 1,
+            },
+        );
     }
 
     fn clamp_x(inner: Rect, chart_width: usize, x_pos: usize) -> u16 {
@@ -139,168 +1056,490 @@ impl Chart {
         Some(inner.y.saturating_add(y_offset).min(max_y))
     }
 
-    fn render_candlesticks(&self, frame: &mut Frame, area: Rect) {
-        if self.candles.is_empty() || area.width < 10 || area.height < 5 {
-            return;
+    /// Mirrors a price within `[max_price - price_range, max_price]` when the
+    /// y-axis is inverted, so every mapping below flips together.
+    fn oriented_price(&self, max_price: f64, price_range: f64, value: f64) -> f64 {
+        if self.invert_y {
+            2.0 * max_price - price_range - value
+        } else {
+            value
         }
+    }
 
-        let chart_width = area.width.saturating_sub(13) as usize;
-        let chart_height = area.height.saturating_sub(2);
-        if chart_width == 0 || chart_height == 0 {
-            return;
-        }
+    fn price_to_y(&self, inner: Rect, max_price: f64, price_range: f64, value: f64) -> Option<u16> {
+        let value = self.oriented_price(max_price, price_range, value);
+        Self::map_price_to_y(inner, max_price, price_range, value)
+    }
 
-        let visible_count = self.get_visible_count(chart_width);
-        let start_idx = self
-            .candles
-            .len()
-            .saturating_sub(visible_count + self.offset);
-        let end_idx = self.candles.len().saturating_sub(self.offset);
-        let visible_candles: Vec<&Candle> = self
-            .candles
-            .iter()
-            .skip(start_idx)
-            .take(end_idx - start_idx)
-            .collect();
+    /// The price a plot row stands for, the inverse of `price_to_y`.
+    fn y_to_price(&self, inner: Rect, max_price: f64, price_range: f64, y: u16) -> f64 {
+        let last_row = f64::from(inner.height.saturating_sub(1)).max(1.0);
+        let normalized = f64::from(y.saturating_sub(inner.y)) / last_row;
+        self.oriented_price(max_price, price_range, max_price - normalized * price_range)
+    }
 
-        if visible_candles.is_empty() {
-            return;
+    /// The crosshair readout for `price`: as implied by the row, then snapped
+    /// to the tick size when that lands somewhere else.
+    fn crosshair_price_label(&self, price: f64, row_step: f64) -> String {
+        if self.tick_size <= 0.0 {
+            return format_price(price, step_decimals(row_step));
         }
+        let tick_decimals = step_decimals(self.tick_size);
+        let implied = format_price(price, step_decimals(row_step).max(tick_decimals));
+        if !self.snap_to_tick {
+            return implied;
+        }
+        let snapped = snap_to_tick(price, self.tick_size);
+        let snapped_text = format_price(snapped, tick_decimals);
+        if (snapped - price).abs() < self.tick_size * 1e-6 {
+            snapped_text
+        } else {
+            format!("{} → {}", implied, snapped_text)
+        }
+    }
 
-        let parsed: Vec<(f64, f64, f64, f64, f64)> = visible_candles
-            .iter()
-            .filter_map(|c| {
-                Some((
-                    c.open.parse().ok()?,
-                    c.high.parse().ok()?,
-                    c.low.parse().ok()?,
-                    c.close.parse().ok()?,
-                    c.volume.parse().ok()?,
-                ))
-            })
-            .collect();
-
-        if parsed.is_empty() {
-            return;
+    /// Maps a price to a half-row index (two per terminal row, top first).
+    fn map_price_to_half_y(
+        inner: Rect,
+        max_price: f64,
+        price_range: f64,
+        value: f64,
+    ) -> Option<u32> {
+        if inner.height == 0
+            || !max_price.is_finite()
+            || !price_range.is_finite()
+            || price_range <= 0.0
+            || !value.is_finite()
+        {
+            return None;
         }
 
-        let (min_price, max_price) = parsed.iter().fold(
-            (f64::MAX, f64::MIN),
+        let half_rows = u32::from(inner.height) * 2;
+        let normalized = ((max_price - value) / price_range).clamp(0.0, 1.0);
+        Some((normalized * f64::from(half_rows - 1)).round() as u32)
+    }
+
+    /// Collapses the upper/lower half of a cell into one glyph. The flag is
+    /// set when a body half shares the cell with a wick half, which is then
+    /// painted through the background.
+    fn half_block_glyph(
+        body_upper: bool,
+        body_lower: bool,
+        wick_upper: bool,
+        wick_lower: bool,
+    ) -> Option<(char, bool)> {
+        match (body_upper, body_lower, wick_upper, wick_lower) {
+            (true, true, _, _) => Some(('█', false)),
+            (true, false, _, wick_lower) => Some(('▀', wick_lower)),
+            (false, true, wick_upper, _) => Some(('▄', wick_upper)),
+            (false, false, true, true) => Some(('│', false)),
+            (false, false, true, false) => Some(('╵', false)),
+            (false, false, false, true) => Some(('╷', false)),
+            (false, false, false, false) => None,
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn draw_half_block_candle(
+        frame: &mut Frame,
+        inner: Rect,
+        x: u16,
+        max_price: f64,
+        price_range: f64,
+        (open, high, low, close): (f64, f64, f64, f64),
+        color: Color,
+        theme: &Theme,
+    ) {
+        let map = |value| Self::map_price_to_half_y(inner, max_price, price_range, value);
+        let (Some(high_h), Some(low_h), Some(open_h), Some(close_h)) =
+            (map(high), map(low), map(open), map(close))
+        else {
+            return;
+        };
+
+        let body = open_h.min(close_h)..=open_h.max(close_h);
+        let wick = high_h.min(low_h)..=high_h.max(low_h);
+
+        for row in 0..inner.height {
+            let upper = u32::from(row) * 2;
+            let lower = upper + 1;
+            let Some((glyph, wick_behind)) = Self::half_block_glyph(
+                body.contains(&upper),
+                body.contains(&lower),
+                wick.contains(&upper),
+                wick.contains(&lower),
+            ) else {
+                continue;
+            };
+
+            let cell = &mut frame.buffer_mut()[(x, inner.y + row)];
+            cell.set_char(glyph).set_fg(color);
+            // A half-cell wick can't be drawn as a line, so it is a dimmer
+            // fill of the half the body leaves empty.
+            if wick_behind {
+                cell.set_bg(theme.adapt(scale_color(color, HALF_BLOCK_WICK_SHADE)));
+            }
+        }
+    }
+
+    /// Plots closes as a line on a braille canvas, two dots across and four
+    /// down per cell, with each close centred on its candle's column.
+    #[allow(clippy::too_many_arguments)]
+    fn draw_braille_closes(
+        &self,
+        frame: &mut Frame,
+        inner: Rect,
+        spacing: usize,
+        max_price: f64,
+        price_range: f64,
+        parsed: &[Ohlcv],
+        theme: &Theme,
+    ) {
+        let mut canvas = BrailleCanvas::new(inner.width, inner.height);
+        let dot_rows = canvas.dot_height().saturating_sub(1);
+        let dot_columns = canvas.dot_width().saturating_sub(1);
+        let points: Vec<(u32, u32)> = parsed
+            .iter()
+            .enumerate()
+            .map(|(idx, (_, _, _, close, _))| {
+                let value = self.oriented_price(max_price, price_range, *close);
+                let normalized = ((max_price - value) / price_range).clamp(0.0, 1.0);
+                let x = ((idx * spacing + spacing / 2) * 2) as u32;
+                let y = (normalized * f64::from(dot_rows)).round() as u32;
+                (x.min(dot_columns), y)
+            })
+            .collect();
+
+        match points.as_slice() {
+            [only] => canvas.set(only.0, only.1),
+            _ => {
+                for pair in points.windows(2) {
+                    canvas.line(pair[0], pair[1]);
+                }
+            }
+        }
+
+        let rising = match (parsed.first(), parsed.last()) {
+            (Some(first), Some(last)) => last.3 >= first.0,
+            _ => true,
+        };
+        canvas.render(frame.buffer_mut(), inner, theme.direction(rising));
+    }
+
+    fn render_candlesticks(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        if self.candles.is_empty() || area.width < 10 || area.height < 5 {
+            return;
+        }
+
+        let columns = self.plot_columns(area);
+        let chart_width = columns.width as usize;
+        let chart_height = area.height.saturating_sub(3);
+        if chart_width == 0 || chart_height == 0 {
+            return;
+        }
+
+        let window = self.window(chart_width);
+        let visible_candles = &window.candles;
+
+        if visible_candles.is_empty() {
+            return;
+        }
+
+        let (parsed, trade_counts): (Vec<Ohlcv>, Vec<u64>) = visible_candles
+            .iter()
+            .filter_map(|c| {
+                Some((
+                    (
+                        c.open.parse().ok()?,
+                        c.high.parse().ok()?,
+                        c.low.parse().ok()?,
+                        c.close.parse().ok()?,
+                        c.volume.parse().ok()?,
+                    ),
+                    c.number_of_trades,
+                ))
+            })
+            .unzip();
+
+        if parsed.is_empty() {
+            return;
+        }
+
+        let activity = if self.activity_tint {
+            Self::activity_levels(&trade_counts)
+        } else {
+            None
+        };
+        let recency = if self.highlight_recent {
+            Self::recency_fades(parsed.len(), self.recent_candles)
+        } else {
+            None
+        };
+
+        // Only the buffer's newest candle can still be forming, and only
+        // while it is drawn on its own column from live data.
+        let live_idx = (self.live_candle != LiveCandleStyle::Off
+            && self.review.is_none()
+            && !window.merged
+            && parsed.len() == visible_candles.len()
+            && window.buffer_idx.last() == Some(&(self.candles.len() - 1))
+            && self.candles.back().is_some_and(|c| !c.is_closed))
+        .then(|| parsed.len() - 1);
+
+        let (min_price, max_price) = parsed.iter().fold(
+            (f64::MAX, f64::MIN),
             |(min, max), (_open, high, low, _close, _vol)| (min.min(*low), max.max(*high)),
         );
 
+        let padding = self.vertical_padding(&parsed, max_price - min_price);
+        let (min_price, max_price) = (min_price - padding, max_price + padding);
         let price_range = (max_price - min_price).max(0.0001);
         let candle_count = parsed.len();
         let spacing = chart_width / candle_count.max(1);
 
-        let inner = Rect {
-            x: area.x + 13,
-            y: area.y + 1,
-            width: chart_width as u16,
-            height: chart_height,
-        };
+        let inner = self.plot_area(area);
+        self.plot_rows.set(inner.height);
 
-        for (idx, (open, high, low, close, _vol)) in parsed.iter().enumerate() {
-            let x_pos = (idx * spacing) + (spacing / 2);
-            let x = Self::clamp_x(inner, chart_width, x_pos);
+        if self.heatmap.visible {
+            self.draw_heat_bands(frame, inner, max_price, price_range, theme);
+        }
+        self.grid.draw(frame.buffer_mut(), inner);
+        let squeeze = self
+            .show_squeeze
+            .then(|| self.squeeze_columns(&window.buffer_idx));
+        if let Some(columns) = &squeeze {
+            self.shade_squeeze(frame, inner, spacing, chart_width, columns, theme);
+        }
 
-            let Some(high_y) = Self::map_price_to_y(inner, max_price, price_range, *high) else {
-                continue;
-            };
-            let Some(low_y) = Self::map_price_to_y(inner, max_price, price_range, *low) else {
-                continue;
-            };
-            let Some(open_y) = Self::map_price_to_y(inner, max_price, price_range, *open) else {
-                continue;
-            };
-            let Some(close_y) = Self::map_price_to_y(inner, max_price, price_range, *close) else {
-                continue;
-            };
+        if self.braille {
+            self.draw_braille_closes(
+                frame,
+                inner,
+                spacing,
+                max_price,
+                price_range,
+                &parsed,
+                theme,
+            );
+        } else {
+            for (idx, (open, high, low, close, _vol)) in parsed.iter().enumerate() {
+                let x_pos = (idx * spacing) + (spacing / 2);
+                let x = Self::clamp_x(inner, chart_width, x_pos);
 
-            let is_bullish = close >= open;
-            let color = if is_bullish { Color::Green } else { Color::Red };
+                let mut color = theme.direction(close >= open);
+                if let Some(levels) = &activity {
+                    color = theme.adapt(scale_color(color, levels[idx]));
+                }
+                if let Some(fades) = &recency {
+                    color = theme.adapt(blend_color(color, Color::DarkGray, fades[idx]));
+                }
+                if live_idx == Some(idx) && self.live_candle == LiveCandleStyle::Bright {
+                    color = theme.adapt(blend_color(color, Color::White, 0.45));
+                }
 
-            let body_top = open_y.min(close_y);
-            let body_bottom = open_y.max(close_y);
+                if self.half_block {
+                    Self::draw_half_block_candle(
+                        frame,
+                        inner,
+                        x,
+                        max_price,
+                        price_range,
+                        (
+                            self.oriented_price(max_price, price_range, *open),
+                            self.oriented_price(max_price, price_range, *high),
+                            self.oriented_price(max_price, price_range, *low),
+                            self.oriented_price(max_price, price_range, *close),
+                        ),
+                        color,
+                        theme,
+                    );
+                    continue;
+                }
 
-            if high_y < low_y {
-                for y in high_y..=low_y {
-                    if y >= inner.y && y < inner.y + inner.height {
-                        let cell = &mut frame.buffer_mut()[(x, y)];
-                        cell.set_char('│').set_fg(color);
+                let Some(high_y) = self.price_to_y(inner, max_price, price_range, *high) else {
+                    continue;
+                };
+                let Some(low_y) = self.price_to_y(inner, max_price, price_range, *low) else {
+                    continue;
+                };
+                let Some(open_y) = self.price_to_y(inner, max_price, price_range, *open) else {
+                    continue;
+                };
+                let Some(close_y) = self.price_to_y(inner, max_price, price_range, *close) else {
+                    continue;
+                };
+
+                let body_top = open_y.min(close_y);
+                let body_bottom = open_y.max(close_y);
+
+                let (wick_top, wick_bottom) = (high_y.min(low_y), high_y.max(low_y));
+                if wick_top < wick_bottom {
+                    for y in wick_top..=wick_bottom {
+                        if y >= inner.y && y < inner.y + inner.height {
+                            let cell = &mut frame.buffer_mut()[(x, y)];
+                            cell.set_char('│').set_fg(color);
+                        }
                     }
                 }
-            }
 
-            if body_top <= body_bottom {
+                let is_flat = body_top == body_bottom;
+                let hollow = self.candle_style == CandleStyle::Hollow && close > open;
+                let columns = self.body_columns(inner, x, spacing);
+                let single_column = columns.start() == columns.end();
                 for y in body_top..=body_bottom {
-                    if y >= inner.y && y < inner.y + inner.height {
-                        let cell = &mut frame.buffer_mut()[(x, y)];
-                        cell.set_char('█').set_fg(color);
+                    if y < inner.y || y >= inner.y + inner.height {
+                        continue;
+                    }
+                    for body_x in columns.clone() {
+                        let glyph = match (is_flat, body_x == x) {
+                            (true, true) => {
+                                Self::doji_glyph(wick_top < body_top, wick_bottom > body_bottom)
+                            }
+                            (true, false) => '─',
+                            (false, _) if hollow => Self::hollow_glyph(
+                                (y == body_top, y == body_bottom),
+                                (!single_column).then(|| {
+                                    if body_x == *columns.start() {
+                                        std::cmp::Ordering::Less
+                                    } else if body_x == *columns.end() {
+                                        std::cmp::Ordering::Greater
+                                    } else {
+                                        std::cmp::Ordering::Equal
+                                    }
+                                }),
+                                (
+                                    body_x == x && wick_top < body_top,
+                                    body_x == x && wick_bottom > body_bottom,
+                                ),
+                            ),
+                            (false, _) => '█',
+                        };
+                        let cell = &mut frame.buffer_mut()[(body_x, y)];
+                        cell.set_char(glyph).set_fg(color);
                     }
                 }
             }
         }
 
-        let full_candles: Vec<Candle> = self.candles.iter().cloned().collect();
+        if let (Some(idx), LiveCandleStyle::Marker) = (live_idx, self.live_candle) {
+            let (_, high, low, _, _) = parsed[idx];
+            let x = Self::clamp_x(inner, chart_width, idx * spacing + spacing / 2);
+            let rows: Vec<u16> = [high, low]
+                .into_iter()
+                .filter_map(|price| self.price_to_y(inner, max_price, price_range, price))
+                .collect();
+            // Above the candle, or below it when it reaches the top row.
+            let marker = match (rows.iter().min(), rows.iter().max()) {
+                (Some(&top), _) if top > inner.y => Some((top - 1, '▾')),
+                (_, Some(&bottom)) if bottom + 1 < inner.y + inner.height => {
+                    Some((bottom + 1, '▴'))
+                }
+                _ => None,
+            };
+            if let Some((y, glyph)) = marker {
+                frame.buffer_mut()[(x, y)]
+                    .set_char(glyph)
+                    .set_fg(Color::Yellow);
+            }
+        }
+
+        let mut value_labels = Vec::new();
         if self.show_sma {
-            let sma_values = calculate_sma(&full_candles, 20);
-            self.draw_sma_overlay(
+            let sma_values = self.indicators.sma_values();
+            let latest = self.draw_sma_overlay(
                 frame,
                 inner,
-                start_idx,
-                candle_count,
+                &window.buffer_idx,
                 spacing,
                 chart_width,
                 max_price,
                 price_range,
                 &sma_values,
             );
+            if let Some((y, value)) = latest {
+                let text = theme.format_price(value);
+                value_labels.push((text, y, Color::Cyan));
+            }
         }
 
         if self.show_rsi {
-            let rsi_values = calculate_rsi(&full_candles, 14);
-            self.draw_rsi_overlay(
+            let rsi_values = self.indicators.rsi_values();
+            let latest = self.draw_rsi_overlay(
                 frame,
                 inner,
-                start_idx,
-                candle_count,
+                &window.buffer_idx,
                 spacing,
                 chart_width,
                 &rsi_values,
             );
+            if let Some((y, value)) = latest {
+                value_labels.push((format!("{value:.1}"), y, Color::Magenta));
+            }
+        }
+        self.draw_overlay_legend(frame, inner);
+        Self::draw_overlay_values(frame, inner, &value_labels);
+        if let Some(columns) = &squeeze {
+            Self::draw_squeeze_releases(frame, inner, spacing, chart_width, columns);
+        }
+
+        if parsed.len() == visible_candles.len() && !window.merged {
+            let open_times: Vec<u64> = visible_candles.iter().map(|c| c.open_time).collect();
+            self.draw_notes_and_crosshair(
+                frame,
+                inner,
+                &open_times,
+                spacing,
+                chart_width,
+                max_price,
+                price_range,
+                theme,
+            );
+            if self.crosshair.is_some() {
+                self.draw_crosshair_price(frame, inner, max_price, price_range);
+            }
         }
 
-        let label_count = 5.min(chart_height as usize / 2);
-        for i in 0..=label_count {
-            let y = inner.y
-                + ((i as u16) * (chart_height.saturating_sub(1)) / label_count.max(1) as u16);
-            let price = max_price - (i as f64 / label_count.max(1) as f64) * price_range;
-            let label = format!("{:>11.2}", price);
+        if self.round_price_labels {
+            self.draw_price_labels(frame, area, inner, max_price, price_range);
+        } else {
+            self.draw_even_price_labels(frame, area, inner, max_price, price_range, theme);
+        }
 
-            for (j, ch) in label.chars().enumerate() {
-                let x_pos = area.x + (j as u16);
-                if x_pos < area.x + 13 && y < area.y + area.height {
-                    let cell = &mut frame.buffer_mut()[(x_pos, y)];
-                    cell.set_char(ch).set_fg(Color::Gray);
-                }
+        if parsed.len() == visible_candles.len() {
+            let times: Vec<u64> = visible_candles
+                .iter()
+                .map(|c| self.time_basis.timestamp(c))
+                .collect();
+            let axis_y = area.y + area.height.saturating_sub(2);
+            self.render_time_axis(frame, inner, axis_y, &times, spacing, chart_width);
+            if !window.merged {
+                let open_times: Vec<u64> = visible_candles.iter().map(|c| c.open_time).collect();
+                self.draw_crosshair_time(frame, inner, axis_y, &open_times, spacing, chart_width);
             }
         }
 
         let latest = parsed.last().unwrap();
         let latest_price = latest.3;
-        let change = latest_price - parsed.first().unwrap().0;
-        let change_pct = (change / parsed.first().unwrap().0) * 100.0;
-
-        let price_label = format!("{:.2}", latest_price);
-        let change_label = format!("{:+.2} ({:+.2}%)", change, change_pct);
-        let change_color = if change >= 0.0 {
-            Color::Green
+        let (change, change_pct) = if parsed.len() < 2 {
+            (0.0, 0.0)
         } else {
-            Color::Red
+            let window_open = parsed.first().unwrap().0;
+            (
+                latest_price - window_open,
+                Self::change_pct(window_open, latest_price),
+            )
         };
 
-        let price_text = Line::from(vec![
+        let decimals = theme.price_decimals(latest_price);
+        let price_label = format_price(latest_price, decimals);
+        let change_label = format!(
+            "{} ({:+.2}%)",
+            format_signed_price(change, decimals),
+            change_pct
+        );
+        let change_color = theme.direction(change >= 0.0);
+
+        let mut spans = vec![
             Span::styled("Price: ", Style::default().fg(Color::Gray)),
             Span::styled(
                 price_label,
@@ -310,41 +1549,583 @@ impl Chart {
             ),
             Span::raw("  "),
             Span::styled(change_label, Style::default().fg(change_color)),
-        ]);
+        ];
+        if let Some(day_open) = self.day_open() {
+            let day_pct = Self::change_pct(day_open, latest_price);
+            spans.push(Span::styled("  Day: ", Style::default().fg(Color::Gray)));
+            spans.push(Span::styled(
+                format!("{:+.2}%", day_pct),
+                Style::default().fg(theme.direction(day_pct >= 0.0)),
+            ));
+        }
+        let bars: Vec<(f64, f64, f64)> = parsed
+            .iter()
+            .map(|(_open, high, low, close, _vol)| (*high, *low, *close))
+            .collect();
+        if let Some(range) = percent_range(&bars, ATR_PERIOD, self.indicator_basis) {
+            // Brighter while volatility runs above its own recent average.
+            let color = if range.value > range.average {
+                Color::Yellow
+            } else {
+                Color::Gray
+            };
+            spans.push(Span::styled("  Range: ", Style::default().fg(Color::Gray)));
+            spans.push(Span::styled(
+                format!("{:.2}% p{:.0}", range.value, range.percentile),
+                Style::default().fg(color),
+            ));
+        }
+        match squeeze.as_ref().and_then(|columns| columns.last()) {
+            Some((_, true)) => spans.push(Span::styled(
+                "  Squeeze fired",
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )),
+            Some((true, _)) => {
+                spans.push(Span::styled("  Squeeze", Style::default().fg(Color::Gray)))
+            }
+            _ => {}
+        }
+        spans.push(Span::styled(
+            format!("  {} candles", window.span),
+            Style::default().fg(Color::Gray),
+        ));
 
-        let price_para = Paragraph::new(price_text);
+        let price_para = Paragraph::new(Line::from(spans));
         frame.render_widget(
             price_para,
             Rect {
-                x: area.x + 13,
                 y: area.y.saturating_add(area.height.saturating_sub(1)),
-                width: area.width.saturating_sub(13),
                 height: 1,
+                ..self.plot_columns(area)
             },
         );
     }
 
-    fn render_volume(&self, frame: &mut Frame, area: Rect) {
-        if self.candles.is_empty() || area.width < 10 || area.height < 2 {
-            return;
+    /// Labels the price axis at round prices (multiples of a 1/2/5 step),
+    /// keeping at least one blank row between labels.
+    fn draw_price_labels(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        inner: Rect,
+        max_price: f64,
+        price_range: f64,
+    ) {
+        let max_ticks = (inner.height as usize / 2).clamp(1, 8);
+        let (ticks, decimals) = price_ticks(max_price - price_range, max_price, max_ticks);
+        let mut rows: Vec<(u16, f64)> = ticks
+            .into_iter()
+            .filter_map(|price| {
+                Some((
+                    self.price_to_y(inner, max_price, price_range, price)?,
+                    price,
+                ))
+            })
+            .collect();
+        rows.sort_by_key(|(y, _)| *y);
+
+        let mut last_y: Option<u16> = None;
+        for (y, price) in rows {
+            if last_y.is_some_and(|prev| y < prev + 2) {
+                continue;
+            }
+            self.draw_price_label(frame, area, y, &format_price(price, decimals));
+            last_y = Some(y);
         }
+    }
 
-        let chart_width = area.width.saturating_sub(13) as usize;
-        if chart_width == 0 {
+    /// Labels evenly spaced rows with the price at each.
+    fn draw_even_price_labels(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        inner: Rect,
+        max_price: f64,
+        price_range: f64,
+        theme: &Theme,
+    ) {
+        let decimals = theme.price_decimals(max_price);
+        let chart_height = inner.height;
+        let label_count = 5.min(chart_height as usize / 2);
+        for i in 0..=label_count {
+            let y = inner.y
+                + ((i as u16) * (chart_height.saturating_sub(1)) / label_count.max(1) as u16);
+            let fraction = i as f64 / label_count.max(1) as f64;
+            let price = if self.invert_y {
+                max_price - price_range + fraction * price_range
+            } else {
+                max_price - fraction * price_range
+            };
+            self.draw_price_label(frame, area, y, &format_price(price, decimals));
+        }
+    }
+
+    /// Writes a price label beside the plot: right-aligned against it in the
+    /// left margin, or left-aligned after it in the right one.
+    fn draw_price_label(&self, frame: &mut Frame, area: Rect, y: u16, text: &str) {
+        let (label, start) = match self.price_axis {
+            PriceAxis::Left => (format!("{:>11}", text), area.x),
+            PriceAxis::Right => (
+                format!(" {}", text),
+                area.x + area.width.saturating_sub(AXIS_WIDTH),
+            ),
+            PriceAxis::Hidden => return,
+        };
+        let end = start + AXIS_WIDTH.min(area.width);
+        for (j, ch) in label.chars().enumerate() {
+            let x_pos = start + (j as u16);
+            if x_pos < end && y < area.y + area.height {
+                let cell = &mut frame.buffer_mut()[(x_pos, y)];
+                cell.set_char(ch).set_fg(Color::Gray);
+            }
+        }
+    }
+
+    /// Maps each candle's trade count onto a brightness ramp relative to the
+    /// quietest and busiest candle in view. `None` when every count is equal,
+    /// so the base colors are used unchanged.
+    fn activity_levels(trade_counts: &[u64]) -> Option<Vec<f64>> {
+        let min = *trade_counts.iter().min()?;
+        let max = *trade_counts.iter().max()?;
+        if min == max {
+            return None;
+        }
+        let span = (max - min) as f64;
+        Some(
+            trade_counts
+                .iter()
+                .map(|count| {
+                    let share = (count - min) as f64 / span;
+                    MIN_ACTIVITY_BRIGHTNESS + (1.0 - MIN_ACTIVITY_BRIGHTNESS) * share
+                })
+                .collect(),
+        )
+    }
+
+    /// How far each of `count` candles is faded toward gray: none for the
+    /// newest `recent`, then rising linearly to `MAX_RECENCY_FADE` at the
+    /// left edge. `None` when every candle counts as recent.
+    fn recency_fades(count: usize, recent: usize) -> Option<Vec<f64>> {
+        let older = count.checked_sub(recent).filter(|older| *older > 0)?;
+        Some(
+            (0..count)
+                .map(|idx| {
+                    let age = older.saturating_sub(idx) as f64;
+                    MAX_RECENCY_FADE * age / older as f64
+                })
+                .collect(),
+        )
+    }
+
+    /// Shades rows behind the candles by recent traded volume at that price,
+    /// relative to the busiest row in view.
+    fn draw_heat_bands(
+        &self,
+        frame: &mut Frame,
+        inner: Rect,
+        max_price: f64,
+        price_range: f64,
+        theme: &Theme,
+    ) {
+        let min_price = max_price - price_range;
+        let mut rows = vec![0.0; inner.height as usize];
+        for (price, weight) in self.heatmap.levels(Instant::now()) {
+            if price < min_price || price > max_price {
+                continue;
+            }
+            if let Some(y) = self.price_to_y(inner, max_price, price_range, price) {
+                rows[(y - inner.y) as usize] += weight;
+            }
+        }
+
+        let peak = rows.iter().copied().fold(0.0, f64::max);
+        if peak <= 0.0 {
             return;
         }
-        let visible_count = self.get_visible_count(chart_width);
-        let start_idx = self
-            .candles
-            .len()
-            .saturating_sub(visible_count + self.offset);
-        let end_idx = self.candles.len().saturating_sub(self.offset);
-        let visible_candles: Vec<&Candle> = self
+
+        for (row, weight) in rows.iter().enumerate() {
+            let intensity = weight / peak;
+            if intensity < 0.1 {
+                continue;
+            }
+            let bg = theme.adapt(scale_color(HEAT_COLOR, 0.1 + 0.3 * intensity));
+            let y = inner.y + row as u16;
+            for x in inner.x..inner.x + inner.width {
+                frame.buffer_mut()[(x, y)].set_bg(bg);
+            }
+        }
+    }
+
+    fn vertical_padding(&self, parsed: &[Ohlcv], raw_range: f64) -> f64 {
+        if self.padding_factor <= 0.0 {
+            return 0.0;
+        }
+
+        let bars: Vec<(f64, f64, f64)> = parsed
+            .iter()
+            .map(|(_open, high, low, close, _vol)| (*high, *low, *close))
+            .collect();
+        match calculate_atr(&bars, ATR_PERIOD).last().copied().flatten() {
+            Some(atr) if atr.is_finite() && atr > 0.0 => atr * self.padding_factor,
+            _ => raw_range * FALLBACK_PADDING_PCT,
+        }
+    }
+
+    /// `(in squeeze, released)` for each window column, from the buffered
+    /// candles behind it: in squeeze when its newest candle is, released
+    /// when any of its candles ended one.
+    fn squeeze_columns(&self, buffer_idx: &[usize]) -> Vec<(bool, bool)> {
+        let bars: Vec<(f64, f64, f64)> = self
             .candles
             .iter()
-            .skip(start_idx)
-            .take(end_idx - start_idx)
+            .map(|c| {
+                let parse = |value: &str| value.parse::<f64>().unwrap_or(0.0);
+                (parse(&c.high), parse(&c.low), parse(&c.close))
+            })
             .collect();
+        let closes: Vec<f64> = bars.iter().map(|bar| bar.2).collect();
+        let squeeze = is_squeeze(
+            &calculate_bollinger(&closes, SQUEEZE_PERIOD, SQUEEZE_BOLLINGER_STD_DEVS),
+            &calculate_keltner(&bars, SQUEEZE_PERIOD, SQUEEZE_KELTNER_ATR_MULTIPLE),
+        );
+        let releases = squeeze_releases(&squeeze);
+
+        let mut group_start = buffer_idx.first().copied().unwrap_or(0);
+        buffer_idx
+            .iter()
+            .map(|&idx| {
+                let released = releases[group_start..=idx].iter().any(|r| *r);
+                group_start = idx + 1;
+                (squeeze[idx], released)
+            })
+            .collect()
+    }
+
+    /// Dims the background behind candles in a squeeze.
+    fn shade_squeeze(
+        &self,
+        frame: &mut Frame,
+        inner: Rect,
+        spacing: usize,
+        chart_width: usize,
+        columns: &[(bool, bool)],
+        theme: &Theme,
+    ) {
+        let bg = theme.adapt(SQUEEZE_SHADE);
+        for (idx, (in_squeeze, _)) in columns.iter().enumerate() {
+            if !in_squeeze {
+                continue;
+            }
+            let from = Self::clamp_x(inner, chart_width, idx * spacing);
+            let to = Self::clamp_x(inner, chart_width, (idx + 1) * spacing.max(1) - 1);
+            for x in from..=to {
+                for y in inner.y..inner.y + inner.height {
+                    frame.buffer_mut()[(x, y)].set_bg(bg);
+                }
+            }
+        }
+    }
+
+    /// Marks each squeeze release along the bottom row of the plot.
+    fn draw_squeeze_releases(
+        frame: &mut Frame,
+        inner: Rect,
+        spacing: usize,
+        chart_width: usize,
+        columns: &[(bool, bool)],
+    ) {
+        if inner.height == 0 {
+            return;
+        }
+        let y = inner.y + inner.height - 1;
+        let style = Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD);
+        for (idx, (_, released)) in columns.iter().enumerate() {
+            if *released {
+                let x = Self::clamp_x(inner, chart_width, idx * spacing + spacing / 2);
+                frame.buffer_mut()[(x, y)].set_char('▲').set_style(style);
+            }
+        }
+    }
+
+    fn change_pct(from: f64, to: f64) -> f64 {
+        if from == 0.0 || !from.is_finite() || !to.is_finite() {
+            return 0.0;
+        }
+        (to - from) / from * 100.0
+    }
+
+    /// Open of the first loaded candle in the current UTC day, i.e. the
+    /// session open. `None` when no loaded candle starts today (weekly or
+    /// monthly bars), since the day open isn't known then.
+    fn day_open(&self) -> Option<f64> {
+        const DAY_MS: u64 = 86_400_000;
+        let latest = self.candles.back()?;
+        let day_start = latest.open_time - latest.open_time % DAY_MS;
+        self.candles
+            .iter()
+            .find(|c| c.open_time >= day_start)?
+            .open
+            .parse()
+            .ok()
+    }
+
+    /// Draws note markers, the crosshair column, and the text of the note
+    /// under the crosshair along the top row of the plot.
+    #[allow(clippy::too_many_arguments)]
+    fn draw_notes_and_crosshair(
+        &self,
+        frame: &mut Frame,
+        inner: Rect,
+        open_times: &[u64],
+        spacing: usize,
+        chart_width: usize,
+        max_price: f64,
+        price_range: f64,
+        theme: &Theme,
+    ) {
+        let column_for = |open_time: u64| {
+            let idx = open_times.iter().position(|t| *t == open_time)?;
+            Some(Self::clamp_x(
+                inner,
+                chart_width,
+                idx * spacing + spacing / 2,
+            ))
+        };
+        let min_price = max_price - price_range;
+        let crosshair_time = self.crosshair_candle().map(|c| c.open_time);
+
+        if let Some(x) = crosshair_time.and_then(column_for) {
+            for y in inner.y..inner.y + inner.height {
+                let cell = &mut frame.buffer_mut()[(x, y)];
+                if cell.symbol() == " " {
+                    cell.set_char('┆').set_fg(Color::DarkGray);
+                }
+            }
+        }
+
+        for note in self.symbol_notes() {
+            if note.price < min_price || note.price > max_price {
+                continue;
+            }
+            let Some(x) = column_for(note.open_time) else {
+                continue;
+            };
+            let Some(y) = self.price_to_y(inner, max_price, price_range, note.price) else {
+                continue;
+            };
+            let marker_x = (x + 1).min(inner.x + inner.width.saturating_sub(1));
+            let cell = &mut frame.buffer_mut()[(marker_x, y)];
+            cell.set_char('◆').set_fg(Color::Yellow);
+        }
+
+        let Some(note) =
+            crosshair_time.and_then(|t| self.symbol_notes().iter().find(|n| n.open_time == t))
+        else {
+            return;
+        };
+        let Some(x) = column_for(note.open_time) else {
+            return;
+        };
+
+        let label = format!(" {} @ {} ", note.text, theme.format_price(note.price));
+        let width = (label.chars().count() as u16).min(inner.width);
+        let right_edge = inner.x + inner.width;
+        let start = (x + 2).min(right_edge.saturating_sub(width)).max(inner.x);
+        let para = Paragraph::new(Span::styled(
+            label,
+            Style::default().fg(Color::Black).bg(Color::Yellow),
+        ));
+        frame.render_widget(
+            para,
+            Rect {
+                x: start,
+                y: inner.y,
+                width,
+                height: 1,
+            },
+        );
+    }
+
+    /// Draws the crosshair row with the price it stands for at its right end.
+    fn draw_crosshair_price(
+        &self,
+        frame: &mut Frame,
+        inner: Rect,
+        max_price: f64,
+        price_range: f64,
+    ) {
+        if inner.height == 0 || inner.width == 0 {
+            return;
+        }
+        let y = inner.y + self.crosshair_row.min(inner.height - 1);
+        for x in inner.x..inner.x + inner.width {
+            let cell = &mut frame.buffer_mut()[(x, y)];
+            if cell.symbol() == " " {
+                cell.set_char('┄').set_fg(Color::DarkGray);
+            }
+        }
+
+        let price = self.y_to_price(inner, max_price, price_range, y);
+        let row_step = price_range / f64::from(inner.height.saturating_sub(1).max(1));
+        let label = format!(" {} ", self.crosshair_price_label(price, row_step));
+        let width = (label.chars().count() as u16).min(inner.width);
+        frame.render_widget(
+            Paragraph::new(Span::styled(
+                label,
+                Style::default().fg(Color::Black).bg(Color::White),
+            )),
+            Rect {
+                x: inner.x + inner.width - width,
+                y,
+                width,
+                height: 1,
+            },
+        );
+    }
+
+    /// Columns of a body centred on `x`: the preferred width, narrowed to
+    /// leave the gap when candles are packed tighter than that.
+    fn body_columns(&self, inner: Rect, x: u16, spacing: usize) -> RangeInclusive<u16> {
+        let max_x = inner.x + inner.width.saturating_sub(1);
+        let preferred = self.preferred_body_width();
+        let room = spacing.saturating_sub(usize::from(self.candle_gap));
+        let mut width = preferred.min(room).max(1) as u16;
+        // Keep an odd body odd so the wick stays centred.
+        if preferred % 2 == 1 && width.is_multiple_of(2) {
+            width -= 1;
+        }
+        let left = (width - 1) / 2;
+        x.saturating_sub(left).max(inner.x)..=(x + width - 1 - left).min(max_x)
+    }
+
+    /// Outline cell of a hollow body. `edge` is whether the cell is on the
+    /// body's top and bottom row; `column` places it on the left edge, inside,
+    /// or on the right edge of a wider body, or is `None` for a single-column
+    /// one. A wick meeting the outline joins it at the centre.
+    fn hollow_glyph(
+        (top, bottom): (bool, bool),
+        column: Option<std::cmp::Ordering>,
+        (wick_above, wick_below): (bool, bool),
+    ) -> char {
+        use std::cmp::Ordering::{Equal, Greater, Less};
+        let Some(column) = column else {
+            return '║';
+        };
+        match (top, bottom, column) {
+            (true, _, Less) => '┌',
+            (true, _, Greater) => '┐',
+            (true, _, Equal) if wick_above => '┴',
+            (true, _, Equal) => '─',
+            (_, true, Less) => '└',
+            (_, true, Greater) => '┘',
+            (_, true, Equal) if wick_below => '┬',
+            (_, true, Equal) => '─',
+            (_, _, Equal) => ' ',
+            _ => '│',
+        }
+    }
+
+    /// Marker for a body that collapsed to one row, so a doji stays distinct
+    /// from the wick passing through it.
+    fn doji_glyph(wick_above: bool, wick_below: bool) -> char {
+        match (wick_above, wick_below) {
+            (true, true) => '┼',
+            (true, false) => '┴',
+            (false, true) => '┬',
+            (false, false) => '─',
+        }
+    }
+
+    fn render_time_axis(
+        &self,
+        frame: &mut Frame,
+        inner: Rect,
+        y: u16,
+        times: &[u64],
+        spacing: usize,
+        chart_width: usize,
+    ) {
+        let min_gap = 2;
+        let right_edge = inner.x.saturating_add(inner.width);
+        let mut next_free = inner.x;
+
+        for (idx, time) in times.iter().enumerate() {
+            let x_pos = (idx * spacing) + (spacing / 2);
+            let center = Self::clamp_x(inner, chart_width, x_pos);
+            let label = format_time_label(*time, &self.interval);
+            let width = label.chars().count() as u16;
+            let start = center.saturating_sub(width / 2).max(inner.x);
+
+            if start < next_free || start.saturating_add(width) > right_edge {
+                continue;
+            }
+
+            for (j, ch) in label.chars().enumerate() {
+                let cell = &mut frame.buffer_mut()[(start + j as u16, y)];
+                cell.set_char(ch).set_fg(Color::DarkGray);
+            }
+            next_free = start + width + min_gap;
+        }
+    }
+
+    /// Labels the crosshair column on the time axis with its candle's time,
+    /// on top of whatever axis label was there.
+    fn draw_crosshair_time(
+        &self,
+        frame: &mut Frame,
+        inner: Rect,
+        y: u16,
+        open_times: &[u64],
+        spacing: usize,
+        chart_width: usize,
+    ) {
+        let Some(candle) = self.crosshair_candle() else {
+            return;
+        };
+        let Some(idx) = open_times.iter().position(|t| *t == candle.open_time) else {
+            return;
+        };
+        let center = Self::clamp_x(inner, chart_width, idx * spacing + spacing / 2);
+        let label = format!(
+            " {} ",
+            format_time_label(self.time_basis.timestamp(candle), &self.interval)
+        );
+        let width = (label.chars().count() as u16).min(inner.width);
+        let right_edge = inner.x + inner.width;
+        let start = center
+            .saturating_sub(width / 2)
+            .min(right_edge.saturating_sub(width))
+            .max(inner.x);
+        frame.render_widget(
+            Paragraph::new(Span::styled(
+                label,
+                Style::default().fg(Color::Black).bg(Color::Gray),
+            )),
+            Rect {
+                x: start,
+                y,
+                width,
+                height: 1,
+            },
+        );
+    }
+
+    fn render_volume(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        if self.candles.is_empty() || area.width < 10 || area.height < 2 {
+            return;
+        }
+
+        let columns = self.plot_columns(area);
+        let chart_width = columns.width as usize;
+        if chart_width == 0 {
+            return;
+        }
+        let window = self.window(chart_width);
+        let visible_candles = &window.candles;
 
         if visible_candles.is_empty() {
             return;
@@ -371,10 +2152,8 @@ impl Chart {
         let spacing = chart_width / volumes.len().max(1);
 
         let inner = Rect {
-            x: area.x + 13,
-            y: area.y,
-            width: chart_width as u16,
             height: chart_height,
+            ..columns
         };
 
         for (idx, volume) in volumes.iter().enumerate() {
@@ -393,21 +2172,38 @@ impl Chart {
             }
         }
 
-        let volume_label = format!("Vol: {:.2}", max_volume);
+        let volume_label = format!("Vol: {}", format_volume(max_volume, 2, theme.volume_format));
         let label_text = Line::from(Span::styled(volume_label, Style::default().fg(Color::Gray)));
         let label_para = Paragraph::new(label_text);
-        frame.render_widget(
-            label_para,
-            Rect {
-                x: area.x,
-                y: area.y,
-                width: 12,
-                height: 1,
-            },
-        );
+        frame.render_widget(label_para, self.gutter(area, area.y, 1));
+
+        let (buy, sell) = visible_candles
+            .iter()
+            .map(|candle| taker_split(candle))
+            .fold((0.0, 0.0), |(buy, sell), (b, s)| (buy + b, sell + s));
+        let total = buy + sell;
+        if total > 0.0 && area.height >= 3 {
+            let buy_pct = buy / total * 100.0;
+            for (row, (label, pct, color)) in [
+                ("Buy ", buy_pct, theme.up),
+                ("Sell", 100.0 - buy_pct, theme.down),
+            ]
+            .into_iter()
+            .enumerate()
+            {
+                let line = Line::from(vec![
+                    Span::styled(format!("{label} "), Style::default().fg(Color::Gray)),
+                    Span::styled(format!("{pct:>5.1}%"), Style::default().fg(color)),
+                ]);
+                frame.render_widget(
+                    Paragraph::new(line),
+                    self.gutter(area, area.y + 1 + row as u16, 1),
+                );
+            }
+        }
     }
 
-    fn render_stats(&self, frame: &mut Frame, area: Rect) {
+    fn render_stats(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
         if self.candles.is_empty() {
             return;
         }
@@ -419,21 +2215,18 @@ impl Chart {
         let close: f64 = latest.close.parse().unwrap_or(0.0);
         let volume: f64 = latest.volume.parse().unwrap_or(0.0);
 
+        let decimals = theme.price_decimals(close);
         let change = close - open;
         let change_pct = if open > 0.0 {
             (change / open) * 100.0
         } else {
             0.0
         };
-        let change_color = if change >= 0.0 {
-            Color::Green
-        } else {
-            Color::Red
-        };
+        let change_color = theme.direction(change >= 0.0);
 
         let (latest_sma, latest_rsi) = self.latest_indicator_values();
         let sma_value_text = latest_sma
-            .map(|value| format!("{:.2}", value))
+            .map(|value| theme.format_price(value))
             .unwrap_or_else(|| "n/a".to_string());
         let rsi_value_text = latest_rsi
             .map(|value| format!("{:.1}", value))
@@ -441,16 +2234,28 @@ impl Chart {
 
         let stats_text = Line::from(vec![
             Span::styled("O: ", Style::default().fg(Color::Gray)),
-            Span::styled(format!("{:.2}  ", open), Style::default().fg(Color::White)),
+            Span::styled(
+                format!("{}  ", format_price(open, decimals)),
+                Style::default().fg(Color::White),
+            ),
             Span::styled("H: ", Style::default().fg(Color::Gray)),
-            Span::styled(format!("{:.2}  ", high), Style::default().fg(Color::Green)),
+            Span::styled(
+                format!("{}  ", format_price(high, decimals)),
+                Style::default().fg(theme.up),
+            ),
             Span::styled("L: ", Style::default().fg(Color::Gray)),
-            Span::styled(format!("{:.2}  ", low), Style::default().fg(Color::Red)),
+            Span::styled(
+                format!("{}  ", format_price(low, decimals)),
+                Style::default().fg(theme.down),
+            ),
             Span::styled("C: ", Style::default().fg(Color::Gray)),
-            Span::styled(format!("{:.2}  ", close), Style::default().fg(Color::White)),
+            Span::styled(
+                format!("{}  ", format_price(close, decimals)),
+                Style::default().fg(Color::White),
+            ),
             Span::styled("Vol: ", Style::default().fg(Color::Gray)),
             Span::styled(
-                format!("{:.2}  ", volume),
+                format!("{}  ", format_volume(volume, 2, theme.volume_format)),
                 Style::default().fg(Color::Yellow),
             ),
             Span::styled("Chg: ", Style::default().fg(Color::Gray)),
@@ -460,7 +2265,10 @@ impl Chart {
                     .fg(change_color)
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::styled("  SMA20: ", Style::default().fg(Color::Gray)),
+            Span::styled(
+                format!("  SMA{}: ", self.indicators.sma_period()),
+                Style::default().fg(Color::Gray),
+            ),
             Span::styled(
                 if self.show_sma {
                     sma_value_text
@@ -473,7 +2281,10 @@ impl Chart {
                     Color::DarkGray
                 }),
             ),
-            Span::styled("  RSI14: ", Style::default().fg(Color::Gray)),
+            Span::styled(
+                format!("  RSI{}: ", self.indicators.rsi_period()),
+                Style::default().fg(Color::Gray),
+            ),
             Span::styled(
                 if self.show_rsi {
                     rsi_value_text
@@ -488,9 +2299,28 @@ impl Chart {
             ),
         ]);
 
-        let stats_block = Block::default()
-            .borders(Borders::ALL)
+        let mut stats_block = Block::default()
+            .borders(theme.borders())
             .border_style(Style::default().fg(Color::Blue));
+        if let Some((delta, pct)) = live_build(latest) {
+            let arrow = match delta.partial_cmp(&0.0) {
+                Some(std::cmp::Ordering::Greater) => "▲",
+                Some(std::cmp::Ordering::Less) => "▼",
+                _ => "•",
+            };
+            stats_block = stats_block.title(
+                Line::styled(
+                    format!(
+                        " Live {arrow} {} ({pct:+.2}%) ",
+                        format_signed_price(delta, decimals)
+                    ),
+                    Style::default()
+                        .fg(theme.direction(delta >= 0.0))
+                        .add_modifier(Modifier::BOLD),
+                )
+                .right_aligned(),
+            );
+        }
         let stats_para = Paragraph::new(stats_text).block(stats_block);
         frame.render_widget(stats_para, area);
     }
@@ -500,31 +2330,38 @@ impl Chart {
         &self,
         frame: &mut Frame,
         inner: Rect,
-        start_idx: usize,
-        candle_count: usize,
+        buffer_idx: &[usize],
         spacing: usize,
         chart_width: usize,
         max_price: f64,
         price_range: f64,
         sma_values: &[Option<f64>],
-    ) {
-        for idx in 0..candle_count {
-            let global_idx = start_idx + idx;
-            let Some(Some(sma_value)) = sma_values.get(global_idx) else {
-                continue;
-            };
-
-            let x_pos = (idx * spacing) + (spacing / 2);
-            let x = Self::clamp_x(inner, chart_width, x_pos);
-            let Some(y) = Self::map_price_to_y(inner, max_price, price_range, *sma_value) else {
-                continue;
-            };
-
-            if y >= inner.y && y < inner.y + inner.height {
-                let cell = &mut frame.buffer_mut()[(x, y)];
-                cell.set_char('•').set_fg(Color::Cyan);
-            }
-        }
+    ) -> Option<(u16, f64)> {
+        let points: Vec<Option<(u16, u16, f64)>> = buffer_idx
+            .iter()
+            .enumerate()
+            .map(|(idx, &global_idx)| {
+                let sma_value = (*sma_values.get(global_idx)?)?;
+                let x = Self::clamp_x(inner, chart_width, idx * spacing + spacing / 2);
+                let y = self.price_to_y(inner, max_price, price_range, sma_value)?;
+                (y >= inner.y && y < inner.y + inner.height).then_some((x, y, sma_value))
+            })
+            .collect();
+        let cells: Vec<Option<(u16, u16)>> =
+            points.iter().map(|p| p.map(|(x, y, _)| (x, y))).collect();
+        overlay::draw_line(
+            frame.buffer_mut(),
+            &cells,
+            self.line_styles.sma,
+            SMA_GLYPH,
+            Color::Cyan,
+        );
+        points
+            .iter()
+            .rev()
+            .flatten()
+            .next()
+            .map(|&(_, y, value)| (y, value))
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -532,41 +2369,1086 @@ impl Chart {
         &self,
         frame: &mut Frame,
         inner: Rect,
-        start_idx: usize,
-        candle_count: usize,
+        buffer_idx: &[usize],
         spacing: usize,
         chart_width: usize,
         rsi_values: &[Option<f64>],
-    ) {
+    ) -> Option<(u16, f64)> {
         let band_height = (inner.height / 4).max(2);
         let band_top = inner.y + inner.height.saturating_sub(band_height);
 
-        for idx in 0..candle_count {
-            let global_idx = start_idx + idx;
-            let Some(Some(rsi_value)) = rsi_values.get(global_idx) else {
-                continue;
-            };
-
-            let rsi_clamped = rsi_value.clamp(0.0, 100.0);
-            let x_pos = (idx * spacing) + (spacing / 2);
-            let x = Self::clamp_x(inner, chart_width, x_pos);
-            let y_offset =
-                ((100.0 - rsi_clamped) / 100.0 * (band_height.saturating_sub(1)) as f64) as u16;
-            let max_band_y = band_top.saturating_add(band_height.saturating_sub(1));
-            let y = band_top.saturating_add(y_offset).min(max_band_y);
+        let points: Vec<Option<(u16, u16, f64)>> = buffer_idx
+            .iter()
+            .enumerate()
+            .map(|(idx, &global_idx)| {
+                let rsi_value = (*rsi_values.get(global_idx)?)?;
+                let rsi_clamped = rsi_value.clamp(0.0, 100.0);
+                let x = Self::clamp_x(inner, chart_width, idx * spacing + spacing / 2);
+                let y_offset =
+                    ((100.0 - rsi_clamped) / 100.0 * (band_height.saturating_sub(1)) as f64) as u16;
+                let max_band_y = band_top.saturating_add(band_height.saturating_sub(1));
+                let y = band_top.saturating_add(y_offset).min(max_band_y);
+                (y >= inner.y && y < inner.y + inner.height).then_some((x, y, rsi_value))
+            })
+            .collect();
+        let cells: Vec<Option<(u16, u16)>> =
+            points.iter().map(|p| p.map(|(x, y, _)| (x, y))).collect();
+        overlay::draw_line(
+            frame.buffer_mut(),
+            &cells,
+            self.line_styles.rsi,
+            RSI_GLYPH,
+            Color::Magenta,
+        );
+        points
+            .iter()
+            .rev()
+            .flatten()
+            .next()
+            .map(|&(_, y, value)| (y, value))
+    }
 
-            if y >= inner.y && y < inner.y + inner.height {
-                let cell = &mut frame.buffer_mut()[(x, y)];
-                cell.set_char('·').set_fg(Color::Magenta);
+    /// Tags each overlay's latest value against the plot's right edge on
+    /// its line's row, moving tags apart when lines run close together.
+    fn draw_overlay_values(frame: &mut Frame, inner: Rect, labels: &[(String, u16, Color)]) {
+        if labels.is_empty() || inner.height == 0 {
+            return;
+        }
+        let wanted: Vec<u16> = labels.iter().map(|(_, y, _)| *y).collect();
+        let rows = overlay::spread_rows(&wanted, inner.y, inner.y + inner.height);
+        for ((text, _, color), y) in labels.iter().zip(rows) {
+            let tag = format!(" {text} ");
+            let width = (tag.chars().count() as u16).min(inner.width);
+            let start = inner.x + inner.width - width;
+            let style = Style::default().fg(Color::Black).bg(*color);
+            for (j, ch) in tag.chars().take(width as usize).enumerate() {
+                frame.buffer_mut()[(start + j as u16, y)]
+                    .set_char(ch)
+                    .set_style(style);
             }
         }
     }
 
+    /// The symbol, last price and change since the day's open in a boxed
+    /// readout at the `price_hud` corner, cleared underneath so the candles
+    /// can't show through.
+    fn draw_price_hud(&self, frame: &mut Frame, inner: Rect, theme: &Theme) {
+        let Some(last) = self
+            .candles
+            .back()
+            .and_then(|candle| candle.close.parse::<f64>().ok())
+        else {
+            return;
+        };
+        let price = theme.format_price(last);
+        let mut lines = vec![
+            Line::from(Span::styled(
+                self.symbol.clone(),
+                Style::default().fg(Color::White),
+            )),
+            Line::from(Span::styled(
+                price,
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            )),
+        ];
+        if let Some(day_open) = self.day_open() {
+            let pct = Self::change_pct(day_open, last);
+            lines.push(Line::from(Span::styled(
+                format!("{pct:+.2}% day"),
+                Style::default()
+                    .fg(theme.direction(pct >= 0.0))
+                    .add_modifier(Modifier::BOLD),
+            )));
+        }
+        let text_width = lines.iter().map(Line::width).max().unwrap_or(0) as u16;
+        let width = text_width + 4;
+        let height = lines.len() as u16 + 2;
+        if width > inner.width || height > inner.height {
+            return;
+        }
+        let x = match self.price_hud {
+            PriceHud::Off => return,
+            PriceHud::TopLeft | PriceHud::BottomLeft => inner.x,
+            PriceHud::TopRight | PriceHud::BottomRight => inner.right() - width,
+        };
+        let y = match self.price_hud {
+            PriceHud::TopLeft | PriceHud::TopRight | PriceHud::Off => inner.y,
+            PriceHud::BottomLeft | PriceHud::BottomRight => inner.bottom() - height,
+        };
+        let rect = Rect::new(x, y, width, height);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow))
+            .style(Style::default().bg(Color::Black))
+            .padding(Padding::horizontal(1));
+        frame.render_widget(Clear, rect);
+        frame.render_widget(Paragraph::new(lines).block(block), rect);
+    }
+
+    /// Names the overlays that are on, with their color and line style, in
+    /// the plot's top-left corner.
+    fn draw_overlay_legend(&self, frame: &mut Frame, inner: Rect) {
+        let mut entries: Vec<(String, LineStyle, char, Color)> = Vec::new();
+        if self.show_sma {
+            entries.push((
+                format!("SMA{}", self.indicators.sma_period()),
+                self.line_styles.sma,
+                SMA_GLYPH,
+                Color::Cyan,
+            ));
+        }
+        if self.show_rsi {
+            entries.push((
+                format!("RSI{}", self.indicators.rsi_period()),
+                self.line_styles.rsi,
+                RSI_GLYPH,
+                Color::Magenta,
+            ));
+        }
+        if entries.is_empty() || inner.height == 0 {
+            return;
+        }
+        frame.render_widget(
+            Paragraph::new(overlay::legend(&entries)),
+            Rect { height: 1, ..inner },
+        );
+    }
+
     fn latest_indicator_values(&self) -> (Option<f64>, Option<f64>) {
-        let all_candles: Vec<Candle> = self.candles.iter().cloned().collect();
-        let sma = calculate_sma(&all_candles, 20);
-        let rsi = calculate_rsi(&all_candles, 14);
+        (self.indicators.latest_sma(), self.indicators.latest_rsi())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::fetch::candle;
+    use crate::ui::grid::GridMode;
+    use crate::ui::theme::{ColorCapability, Palette};
+
+    #[test]
+    fn half_block_glyph_keeps_the_wick_beside_the_body() {
+        assert_eq!(
+            Chart::half_block_glyph(true, true, true, true),
+            Some(('█', false))
+        );
+        assert_eq!(
+            Chart::half_block_glyph(true, false, true, true),
+            Some(('▀', true))
+        );
+        assert_eq!(
+            Chart::half_block_glyph(true, false, true, false),
+            Some(('▀', false))
+        );
+        assert_eq!(
+            Chart::half_block_glyph(false, true, true, true),
+            Some(('▄', true))
+        );
+        assert_eq!(
+            Chart::half_block_glyph(false, false, true, false),
+            Some(('╵', false))
+        );
+        assert_eq!(
+            Chart::half_block_glyph(false, false, false, true),
+            Some(('╷', false))
+        );
+        assert_eq!(Chart::half_block_glyph(false, false, false, false), None);
+    }
+
+    #[test]
+    fn half_block_cell_shows_body_and_wick_together() {
+        let mut chart = Chart::new("BTCUSDT".to_string(), "1m".to_string());
+        chart.half_block = true;
+        chart.padding_factor = 0.0;
+        // The body's top lands in the lower half of a cell, under the wick.
+        chart.update_candles(vec![
+            candle(0, 100.0, 100.0, 100.0, 100.0),
+            candle(60_000, 120.0, 130.0, 110.0, 124.0),
+            candle(120_000, 140.0, 140.0, 140.0, 140.0),
+        ]);
+        for theme in [
+            Theme::default(),
+            Theme::new(Palette::BlueOrange, ColorCapability::Basic16),
+        ] {
+            let buffer = render_with_theme(&chart, 60, 22, &theme);
+            let split: Vec<_> = buffer
+                .content()
+                .iter()
+                .filter(|cell| matches!(cell.symbol(), "▀" | "▄") && cell.bg != Color::Reset)
+                .collect();
+            assert!(!split.is_empty());
+            for cell in split {
+                let wick = theme.adapt(scale_color(cell.fg, HALF_BLOCK_WICK_SHADE));
+                assert_eq!(cell.bg, wick);
+                if theme.capability != ColorCapability::TrueColor {
+                    assert!(!matches!(cell.bg, Color::Rgb(..)));
+                }
+            }
+        }
+    }
+
+    fn render_to_buffer(chart: &Chart, width: u16, height: u16) -> ratatui::buffer::Buffer {
+        render_with_theme(chart, width, height, &Theme::default())
+    }
+
+    fn render_with_theme(
+        chart: &Chart,
+        width: u16,
+        height: u16,
+        theme: &Theme,
+    ) -> ratatui::buffer::Buffer {
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(width, height)).unwrap();
+        terminal
+            .draw(|f| chart.render_candlesticks(f, f.area(), theme))
+            .unwrap();
+        terminal.backend().buffer().clone()
+    }
+
+    #[test]
+    fn zoom_presets_clamp_and_merge_past_the_width() {
+        let mut chart = Chart::new("BTCUSDT".to_string(), "1m".to_string());
+        chart.update_candles(
+            (0..120)
+                .map(|i| candle(i * 60_000, 10.0, 12.0, 9.0, 11.0))
+                .collect(),
+        );
+        let width = chart.axis_width() + 100;
+
+        chart.cycle_zoom_preset();
+        assert_eq!(chart.preset, Some(50));
+        assert_eq!(chart.visible_candles(width).len(), 50);
+        chart.cycle_zoom_preset();
+        assert_eq!(chart.visible_candles(width).len(), 100);
+        assert_eq!(chart.candles_per_column(width), 1);
+
+        // 200 is clamped to the 120 buffered, merged in pairs to fit.
+        chart.cycle_zoom_preset();
+        assert_eq!(chart.candles_per_column(width), 2);
+        let visible = chart.visible_candles(width);
+        assert_eq!(visible.len(), 60);
+        assert_eq!(visible.last().unwrap().open_time, 118 * 60_000);
+
+        chart.cycle_zoom_preset();
+        assert_eq!(chart.preset, Some(500));
+        chart.cycle_zoom_preset();
+        assert!(chart.fit);
+        assert_eq!(chart.preset, None);
+        chart.cycle_zoom_preset();
+        assert_eq!(chart.preset, Some(50));
+        assert!(!chart.fit);
+
+        // Regular zoom takes over again without changing its level.
+        chart.zoom_in();
+        assert_eq!(chart.preset, None);
+        assert_eq!(chart.zoom, 1);
+    }
+
+    #[test]
+    fn fit_window_merges_from_newest_candle() {
+        let mut chart = Chart::new("BTCUSDT".to_string(), "1m".to_string());
+        chart.update_candles(
+            (0..7)
+                .map(|i| {
+                    candle(
+                        i * 60_000,
+                        i as f64 + 10.0,
+                        i as f64 + 12.0,
+                        i as f64 + 9.0,
+                        i as f64 + 11.0,
+                    )
+                })
+                .collect(),
+        );
+        chart.fit = true;
+
+        let window = chart.window(3);
+        assert!(window.merged);
+        assert_eq!(window.buffer_idx, vec![0, 3, 6]);
+        let last = &window.candles[2];
+        assert_eq!(last.open_time, 4 * 60_000);
+        assert_eq!(last.open, "14");
+        assert_eq!(last.close, "17");
+        assert_eq!(last.high, "18");
+        assert_eq!(last.low, "13");
+        assert_eq!(last.volume, "3");
+        assert_eq!(last.number_of_trades, 3);
+
+        assert_eq!(chart.candles_per_column(13 + 3), 3);
+
+        chart.zoom_in();
+        assert!(!chart.fit);
+        assert_eq!(chart.zoom, 1);
+        assert_eq!(chart.candles_per_column(13 + 3), 1);
+
+        // Zooming out past the widest zoom fits the history again.
+        chart.zoom_in();
+        chart.zoom_out();
+        assert!(!chart.fit);
+        chart.zoom_out();
+        assert!(chart.fit);
+    }
+
+    #[test]
+    fn indicator_prefs_follow_the_symbol() {
+        let mut chart = Chart::new("BTCUSDT".to_string(), "1m".to_string());
+        chart.indicator_prefs.insert(
+            "BTCUSDT".to_string(),
+            IndicatorPrefs {
+                show_sma: true,
+                ..IndicatorPrefs::default()
+            },
+        );
+        chart.indicator_prefs.insert(
+            "ETHUSDT".to_string(),
+            IndicatorPrefs {
+                show_rsi: true,
+                rsi_period: 7,
+                ..IndicatorPrefs::default()
+            },
+        );
+
+        chart.symbol = "ETHUSDT".to_string();
+        chart.apply_indicator_prefs();
+        assert!(!chart.show_sma && chart.show_rsi);
+        assert_eq!(chart.indicators.rsi_period(), 7);
+
+        chart.symbol = "BTCUSDT".to_string();
+        chart.apply_indicator_prefs();
+        assert!(chart.show_sma && !chart.show_rsi);
+        assert_eq!(chart.indicators.rsi_period(), DEFAULT_RSI_PERIOD);
+
+        // Toggling a symbol's own setup back to the default drops its entry.
+        chart.toggle_sma();
+        assert!(!chart.indicator_prefs.contains_key("BTCUSDT"));
+    }
+
+    #[test]
+    fn toggles_without_a_symbol_entry_set_the_global_default() {
+        let mut chart = Chart::new("BTCUSDT".to_string(), "1m".to_string());
+        chart.default_indicators = IndicatorPrefs {
+            sma_period: 50,
+            ..IndicatorPrefs::default()
+        };
+        chart.apply_indicator_prefs();
+        chart.toggle_sma();
+        chart.toggle_rsi();
+        assert!(chart.indicator_prefs.is_empty());
+        assert!(chart.default_indicators.show_sma && chart.default_indicators.show_rsi);
+        assert_eq!(chart.default_indicators.sma_period, 50);
+
+        // The next symbol, and the next launch via `active_indicators`,
+        // come up with the same overlays.
+        chart.show_sma = false;
+        chart.show_rsi = false;
+        chart.symbol = "SOLUSDT".to_string();
+        chart.apply_indicator_prefs();
+        assert!(chart.show_sma && chart.show_rsi);
+        assert_eq!(
+            IndicatorPrefs::from_active(&chart.default_indicators.active()),
+            chart.default_indicators
+        );
+    }
+
+    #[test]
+    fn catch_up_eases_back_to_the_live_edge() {
+        let mut chart = Chart::new("BTCUSDT".to_string(), "1m".to_string());
+        chart.offset = 80;
+        let start = Instant::now();
+        chart.jump_to_live(start);
+
+        assert!(chart.advance_catch_up(start + Duration::from_millis(100)));
+        let early = chart.offset;
+        assert!(early > 0 && early < 80);
+        assert!(chart.advance_catch_up(start + Duration::from_millis(300)));
+        assert!(chart.offset < early);
+        assert!(!chart.advance_catch_up(start + CATCH_UP_DURATION));
+        assert_eq!(chart.offset, 0);
+        assert!(!chart.advance_catch_up(start + Duration::from_secs(5)));
+
+        chart.offset = 80;
+        chart.jump_to_live(start);
+        chart.advance_catch_up(start + Duration::from_millis(100));
+        chart.cancel_catch_up();
+        let stopped = chart.offset;
+        assert!(!chart.advance_catch_up(start + Duration::from_secs(1)));
+        assert_eq!(chart.offset, stopped);
+
+        chart.smooth_catch_up = false;
+        chart.jump_to_live(start);
+        assert_eq!(chart.offset, 0);
+    }
+
+    #[test]
+    fn hollow_bodies_draw_an_outline() {
+        use std::cmp::Ordering::{Equal, Greater, Less};
+        assert_eq!(Chart::hollow_glyph((false, false), None, (true, true)), '║');
+        assert_eq!(
+            Chart::hollow_glyph((true, false), Some(Less), (true, true)),
+            '┌'
+        );
+        assert_eq!(
+            Chart::hollow_glyph((true, false), Some(Equal), (true, true)),
+            '┴'
+        );
+        assert_eq!(
+            Chart::hollow_glyph((true, false), Some(Equal), (false, true)),
+            '─'
+        );
+        assert_eq!(
+            Chart::hollow_glyph((false, true), Some(Greater), (true, true)),
+            '┘'
+        );
+        assert_eq!(
+            Chart::hollow_glyph((false, true), Some(Equal), (true, true)),
+            '┬'
+        );
+        assert_eq!(
+            Chart::hollow_glyph((false, false), Some(Equal), (true, true)),
+            ' '
+        );
+        assert_eq!(
+            Chart::hollow_glyph((false, false), Some(Less), (true, true)),
+            '│'
+        );
+
+        let mut chart = Chart::new("BTCUSDT".to_string(), "1m".to_string());
+        chart.candle_style = CandleStyle::Hollow;
+        chart.emphasis = CandleEmphasis::Body;
+        chart.update_candles(vec![
+            candle(0, 100.0, 130.0, 90.0, 125.0),
+            candle(60_000, 125.0, 130.0, 90.0, 95.0),
+        ]);
+        let buffer = render_to_buffer(&chart, 60, 20);
+        let glyphs: String = buffer.content().iter().map(|c| c.symbol()).collect();
+        assert!(glyphs.contains('┌') && glyphs.contains('┘'));
+        assert!(glyphs.contains('█'));
+    }
+
+    #[test]
+    fn live_build_tracks_only_the_open_candle() {
+        let rising = candle(0, 100.0, 103.0, 99.0, 102.0);
+        let (delta, pct) = live_build(&rising).unwrap();
+        assert!((delta - 2.0).abs() < 1e-9);
+        assert!((pct - 2.0).abs() < 1e-9);
+
+        let falling = candle(0, 100.0, 101.0, 95.0, 95.0);
+        assert!(live_build(&falling).unwrap().0 < 0.0);
+
+        let closed = Candle {
+            is_closed: true,
+            ..rising
+        };
+        assert_eq!(live_build(&closed), None);
+    }
+
+    #[test]
+    fn near_flat_candle_renders_doji_marker() {
+        let mut chart = Chart::new("BTCUSDT".to_string(), "1m".to_string());
+        let mut candles: Vec<Candle> = (0..9)
+            .map(|i| candle(i * 60_000, 100.0, 110.0, 90.0, 105.0))
+            .collect();
+        candles.push(candle(9 * 60_000, 100.0, 108.0, 92.0, 100.01));
+        chart.update_candles(candles);
+
+        let buffer = render_to_buffer(&chart, 60, 20);
+        let glyphs: String = buffer.content().iter().map(|c| c.symbol()).collect();
+        assert!(glyphs.contains('┼'));
+    }
+
+    #[test]
+    fn braille_mode_draws_a_line_instead_of_candles() {
+        let mut chart = Chart::new("BTCUSDT".to_string(), "1m".to_string());
+        chart.update_candles(
+            (0..10)
+                .map(|i| {
+                    let close = 100.0 + i as f64;
+                    candle(i * 60_000, close - 0.5, close + 1.0, close - 1.0, close)
+                })
+                .collect(),
+        );
+        chart.braille = true;
+
+        let buffer = render_to_buffer(&chart, 60, 20);
+        let glyphs: String = buffer.content().iter().map(|c| c.symbol()).collect();
+        assert!(glyphs
+            .chars()
+            .any(|c| ('\u{2801}'..='\u{28FF}').contains(&c)));
+        assert!(!glyphs.contains('█') && !glyphs.contains('│'));
+    }
+
+    #[test]
+    fn body_emphasis_widens_bodies_within_bounds() {
+        let mut chart = Chart::new("BTCUSDT".to_string(), "1m".to_string());
+        chart.emphasis = CandleEmphasis::Body;
+        chart.update_candles(
+            (0..10)
+                .map(|i| candle(i * 60_000, 100.0, 110.0, 90.0, 105.0))
+                .collect(),
+        );
+
+        let buffer = render_to_buffer(&chart, 60, 20);
+        let widest_body_row = (0..20)
+            .map(|y| (0..60).filter(|x| buffer[(*x, y)].symbol() == "█").count())
+            .max()
+            .unwrap();
+        assert_eq!(widest_body_row, 30);
+    }
+
+    #[test]
+    fn notes_follow_their_candle_as_the_view_pans() {
+        let mut chart = Chart::new("BTCUSDT".to_string(), "1m".to_string());
+        chart.update_candles(
+            (0..40)
+                .map(|i| candle(i * 60_000, 100.0, 110.0, 90.0, 105.0))
+                .collect(),
+        );
+        chart.toggle_crosshair();
+        for _ in 0..3 {
+            chart.crosshair_left();
+        }
+        assert!(chart.add_note("entry".to_string()));
+        assert_eq!(chart.notes["BTCUSDT"][0].open_time, 36 * 60_000);
+
+        let marker_col = |chart: &Chart| {
+            let buffer = render_to_buffer(chart, 60, 20);
+            (0..60).find(|x| (0..20).any(|y| buffer[(*x, y)].symbol() == "◆"))
+        };
+        let before = marker_col(&chart).unwrap();
+        chart.crosshair = None;
+        chart.pan_left();
+        let after = marker_col(&chart).unwrap();
+        assert!(after > before);
+
+        chart.crosshair = Some(3);
+        assert!(chart.delete_note());
+        assert!(chart.notes.is_empty());
+    }
+
+    #[test]
+    fn candle_width_sets_the_visible_count() {
+        let mut chart = Chart::new("BTCUSDT".to_string(), "1m".to_string());
+        chart.update_candles(
+            (0..200)
+                .map(|i| candle(i * 60_000, 100.0, 101.0, 99.0, 100.5))
+                .collect(),
+        );
+        // 80 plot columns at the default one-wide body and one-column gap.
+        assert_eq!(chart.visible_candles(93).len(), 40);
+        assert_eq!(chart.visible_candles(213).len(), 100);
+
+        chart.candle_width = 3;
+        assert_eq!(chart.visible_candles(93).len(), 20);
+        chart.zoom = 2;
+        assert_eq!(chart.visible_candles(93).len(), 10);
+
+        let inner = Rect::new(13, 1, 80, 10);
+        assert_eq!(chart.body_columns(inner, 20, 4), 19..=21);
+        // Packed tighter than preferred, the body narrows but stays odd.
+        assert_eq!(chart.body_columns(inner, 20, 3), 20..=20);
+        chart.candle_width = 5;
+        chart.candle_gap = 0;
+        assert_eq!(chart.body_columns(inner, 20, 5), 18..=22);
+        assert_eq!(chart.body_columns(inner, 13, 5), 13..=15);
+    }
+
+    #[test]
+    fn crosshair_readout_snaps_to_the_tick_size() {
+        let mut chart = Chart::new("BTCUSDT".to_string(), "1m".to_string());
+        let inner = Rect::new(13, 1, 40, 17);
+        assert_eq!(chart.y_to_price(inner, 195.0, 100.0, 1), 195.0);
+        assert_eq!(chart.y_to_price(inner, 195.0, 100.0, 17), 95.0);
+        assert_eq!(chart.y_to_price(inner, 195.0, 100.0, 2), 188.75);
+        chart.invert_y = true;
+        assert_eq!(chart.y_to_price(inner, 195.0, 100.0, 2), 101.25);
+
+        // Without a known tick size only the row's own price is shown.
+        assert_eq!(chart.crosshair_price_label(188.753, 0.01), "188.75");
+        chart.tick_size = 0.5;
+        assert_eq!(chart.crosshair_price_label(188.753, 0.01), "188.75 → 189.0");
+        assert_eq!(chart.crosshair_price_label(188.5, 0.01), "188.5");
+        chart.snap_to_tick = false;
+        assert_eq!(chart.crosshair_price_label(188.753, 0.01), "188.75");
+
+        chart.update_candles(vec![candle(0, 100.0, 110.0, 90.0, 105.0)]);
+        chart.toggle_crosshair();
+        render_to_buffer(&chart, 60, 20);
+        for _ in 0..100 {
+            chart.crosshair_down();
+        }
+        assert_eq!(chart.crosshair_row, chart.plot_rows.get() - 1);
+    }
+
+    #[test]
+    fn price_precision_prefers_the_tick_size_then_the_data() {
+        let mut chart = Chart::new("ADAUSDT".to_string(), "1m".to_string());
+        assert_eq!(chart.price_precision(), None);
+
+        let mut latest = candle(60_000, 1.3, 1.3, 1.2, 1.2);
+        latest.close = "1.23450000".to_string();
+        chart.update_candles(vec![candle(0, 1.2, 1.3, 1.1, 1.2), latest]);
+        assert_eq!(chart.price_precision(), Some(4));
+
+        chart.tick_size = 0.001;
+        assert_eq!(chart.price_precision(), Some(3));
+
+        chart.tick_size = 0.0;
+        chart.infer_precision = false;
+        assert_eq!(chart.price_precision(), None);
+    }
+
+    #[test]
+    fn time_basis_shifts_axis_and_crosshair_labels_to_the_close() {
+        let mut chart = Chart::new("BTCUSDT".to_string(), "1m".to_string());
+        chart.update_candles(vec![candle(45 * 60_000, 100.0, 110.0, 90.0, 105.0)]);
+        let axis_text = |chart: &Chart| {
+            let buffer = render_to_buffer(chart, 60, 20);
+            (0..20)
+                .map(|y| (0..60).map(|x| buffer[(x, y)].symbol()).collect::<String>())
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        assert!(axis_text(&chart).contains("00:45"));
+
+        chart.time_basis = CandleTimeBasis::Close;
+        let text = axis_text(&chart);
+        assert!(text.contains("00:46") && !text.contains("00:45"));
+
+        chart.toggle_crosshair();
+        assert!(axis_text(&chart).contains(" 00:46 "));
+    }
+
+    #[test]
+    fn inverted_axis_puts_highs_at_the_bottom_within_bounds() {
+        let mut chart = Chart::new("BTCUSDT".to_string(), "1m".to_string());
+        chart.invert_y = true;
+        chart.padding_factor = 0.0;
+        chart.round_price_labels = false;
+        // Only candle glyphs should be found when probing for the top row.
+        chart.grid.mode = GridMode::Off;
+        chart.live_candle = LiveCandleStyle::Off;
+        chart.update_candles(
+            (0..10)
+                .map(|i| {
+                    let base = 100.0 + i as f64 * 10.0;
+                    candle(i * 60_000, base, base + 5.0, base - 5.0, base + 4.0)
+                })
+                .collect(),
+        );
+
+        let buffer = render_to_buffer(&chart, 60, 20);
+        let label = |y: u16| -> String { (0..11).map(|x| buffer[(x, y)].symbol()).collect() };
+        assert_eq!(label(1).trim(), "95.00");
+        assert_eq!(label(17).trim(), "195.00");
+
+        let top_row = |x: u16| (1..18).find(|y| buffer[(x, *y)].symbol() != " ");
+        assert_eq!(top_row(15), Some(1));
+        assert_eq!(top_row(51), Some(15));
+    }
+
+    #[test]
+    fn price_axis_moves_right_or_gives_its_columns_to_the_plot() {
+        let mut chart = Chart::new("BTCUSDT".to_string(), "1m".to_string());
+        chart.round_price_labels = false;
+        chart.padding_factor = 0.0;
+        chart.update_candles(
+            (0..100)
+                .map(|i| candle(i * 60_000, 100.0, 105.0, 95.0, 100.0))
+                .collect(),
+        );
+        // Two columns per candle: a body and a gap.
+        assert_eq!(chart.visible_candles(60).len(), 23);
+
+        chart.cycle_price_axis();
+        assert_eq!(chart.price_axis, PriceAxis::Right);
+        assert_eq!(chart.visible_candles(60).len(), 23);
+        let buffer = render_to_buffer(&chart, 60, 20);
+        let right: String = (47..60).map(|x| buffer[(x, 1)].symbol()).collect();
+        assert_eq!(right.trim(), "105.00");
+        let left: String = (0..11).map(|x| buffer[(x, 1)].symbol()).collect();
+        assert!(!left.contains("105.00"));
+
+        chart.cycle_price_axis();
+        assert_eq!(chart.price_axis, PriceAxis::Hidden);
+        assert_eq!(chart.visible_candles(60).len(), 30);
+        let buffer = render_to_buffer(&chart, 60, 20);
+        let top: String = (0..60).map(|x| buffer[(x, 1)].symbol()).collect();
+        assert!(!top.contains("105.00"));
+
+        chart.cycle_price_axis();
+        assert_eq!(chart.price_axis, PriceAxis::Left);
+    }
+
+    #[test]
+    fn live_candle_is_marked_until_it_closes() {
+        let mut chart = Chart::new("BTCUSDT".to_string(), "1m".to_string());
+        chart.padding_factor = 0.0;
+        chart.update_candles(
+            (0..10)
+                .map(|i| {
+                    let base = 100.0 + i as f64;
+                    Candle {
+                        is_closed: i < 9,
+                        ..candle(i * 60_000, base, base + 5.0, base - 5.0, base + 4.0)
+                    }
+                })
+                .collect(),
+        );
+        let markers = |chart: &Chart| {
+            let buffer = render_to_buffer(chart, 60, 20);
+            (0..20)
+                .flat_map(|y| (0..60).map(move |x| (x, y)))
+                .filter(|&(x, y)| matches!(buffer[(x, y)].symbol(), "▾" | "▴"))
+                .count()
+        };
+        assert_eq!(markers(&chart), 1);
+
+        chart.live_candle = LiveCandleStyle::Off;
+        assert_eq!(markers(&chart), 0);
+
+        chart.live_candle = LiveCandleStyle::Marker;
+        chart.candles.back_mut().unwrap().is_closed = true;
+        assert_eq!(markers(&chart), 0);
+    }
+
+    #[test]
+    fn overlays_tag_their_latest_value_at_the_right_edge() {
+        let mut chart = Chart::new("BTCUSDT".to_string(), "1m".to_string());
+        chart.update_candles(
+            (0..30)
+                .map(|i| {
+                    let close = 100.0 + i as f64;
+                    candle(i * 60_000, close, close + 1.0, close - 1.0, close)
+                })
+                .collect(),
+        );
+        chart.toggle_sma();
+        let buffer = render_to_buffer(&chart, 80, 24);
+        let tag = (0..24).find_map(|y| {
+            let row: String = (0..80).map(|x| buffer[(x, y)].symbol()).collect();
+            row.find(" 119.50 ").map(|_| (y, row))
+        });
+
+        // SMA20 of closes 110..=129.
+        let (y, row) = tag.expect("SMA value tag");
+        assert!(row.trim_end().ends_with("119.50"));
+        let x = row.chars().count() as u16 - 2;
+        assert_eq!(buffer[(x, y)].bg, Color::Cyan);
+    }
+
+    #[test]
+    fn squeeze_shades_the_quiet_stretch_and_marks_the_release() {
+        let mut chart = Chart::new("BTCUSDT".to_string(), "1m".to_string());
+        let mut candles: Vec<Candle> = (0..40)
+            .map(|i| candle(i * 60_000, 100.0, 105.0, 95.0, 100.0))
+            .collect();
+        candles.push(candle(40 * 60_000, 100.0, 161.0, 99.0, 160.0));
+        chart.update_candles(candles);
+        chart.show_squeeze = true;
+        let buffer = render_to_buffer(&chart, 120, 24);
+        let rows: Vec<String> = (0..24)
+            .map(|y| (0..120).map(|x| buffer[(x, y)].symbol()).collect())
+            .collect();
+
+        assert!(rows.iter().any(|row| row.contains("Squeeze fired")));
+        let marker = rows.iter().position(|row| row.contains('▲')).unwrap();
+        let shade = Theme::default().adapt(SQUEEZE_SHADE);
+        assert!((0..120).any(|x| buffer[(x, marker as u16)].bg == shade));
+        // Nothing is shaded before the bands have a full lookback.
+        let first_column = chart.plot_columns(Rect::new(0, 0, 120, 24)).x;
+        assert_ne!(buffer[(first_column, marker as u16)].bg, shade);
+    }
+
+    #[test]
+    fn roc_strip_colors_around_the_zero_line() {
+        let mut chart = Chart::new("BTCUSDT".to_string(), "1m".to_string());
+        // Up for twenty candles, then down for twenty.
+        chart.update_candles(
+            (0..40)
+                .map(|i| {
+                    let close = 100.0 + 20.0 - (i as f64 - 20.0).abs();
+                    candle(i * 60_000, close, close + 1.0, close - 1.0, close)
+                })
+                .collect(),
+        );
+        chart.show_roc = true;
+        chart.roc_period = 5;
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(100, 34)).unwrap();
+        terminal
+            .draw(|f| chart.render(f, f.area(), &Theme::default()))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        let top = (0..34)
+            .find(|y| {
+                (0..12)
+                    .map(|x| buffer[(x, *y)].symbol())
+                    .collect::<String>()
+                    == "ROC5        "
+            })
+            .expect("ROC gutter");
+        let strip: Vec<(u16, u16)> = (top..top + 4)
+            .flat_map(|y| (12..100).map(move |x| (x, y)))
+            .collect();
+        let theme = Theme::default();
+        let dots = |color: Color| {
+            strip
+                .iter()
+                .filter(|&&(x, y)| buffer[(x, y)].symbol() == "•" && buffer[(x, y)].fg == color)
+                .count()
+        };
+        assert!(dots(theme.up) > 0 && dots(theme.down) > 0);
+        assert!(strip.iter().any(|&(x, y)| buffer[(x, y)].symbol() == "┄"));
+    }
+
+    #[test]
+    fn price_hud_sits_on_top_in_its_corner() {
+        let mut chart = Chart::new("BTCUSDT".to_string(), "1m".to_string());
+        chart.update_candles(
+            (0..30)
+                .map(|i| {
+                    let close = 100.0 + i as f64;
+                    candle(i * 60_000, close, close + 1.0, close - 1.0, close)
+                })
+                .collect(),
+        );
+        chart.price_hud = PriceHud::TopRight;
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(80, 30)).unwrap();
+        terminal
+            .draw(|f| chart.render(f, f.area(), &Theme::default()))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        let rows: Vec<String> = (0..30)
+            .map(|y| (0..80).map(|x| buffer[(x, y)].symbol()).collect())
+            .collect();
+
+        let y = rows
+            .iter()
+            .position(|row| row.contains("│ 129.00"))
+            .expect("price readout");
+        assert!(rows[y - 1].ends_with("│ BTCUSDT     │"));
+        assert!(rows[y + 1].ends_with("│ +29.00% day │"));
+        // The newest candles run under the box without showing through.
+        assert!(rows[y].ends_with("│ 129.00      │"));
+        assert_eq!(buffer[(70, y as u16)].bg, Color::Black);
+    }
+
+    #[test]
+    fn info_line_shows_window_and_day_change() {
+        const HOUR: u64 = 3_600_000;
+        let mut chart = Chart::new("BTCUSDT".to_string(), "1h".to_string());
+        chart.update_candles(vec![
+            candle(22 * HOUR, 100.0, 101.0, 99.0, 100.0),
+            candle(23 * HOUR, 100.0, 101.0, 99.0, 100.0),
+            candle(24 * HOUR, 200.0, 211.0, 199.0, 210.0),
+        ]);
+        let buffer = render_to_buffer(&chart, 60, 20);
+        let info: String = (0..60).map(|x| buffer[(x, 19)].symbol()).collect();
+        assert!(info.contains("(+110.00%)"), "{info}");
+        assert!(info.contains("Day: +5.00%"), "{info}");
+
+        let mut single = Chart::new("BTCUSDT".to_string(), "1w".to_string());
+        single.update_candles(vec![candle(0, 100.0, 110.0, 90.0, 105.0)]);
+        let buffer = render_to_buffer(&single, 60, 20);
+        let info: String = (0..60).map(|x| buffer[(x, 19)].symbol()).collect();
+        assert!(info.contains("+0.00 (+0.00%)"), "{info}");
+    }
+
+    #[test]
+    fn price_labels_snap_to_round_prices() {
+        let mut chart = Chart::new("BTCUSDT".to_string(), "1m".to_string());
+        chart.padding_factor = 0.0;
+        chart.update_candles(
+            (0..10)
+                .map(|i| {
+                    let base = 69_387.21 + i as f64 * 30.0;
+                    candle(i * 60_000, base, base + 30.0, base, base + 25.0)
+                })
+                .collect(),
+        );
+
+        let buffer = render_to_buffer(&chart, 60, 20);
+        let gutter: Vec<String> = (0..20)
+            .map(|y| (0..13).map(|x| buffer[(x, y)].symbol()).collect::<String>())
+            .filter(|label| label.trim().contains(','))
+            .collect();
+        assert!(gutter.len() >= 2, "{gutter:?}");
+        for label in &gutter {
+            let price: f64 = label.trim().replace(',', "").parse().unwrap();
+            assert_eq!(price % 50.0, 0.0, "{gutter:?}");
+        }
+        assert!(
+            gutter.iter().any(|label| label.contains("69,500")),
+            "{gutter:?}"
+        );
+
+        chart.round_price_labels = false;
+        let buffer = render_to_buffer(&chart, 60, 20);
+        let top: String = (0..13).map(|x| buffer[(x, 1)].symbol()).collect();
+        assert!(top.contains('.'), "{top}");
+    }
 
-        (sma.last().copied().flatten(), rsi.last().copied().flatten())
+    #[test]
+    fn padding_keeps_extremes_off_the_edges() {
+        let mut chart = Chart::new("BTCUSDT".to_string(), "1m".to_string());
+        chart.round_price_labels = false;
+        chart.update_candles(
+            (0..20)
+                .map(|i| candle(i * 60_000, 100.0, 110.0, 90.0, 105.0))
+                .collect(),
+        );
+        let buffer = render_to_buffer(&chart, 60, 20);
+        let label = |y: u16| -> String { (0..11).map(|x| buffer[(x, y)].symbol()).collect() };
+        assert_eq!(label(1).trim(), "120.00");
+        assert_eq!(label(17).trim(), "80.00");
+        assert!((13..60).all(|x| buffer[(x, 1)].symbol() == " "));
+    }
+
+    #[test]
+    fn heat_bands_shade_rows_with_recent_volume() {
+        let mut chart = Chart::new("BTCUSDT".to_string(), "1m".to_string());
+        chart.padding_factor = 0.0;
+        chart.update_candles(
+            (0..10)
+                .map(|i| candle(i * 60_000, 100.0, 110.0, 90.0, 105.0))
+                .collect(),
+        );
+        chart.heatmap.visible = true;
+        chart.heatmap.record(109.9, 5.0, Instant::now());
+
+        let buffer = render_to_buffer(&chart, 60, 20);
+        assert_ne!(buffer[(14, 1)].bg, Color::Reset);
+        assert_eq!(buffer[(14, 10)].bg, Color::Reset);
+    }
+
+    #[test]
+    fn recency_fades_older_candles_toward_the_left() {
+        assert_eq!(Chart::recency_fades(5, 5), None);
+        assert_eq!(Chart::recency_fades(3, 20), None);
+        let fades = Chart::recency_fades(6, 2).unwrap();
+        assert_eq!(fades.len(), 6);
+        assert_eq!(fades[0], MAX_RECENCY_FADE);
+        assert!(fades
+            .windows(2)
+            .all(|pair| pair[0] > pair[1] || pair[1] == 0.0));
+        assert_eq!(&fades[4..], &[0.0, 0.0]);
+        assert!(fades[3] > 0.0);
+    }
+
+    #[test]
+    fn activity_levels_ramp_between_quietest_and_busiest() {
+        assert_eq!(Chart::activity_levels(&[]), None);
+        assert_eq!(Chart::activity_levels(&[7, 7, 7]), None);
+        let levels = Chart::activity_levels(&[10, 60, 110]).unwrap();
+        assert!((levels[0] - MIN_ACTIVITY_BRIGHTNESS).abs() < 1e-9);
+        assert!((levels[1] - 0.7).abs() < 1e-9);
+        assert!((levels[2] - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn merge_fills_gaps_and_keeps_closed_candles() {
+        let mut chart = Chart::new("BTCUSDT".to_string(), "1m".to_string());
+        chart.max_candles = 5;
+        let mut closed = candle(60_000, 100.0, 101.0, 99.0, 100.5);
+        closed.is_closed = true;
+        chart.update_candles(vec![
+            candle(0, 100.0, 101.0, 99.0, 100.0),
+            closed,
+            candle(240_000, 100.0, 106.0, 99.0, 104.0),
+        ]);
+
+        chart.merge_candles(vec![
+            candle(60_000, 1.0, 1.0, 1.0, 1.0),
+            candle(120_000, 100.0, 106.0, 99.0, 102.0),
+            candle(180_000, 100.0, 106.0, 99.0, 103.0),
+            candle(240_000, 100.0, 106.0, 99.0, 104.5),
+            candle(300_000, 100.0, 106.0, 99.0, 105.0),
+        ]);
+
+        let times: Vec<u64> = chart.candles.iter().map(|c| c.open_time).collect();
+        assert_eq!(times, vec![60_000, 120_000, 180_000, 240_000, 300_000]);
+        let closes: Vec<&str> = chart.candles.iter().map(|c| c.close.as_str()).collect();
+        assert_eq!(closes, vec!["100.5", "102", "103", "104.5", "105"]);
+    }
+
+    #[test]
+    fn invalid_candles_are_dropped_and_counted() {
+        let mut chart = Chart::new("BTCUSDT".to_string(), "1m".to_string());
+        chart.update_candles(vec![
+            candle(0, 100.0, 101.0, 99.0, 100.0),
+            candle(60_000, 0.0, 101.0, 0.0, 100.0),
+            candle(120_000, 100.0, 99.0, 101.0, 100.0),
+        ]);
+        // The same bad live candle on every tick is still one candle.
+        chart.add_candle(candle(180_000, -1.0, 101.0, -2.0, 100.0));
+        chart.add_candle(candle(180_000, -1.0, 101.0, -2.0, 100.0));
+        chart.add_candle(candle(180_000, 100.0, 101.0, 99.0, 100.5));
+        assert!(!chart.reject_candles([60_000]));
+
+        let times: Vec<u64> = chart.candles.iter().map(|c| c.open_time).collect();
+        assert_eq!(times, vec![0, 180_000]);
+        assert_eq!(chart.dropped_candles.len(), 3);
+    }
+
+    #[test]
+    fn taker_split_parses_defensively() {
+        let mut c = candle(0, 1.0, 1.0, 1.0, 1.0);
+        c.volume = "10".to_string();
+        c.taker_buy_base = "6.5".to_string();
+        assert_eq!(taker_split(&c), (6.5, 3.5));
+
+        c.taker_buy_base = "12".to_string();
+        assert_eq!(taker_split(&c), (10.0, 0.0));
+
+        c.taker_buy_base = "n/a".to_string();
+        assert_eq!(taker_split(&c), (0.0, 10.0));
+
+        c.volume = "-3".to_string();
+        assert_eq!(taker_split(&c), (0.0, 0.0));
+    }
+
+    #[test]
+    fn volume_panel_shows_window_taker_split() {
+        let mut chart = Chart::new("BTCUSDT".to_string(), "1m".to_string());
+        chart.update_candles(
+            (0..10)
+                .map(|i| {
+                    let mut c = candle(i * 60_000, 100.0, 110.0, 90.0, 105.0);
+                    c.volume = "4".to_string();
+                    c.taker_buy_base = if i % 2 == 0 { "3" } else { "1.8" }.to_string();
+                    c
+                })
+                .collect(),
+        );
+
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(60, 4)).unwrap();
+        terminal
+            .draw(|f| chart.render_volume(f, f.area(), &Theme::default()))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        let row = |y: u16| -> String { (0..12).map(|x| buffer[(x, y)].symbol()).collect() };
+        assert_eq!(row(1), "Buy   60.0% ");
+        assert_eq!(row(2), "Sell  40.0% ");
+    }
+
+    #[test]
+    fn half_y_mapping_doubles_resolution() {
+        let inner = Rect::new(0, 0, 10, 5);
+        assert_eq!(
+            Chart::map_price_to_half_y(inner, 100.0, 10.0, 100.0),
+            Some(0)
+        );
+        assert_eq!(
+            Chart::map_price_to_half_y(inner, 100.0, 10.0, 90.0),
+            Some(9)
+        );
+        assert_eq!(
+            Chart::map_price_to_half_y(inner, 100.0, 10.0, 95.0),
+            Some(5)
+        );
     }
 }
@@ -0,0 +1,130 @@
+use crate::data::coordinator::Feed;
+use crate::data::stats::{FeedStats, StatsSnapshot};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+const FEEDS: [(Feed, &str); 4] = [
+    (Feed::Kline, "Kline"),
+    (Feed::OrderBook, "Order book"),
+    (Feed::Trades, "Trades"),
+    (Feed::Prices, "Prices"),
+];
+
+/// Where a feed's connection stands, from its counters.
+pub fn connection_label(stats: &StatsSnapshot) -> String {
+    if stats.failures > 0 {
+        format!(
+            "backing off: {} failed, retry in {}s",
+            stats.failures,
+            stats.backoff.as_secs_f64().ceil()
+        )
+    } else if stats.endpoints.is_empty() {
+        "idle".to_string()
+    } else if stats.messages == 0 {
+        "connecting".to_string()
+    } else {
+        "connected".to_string()
+    }
+}
+
+fn feed_lines(name: &str, stats: &StatsSnapshot) -> Vec<Line<'static>> {
+    let failures_color = if stats.parse_failures > 0 {
+        Color::Yellow
+    } else {
+        Color::Gray
+    };
+    let state_color = if stats.failures > 0 {
+        Color::Red
+    } else {
+        Color::Green
+    };
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(format!("{:<11}", name), Style::default().fg(Color::Cyan)),
+            Span::styled(connection_label(stats), Style::default().fg(state_color)),
+        ]),
+        Line::from(vec![
+            Span::styled(
+                format!("  msgs {:<10}", stats.messages),
+                Style::default().fg(Color::White),
+            ),
+            Span::styled(
+                format!("parse errors {:<6}", stats.parse_failures),
+                Style::default().fg(failures_color),
+            ),
+            Span::styled(
+                format!("reconnects {}", stats.reconnects),
+                Style::default().fg(Color::Gray),
+            ),
+        ]),
+    ];
+    if let Some(err) = &stats.last_error {
+        lines.push(Line::from(Span::styled(
+            format!("  error {}", err),
+            Style::default().fg(Color::Red),
+        )));
+    }
+    lines.extend(stats.endpoints.iter().map(|endpoint| {
+        Line::from(Span::styled(
+            format!("  {}", endpoint),
+            Style::default().fg(Color::DarkGray),
+        ))
+    }));
+    lines
+}
+
+/// Per-feed message counters, endpoints and backoff, drawn over the
+/// screen for diagnosing a feed that seems stuck.
+pub fn render_debug_overlay(frame: &mut Frame, stats: &FeedStats) {
+    let mut lines = Vec::new();
+    for (feed, name) in FEEDS {
+        if !lines.is_empty() {
+            lines.push(Line::from(""));
+        }
+        lines.extend(feed_lines(name, &stats.get(feed).snapshot()));
+    }
+
+    let area = frame.area();
+    let width = 72.min(area.width);
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let popup = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    };
+    let block = Block::default()
+        .title("Streams (Ctrl+D to close)")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Magenta));
+
+    frame.render_widget(Clear, popup);
+    frame.render_widget(Paragraph::new(lines).block(block), popup);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn labels_the_connection_state() {
+        let mut stats = StatsSnapshot::default();
+        assert_eq!(connection_label(&stats), "idle");
+        stats.endpoints = vec!["wss://example/ws".to_string()];
+        assert_eq!(connection_label(&stats), "connecting");
+        stats.messages = 5;
+        assert_eq!(connection_label(&stats), "connected");
+        stats.failures = 3;
+        stats.backoff = Duration::from_millis(3_500);
+        assert_eq!(
+            connection_label(&stats),
+            "backing off: 3 failed, retry in 4s"
+        );
+    }
+}
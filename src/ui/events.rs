@@ -0,0 +1,174 @@
+use crate::data::coordinator::Feed;
+use crate::ui::timeaxis::utc_parts;
+use crate::ui::Theme;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, List, ListItem},
+    Frame,
+};
+use std::collections::VecDeque;
+use std::time::Duration;
+
+pub const DEFAULT_EVENT_LOG_SIZE: usize = 100;
+
+/// What happened to a feed's connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventKind {
+    Connecting,
+    /// First data after connecting.
+    Live,
+    Reconnected(Duration),
+    GaveUp,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectionLogEntry {
+    pub timestamp_ms: u64,
+    pub feed: Feed,
+    pub kind: EventKind,
+}
+
+impl ConnectionLogEntry {
+    fn describe(&self) -> String {
+        match self.kind {
+            EventKind::Connecting => "connecting".to_string(),
+            EventKind::Live => "live".to_string(),
+            EventKind::Reconnected(downtime) => {
+                format!("reconnected after {}s", downtime.as_secs())
+            }
+            EventKind::GaveUp => "gave up reconnecting".to_string(),
+        }
+    }
+
+    fn color(&self) -> Color {
+        match self.kind {
+            EventKind::Connecting => Color::Yellow,
+            EventKind::Live => Color::Green,
+            EventKind::Reconnected(_) => Color::Cyan,
+            EventKind::GaveUp => Color::Red,
+        }
+    }
+}
+
+fn feed_name(feed: Feed) -> &'static str {
+    match feed {
+        Feed::Kline => "kline",
+        Feed::OrderBook => "orderbook",
+        Feed::Trades => "trades",
+        Feed::Prices => "prices",
+    }
+}
+
+/// The last `capacity` connection events across all feeds, kept after the
+/// status bar has moved on so flaky sessions leave a timeline behind.
+pub struct EventLog {
+    entries: VecDeque<ConnectionLogEntry>,
+    capacity: usize,
+    pub visible: bool,
+}
+
+impl EventLog {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::new(),
+            capacity: capacity.max(1),
+            visible: false,
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(1);
+        self.trim();
+    }
+
+    pub fn push(&mut self, entry: ConnectionLogEntry) {
+        self.entries.push_back(entry);
+        self.trim();
+    }
+
+    fn trim(&mut self) {
+        while self.entries.len() > self.capacity {
+            self.entries.pop_front();
+        }
+    }
+
+    /// Newest first.
+    pub fn entries(&self) -> impl Iterator<Item = &ConnectionLogEntry> {
+        self.entries.iter().rev()
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let block = Block::default()
+            .title(format!("Events ({})", self.entries.len()))
+            .borders(theme.borders())
+            .border_style(Style::default().fg(Color::Blue));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let items: Vec<ListItem> = self
+            .entries()
+            .take(inner.height as usize)
+            .map(|entry| {
+                let parts = utc_parts(entry.timestamp_ms);
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("{:02}:{:02}:{:02} ", parts.hour, parts.minute, parts.second),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Span::styled(
+                        format!("{:<10}", feed_name(entry.feed)),
+                        Style::default().fg(Color::White),
+                    ),
+                    Span::styled(entry.describe(), Style::default().fg(entry.color())),
+                ]))
+            })
+            .collect();
+        frame.render_widget(List::new(items), inner);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(timestamp_ms: u64) -> ConnectionLogEntry {
+        ConnectionLogEntry {
+            timestamp_ms,
+            feed: Feed::Kline,
+            kind: EventKind::Connecting,
+        }
+    }
+
+    #[test]
+    fn keeps_the_newest_entries_newest_first() {
+        let mut log = EventLog::new(3);
+        for at in 1..=5 {
+            log.push(entry(at));
+        }
+        let kept: Vec<u64> = log.entries().map(|e| e.timestamp_ms).collect();
+        assert_eq!(kept, vec![5, 4, 3]);
+
+        log.set_capacity(2);
+        let kept: Vec<u64> = log.entries().map(|e| e.timestamp_ms).collect();
+        assert_eq!(kept, vec![5, 4]);
+    }
+
+    #[test]
+    fn describes_reconnect_downtime() {
+        let reconnected = ConnectionLogEntry {
+            kind: EventKind::Reconnected(Duration::from_millis(12_400)),
+            ..entry(0)
+        };
+        assert_eq!(reconnected.describe(), "reconnected after 12s");
+    }
+}
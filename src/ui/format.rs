@@ -0,0 +1,238 @@
+use serde::{Deserialize, Serialize};
+
+/// How volumes and quantities are shown; prices always use full precision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum VolumeFormat {
+    /// Grouped digits, e.g. `1,234,567.89`.
+    Full,
+    /// Three significant digits with a unit suffix, e.g. `1.23M`.
+    #[default]
+    Compact,
+}
+
+/// Most decimals a price below 1 is shown with by default.
+pub const DEFAULT_MAX_PRICE_DECIMALS: usize = 8;
+/// Significant digits kept for prices below 1.
+const SMALL_PRICE_DIGITS: usize = 4;
+
+const COMPACT_UNITS: [(&str, f64); 5] =
+    [("", 1.0), ("K", 1e3), ("M", 1e6), ("B", 1e9), ("T", 1e12)];
+
+/// Formats a volume with `decimals` places in [`VolumeFormat::Full`] or via
+/// [`format_compact`] otherwise.
+pub fn format_volume(value: f64, decimals: usize, format: VolumeFormat) -> String {
+    match format {
+        VolumeFormat::Full => format_price(value, decimals),
+        VolumeFormat::Compact => format_compact(value),
+    }
+}
+
+/// Shortens a magnitude to about three significant digits with a `K`/`M`/
+/// `B`/`T` suffix: `45.6K`, `1.23M`, `2.1B`. Values that round up to the
+/// next unit move to it (`999_999` is `1M`, not `1000K`), and fractional
+/// quantities below 1 keep four decimals so small fills stay visible.
+pub fn format_compact(value: f64) -> String {
+    if !value.is_finite() {
+        return format!("{value}");
+    }
+
+    let magnitude = value.abs();
+    let mut unit_idx = COMPACT_UNITS
+        .iter()
+        .rposition(|(_, scale)| magnitude >= *scale)
+        .unwrap_or(0);
+
+    let (mut scaled, mut decimals) = compact_parts(magnitude, unit_idx);
+    if scaled >= 1000.0 && unit_idx + 1 < COMPACT_UNITS.len() {
+        unit_idx += 1;
+        (scaled, decimals) = compact_parts(magnitude, unit_idx);
+    }
+
+    let mut text = format!("{:.*}", decimals, scaled);
+    if text.contains('.') {
+        text.truncate(text.trim_end_matches('0').trim_end_matches('.').len());
+    }
+    let sign = if value < 0.0 && text.bytes().any(|b| (b'1'..=b'9').contains(&b)) {
+        "-"
+    } else {
+        ""
+    };
+    format!("{sign}{text}{}", COMPACT_UNITS[unit_idx].0)
+}
+
+/// Returns the value scaled into `COMPACT_UNITS[unit_idx]`, already rounded
+/// to the decimals it will be shown with.
+fn compact_parts(magnitude: f64, unit_idx: usize) -> (f64, usize) {
+    let scaled = magnitude / COMPACT_UNITS[unit_idx].1;
+    let decimals = if scaled >= 100.0 {
+        0
+    } else if scaled >= 10.0 {
+        1
+    } else if scaled >= 1.0 || unit_idx > 0 {
+        2
+    } else {
+        4
+    };
+    let factor = 10f64.powi(decimals as i32);
+    ((scaled * factor).round() / factor, decimals)
+}
+
+/// Formats a price with `decimals` places and comma thousands separators,
+/// e.g. `69,420.50`. Values below 1000 come out unchanged apart from the
+/// rounding, so small prices like `0.000123` stay readable.
+pub fn format_price(value: f64, decimals: usize) -> String {
+    if !value.is_finite() {
+        return format!("{value}");
+    }
+
+    let formatted = format!("{:.*}", decimals, value.abs());
+    let (int_part, frac_part) = match formatted.split_once('.') {
+        Some((int_part, frac_part)) => (int_part, Some(frac_part)),
+        None => (formatted.as_str(), None),
+    };
+
+    let mut grouped = String::with_capacity(formatted.len() + int_part.len() / 3 + 1);
+    if value < 0.0 && formatted.bytes().any(|b| b.is_ascii_digit() && b != b'0') {
+        grouped.push('-');
+    }
+    for (idx, ch) in int_part.chars().enumerate() {
+        if idx > 0 && (int_part.len() - idx) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(ch);
+    }
+    if let Some(frac_part) = frac_part {
+        grouped.push('.');
+        grouped.push_str(frac_part);
+    }
+    grouped
+}
+
+/// Decimals that keep a price legible: two from 1 upward, and below 1
+/// enough for four significant digits (`0.4512`, `0.00002431`), capped at
+/// `max_decimals`.
+pub fn price_decimals(value: f64, max_decimals: usize) -> usize {
+    let magnitude = value.abs();
+    if !magnitude.is_finite() || magnitude == 0.0 || magnitude >= 1.0 {
+        return 2;
+    }
+    let leading_zeros = (-magnitude.log10()).ceil() as usize - 1;
+    (leading_zeros + SMALL_PRICE_DIGITS).clamp(2, max_decimals.max(2))
+}
+
+/// Decimals a symbol quotes in, read off the close strings as received:
+/// the most digits any of them has after the point once Binance's zero
+/// padding is trimmed, so `["64210.51000000", "64210.50000000"]` gives 2.
+pub fn infer_precision(closes: &[&str]) -> usize {
+    closes
+        .iter()
+        .filter_map(|close| close.split_once('.'))
+        .map(|(_, frac)| frac.trim_end_matches('0').len())
+        .max()
+        .unwrap_or(0)
+}
+
+/// Decimals for a price on a symbol known to quote in `precision` decimals,
+/// capped at `max_decimals`; [`price_decimals`] when the precision is unknown.
+pub fn symbol_price_decimals(value: f64, precision: Option<usize>, max_decimals: usize) -> usize {
+    match precision {
+        Some(precision) => precision.min(max_decimals.max(2)),
+        None => price_decimals(value, max_decimals),
+    }
+}
+
+/// [`format_price`] with the decimals picked by [`price_decimals`].
+pub fn format_display_price(value: f64, max_decimals: usize) -> String {
+    format_price(value, price_decimals(value, max_decimals))
+}
+
+/// Like [`format_price`] but always signed, for changes: `+1,250.00`.
+pub fn format_signed_price(value: f64, decimals: usize) -> String {
+    let formatted = format_price(value, decimals);
+    if formatted.starts_with('-') {
+        formatted
+    } else {
+        format!("+{formatted}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups_thousands_at_boundaries() {
+        assert_eq!(format_price(999.9, 2), "999.90");
+        assert_eq!(format_price(999.999, 2), "1,000.00");
+        assert_eq!(format_price(1000.0, 2), "1,000.00");
+        assert_eq!(format_price(1_234_567.89, 2), "1,234,567.89");
+        assert_eq!(format_price(0.000123, 6), "0.000123");
+        assert_eq!(format_price(69_420.5, 0), "69,420");
+    }
+
+    #[test]
+    fn compacts_at_magnitude_boundaries() {
+        assert_eq!(format_compact(999.0), "999");
+        assert_eq!(format_compact(1000.0), "1K");
+        assert_eq!(format_compact(45_600.0), "45.6K");
+        assert_eq!(format_compact(999_499.0), "999K");
+        assert_eq!(format_compact(999_999.0), "1M");
+        assert_eq!(format_compact(1_000_000.0), "1M");
+        assert_eq!(format_compact(1_234_567.89), "1.23M");
+        assert_eq!(format_compact(2_100_000_000.0), "2.1B");
+        assert_eq!(format_compact(5e15), "5000T");
+    }
+
+    #[test]
+    fn compact_rounding_and_small_values() {
+        assert_eq!(format_compact(999.999), "1K");
+        assert_eq!(format_compact(12.345), "12.3");
+        assert_eq!(format_compact(1.004), "1");
+        assert_eq!(format_compact(0.00123), "0.0012");
+        assert_eq!(format_compact(0.0), "0");
+        assert_eq!(format_compact(-1_500.0), "-1.5K");
+        assert_eq!(format_compact(-0.00001), "0");
+        assert_eq!(
+            format_volume(1_234_567.891, 2, VolumeFormat::Full),
+            "1,234,567.89"
+        );
+    }
+
+    #[test]
+    fn handles_negative_and_signed_values() {
+        assert_eq!(format_price(-1234.5, 2), "-1,234.50");
+        assert_eq!(format_price(-0.001, 2), "0.00");
+        assert_eq!(format_signed_price(1250.0, 2), "+1,250.00");
+        assert_eq!(format_signed_price(-12.345, 2), "-12.35");
+    }
+
+    #[test]
+    fn small_prices_keep_significant_digits() {
+        assert_eq!(format_display_price(69_420.5, 8), "69,420.50");
+        assert_eq!(format_display_price(1.0, 8), "1.00");
+        assert_eq!(format_display_price(0.4512, 8), "0.4512");
+        assert_eq!(format_display_price(0.1, 8), "0.1000");
+        assert_eq!(format_display_price(0.00002431, 8), "0.00002431");
+        assert_eq!(format_display_price(0.00002431, 6), "0.000024");
+        assert_eq!(format_display_price(-0.0123, 8), "-0.01230");
+        assert_eq!(price_decimals(0.0, 8), 2);
+        assert_eq!(price_decimals(f64::NAN, 8), 2);
+    }
+
+    #[test]
+    fn infers_precision_from_close_strings() {
+        assert_eq!(infer_precision(&["64210.51000000", "64210.50000000"]), 2);
+        assert_eq!(infer_precision(&["1.23450000", "1.20000000"]), 4);
+        assert_eq!(infer_precision(&["0.00002431"]), 8);
+        assert_eq!(infer_precision(&["100.00000000", "101"]), 0);
+        assert_eq!(infer_precision(&[]), 0);
+    }
+
+    #[test]
+    fn known_precision_overrides_the_heuristic() {
+        assert_eq!(symbol_price_decimals(1.2345, Some(4), 8), 4);
+        assert_eq!(symbol_price_decimals(0.00002431, Some(8), 6), 6);
+        assert_eq!(symbol_price_decimals(64_210.0, Some(0), 8), 0);
+        assert_eq!(symbol_price_decimals(0.4512, None, 8), 4);
+    }
+}
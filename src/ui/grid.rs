@@ -0,0 +1,156 @@
+use ratatui::{buffer::Buffer, layout::Rect, style::Color};
+use serde::{Deserialize, Serialize};
+
+/// Rows between horizontal gridlines by default; vertical lines are twice as
+/// far apart so the cells come out roughly square.
+pub const DEFAULT_GRID_SPACING: u16 = 6;
+
+/// Which gridlines are drawn behind the candles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum GridMode {
+    Off,
+    #[default]
+    Horizontal,
+    /// Horizontal and vertical lines.
+    Full,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum GridLineStyle {
+    #[default]
+    Dotted,
+    Dashed,
+    Solid,
+}
+
+impl GridLineStyle {
+    fn horizontal(self) -> char {
+        match self {
+            GridLineStyle::Dotted => '┈',
+            GridLineStyle::Dashed => '╌',
+            GridLineStyle::Solid => '─',
+        }
+    }
+
+    fn vertical(self) -> char {
+        match self {
+            GridLineStyle::Dotted => '┊',
+            GridLineStyle::Dashed => '╎',
+            GridLineStyle::Solid => '│',
+        }
+    }
+
+    fn crossing(self) -> char {
+        match self {
+            GridLineStyle::Solid => '┼',
+            _ => self.horizontal(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Grid {
+    pub mode: GridMode,
+    pub style: GridLineStyle,
+    pub spacing: u16,
+}
+
+impl Default for Grid {
+    fn default() -> Self {
+        Self {
+            mode: GridMode::default(),
+            style: GridLineStyle::default(),
+            spacing: DEFAULT_GRID_SPACING,
+        }
+    }
+}
+
+impl Grid {
+    /// Draws the grid over `area`, counted from its bottom-right corner so the
+    /// lines stay put as the window grows. Only blank cells are written, so
+    /// anything already drawn there shows through.
+    pub fn draw(&self, buffer: &mut Buffer, area: Rect) {
+        if self.mode == GridMode::Off || area.width == 0 || area.height == 0 {
+            return;
+        }
+        let spacing = self.spacing.max(1);
+        let column_spacing = spacing.saturating_mul(2);
+        let bottom = area.y + area.height - 1;
+        let right = area.x + area.width - 1;
+
+        for y in area.y..=bottom {
+            let on_row = (bottom - y) % spacing == spacing - 1;
+            for x in area.x..=right {
+                let on_column = self.mode == GridMode::Full
+                    && (right - x) % column_spacing == column_spacing - 1;
+                let glyph = match (on_row, on_column) {
+                    (true, true) => self.style.crossing(),
+                    (true, false) => self.style.horizontal(),
+                    (false, true) => self.style.vertical(),
+                    (false, false) => continue,
+                };
+                let cell = &mut buffer[(x, y)];
+                if cell.symbol() == " " {
+                    cell.set_char(glyph).set_fg(Color::DarkGray);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(buffer: &Buffer) -> Vec<String> {
+        (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol().to_string())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn draws_lines_at_the_spacing_around_existing_glyphs() {
+        let area = Rect::new(0, 0, 6, 5);
+        let mut buffer = Buffer::empty(area);
+        buffer[(2, 3)].set_char('█');
+        Grid {
+            mode: GridMode::Full,
+            style: GridLineStyle::Solid,
+            spacing: 2,
+        }
+        .draw(&mut buffer, area);
+        assert_eq!(
+            rows(&buffer),
+            vec!["  │   ", "──┼───", "  │   ", "──█───", "  │   "]
+        );
+
+        let area = Rect::new(0, 0, 4, 8);
+        let mut buffer = Buffer::empty(area);
+        Grid::default().draw(&mut buffer, area);
+        assert_eq!(
+            rows(&buffer),
+            vec![
+                "    ",
+                "    ",
+                "┈┈┈┈",
+                "    ",
+                "    ",
+                "    ",
+                "    ",
+                "    "
+            ]
+        );
+
+        let mut buffer = Buffer::empty(area);
+        Grid {
+            mode: GridMode::Off,
+            ..Grid::default()
+        }
+        .draw(&mut buffer, area);
+        assert!(rows(&buffer).iter().all(|row| row.trim().is_empty()));
+    }
+}
@@ -0,0 +1,144 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Bucket width as a fraction of the first traded price (5 bps).
+const BUCKET_FRACTION: f64 = 0.0005;
+const HALF_LIFE: Duration = Duration::from_secs(60);
+const MAX_BUCKETS: usize = 256;
+/// Buckets decayed below this much volume are dropped.
+const MIN_WEIGHT: f64 = 1e-9;
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    weight: f64,
+    updated: Instant,
+}
+
+/// Rolling, time-decayed traded volume per price bucket. Each bucket halves
+/// every `HALF_LIFE`, so heavy recent activity stands out and old activity
+/// fades away.
+#[derive(Debug, Clone)]
+pub struct TradeHeatmap {
+    buckets: HashMap<i64, Bucket>,
+    bucket_size: Option<f64>,
+    half_life: Duration,
+    max_buckets: usize,
+    pub visible: bool,
+}
+
+impl TradeHeatmap {
+    pub fn new() -> Self {
+        Self {
+            buckets: HashMap::new(),
+            bucket_size: None,
+            half_life: HALF_LIFE,
+            max_buckets: MAX_BUCKETS,
+            visible: false,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.buckets.clear();
+        self.bucket_size = None;
+    }
+
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+    }
+
+    fn decay_factor(&self, from: Instant, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(from).as_secs_f64();
+        0.5f64.powf(elapsed / self.half_life.as_secs_f64())
+    }
+
+    pub fn record(&mut self, price: f64, quantity: f64, now: Instant) {
+        if !price.is_finite() || price <= 0.0 || !quantity.is_finite() || quantity <= 0.0 {
+            return;
+        }
+
+        let bucket_size = *self.bucket_size.get_or_insert(price * BUCKET_FRACTION);
+        let key = (price / bucket_size).floor() as i64;
+        let weight = self
+            .buckets
+            .get(&key)
+            .map_or(0.0, |b| b.weight * self.decay_factor(b.updated, now));
+        self.buckets.insert(
+            key,
+            Bucket {
+                weight: weight + quantity,
+                updated: now,
+            },
+        );
+
+        if self.buckets.len() > self.max_buckets {
+            self.prune(now);
+        }
+    }
+
+    /// Drops faded buckets, then the weakest ones until back under the cap.
+    fn prune(&mut self, now: Instant) {
+        let mut weights: Vec<(i64, f64)> = self
+            .buckets
+            .iter()
+            .map(|(key, b)| (*key, b.weight * self.decay_factor(b.updated, now)))
+            .collect();
+        weights.sort_by(|a, b| b.1.total_cmp(&a.1));
+        for (rank, (key, weight)) in weights.into_iter().enumerate() {
+            if rank >= self.max_buckets || weight < MIN_WEIGHT {
+                self.buckets.remove(&key);
+            }
+        }
+    }
+
+    /// `(bucket mid price, decayed volume)` for every live bucket at `now`.
+    pub fn levels(&self, now: Instant) -> Vec<(f64, f64)> {
+        let Some(bucket_size) = self.bucket_size else {
+            return Vec::new();
+        };
+        self.buckets
+            .iter()
+            .map(|(key, b)| {
+                let mid = (*key as f64 + 0.5) * bucket_size;
+                (mid, b.weight * self.decay_factor(b.updated, now))
+            })
+            .filter(|(_, weight)| *weight >= MIN_WEIGHT)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buckets_accumulate_and_decay_by_half_life() {
+        let start = Instant::now();
+        let mut heat = TradeHeatmap::new();
+        heat.record(100.0, 1.0, start);
+        heat.record(100.01, 2.0, start);
+        heat.record(101.0, 1.0, start);
+
+        let mut levels = heat.levels(start);
+        levels.sort_by(|a, b| a.0.total_cmp(&b.0));
+        assert_eq!(levels.len(), 2);
+        assert!((levels[0].0 - 100.025).abs() < 1e-9);
+        assert!((levels[0].1 - 3.0).abs() < 1e-9);
+
+        let later = heat.levels(start + HALF_LIFE);
+        let total: f64 = later.iter().map(|(_, w)| w).sum();
+        assert!((total - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn bucket_count_stays_bounded() {
+        let start = Instant::now();
+        let mut heat = TradeHeatmap::new();
+        heat.max_buckets = 4;
+        for i in 0..20 {
+            heat.record(100.0 + i as f64, 1.0 + i as f64, start);
+        }
+        let levels = heat.levels(start);
+        assert_eq!(levels.len(), 4);
+        assert!(levels.iter().all(|(_, w)| *w >= 17.0));
+    }
+}
@@ -1,6 +1,139 @@
 use crate::data::Candle;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+
+pub const DEFAULT_SMA_PERIOD: usize = 20;
+pub const DEFAULT_RSI_PERIOD: usize = 14;
+pub const DEFAULT_ROC_PERIOD: usize = 12;
+
+/// Which overlays a symbol shows and with what periods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IndicatorPrefs {
+    pub show_sma: bool,
+    pub show_rsi: bool,
+    pub sma_period: usize,
+    pub rsi_period: usize,
+}
+
+impl Default for IndicatorPrefs {
+    fn default() -> Self {
+        Self {
+            show_sma: false,
+            show_rsi: false,
+            sma_period: DEFAULT_SMA_PERIOD,
+            rsi_period: DEFAULT_RSI_PERIOD,
+        }
+    }
+}
+
+impl IndicatorPrefs {
+    /// Periods clamped to something the chart can draw.
+    pub fn sanitized(self) -> Self {
+        Self {
+            sma_period: self.sma_period.clamp(2, 200),
+            rsi_period: self.rsi_period.clamp(2, 100),
+            ..self
+        }
+    }
+}
+
+/// An overlay kind that can be switched on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IndicatorKind {
+    Sma,
+    Rsi,
+}
+
+/// One overlay that is on, with its period.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndicatorConfig {
+    pub kind: IndicatorKind,
+    pub period: usize,
+}
+
+impl IndicatorPrefs {
+    /// Prefs with the listed overlays on. A kind listed twice keeps its
+    /// first period; kinds not listed are off at their default period.
+    pub fn from_active(active: &[IndicatorConfig]) -> Self {
+        let mut prefs = Self::default();
+        for kind in [IndicatorKind::Sma, IndicatorKind::Rsi] {
+            let Some(config) = active.iter().find(|config| config.kind == kind) else {
+                continue;
+            };
+            match kind {
+                IndicatorKind::Sma => {
+                    prefs.show_sma = true;
+                    prefs.sma_period = config.period;
+                }
+                IndicatorKind::Rsi => {
+                    prefs.show_rsi = true;
+                    prefs.rsi_period = config.period;
+                }
+            }
+        }
+        prefs
+    }
+
+    /// The overlays that are on, as saved in `active_indicators`.
+    pub fn active(&self) -> Vec<IndicatorConfig> {
+        let mut active = Vec::new();
+        if self.show_sma {
+            active.push(IndicatorConfig {
+                kind: IndicatorKind::Sma,
+                period: self.sma_period,
+            });
+        }
+        if self.show_rsi {
+            active.push(IndicatorConfig {
+                kind: IndicatorKind::Rsi,
+                period: self.rsi_period,
+            });
+        }
+        active
+    }
+}
+
+/// What series the volatility and return readouts are computed on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum IndicatorBasis {
+    /// Raw closes, as quoted.
+    #[default]
+    Price,
+    /// Natural log of each close, so equal moves in percent weigh the same at
+    /// any price level.
+    LogPrice,
+    /// Log return from the previous close; the first value has none.
+    LogReturn,
+}
+
+impl IndicatorBasis {
+    pub fn is_log(self) -> bool {
+        self != IndicatorBasis::Price
+    }
+}
+
+/// `closes` on `basis`. Non-positive closes have no logarithm, so they and
+/// any return touching them come out as `None`.
+pub fn transform_closes(closes: &[f64], basis: IndicatorBasis) -> Vec<Option<f64>> {
+    let ln = |close: f64| (close > 0.0).then(|| close.ln());
+    match basis {
+        IndicatorBasis::Price => closes.iter().map(|close| Some(*close)).collect(),
+        IndicatorBasis::LogPrice => closes.iter().map(|close| ln(*close)).collect(),
+        IndicatorBasis::LogReturn => {
+            let logs: Vec<Option<f64>> = closes.iter().map(|close| ln(*close)).collect();
+            std::iter::once(None)
+                .chain(logs.windows(2).map(|pair| Some(pair[1]? - pair[0]?)))
+                .take(closes.len())
+                .collect()
+        }
+    }
+}
+
+/// Indicator preferences keyed by symbol; symbols without an entry use the
+/// defaults.
+pub type SymbolIndicators = BTreeMap<String, IndicatorPrefs>;
 
-#[allow(dead_code)]
 pub fn calculate_sma(candles: &[Candle], period: usize) -> Vec<Option<f64>> {
     if period == 0 {
         return vec![None; candles.len()];
@@ -24,46 +157,866 @@ pub fn calculate_sma(candles: &[Candle], period: usize) -> Vec<Option<f64>> {
     sma
 }
 
+/// Rate of change: percent move of each close from the close `period`
+/// candles earlier. `None` until there is one, or when that close is zero.
+pub fn calculate_roc(candles: &[Candle], period: usize) -> Vec<Option<f64>> {
+    let closes: Vec<Option<f64>> = candles.iter().map(|c| c.close.parse().ok()).collect();
+    (0..closes.len())
+        .map(|idx| {
+            let base = closes[idx.checked_sub(period.max(1))?]?;
+            let close = closes[idx]?;
+            (base != 0.0).then(|| (close - base) / base * 100.0)
+        })
+        .collect()
+}
+
 #[allow(dead_code)]
 pub fn calculate_rsi(candles: &[Candle], period: usize) -> Vec<Option<f64>> {
     if period == 0 {
         return vec![None; candles.len()];
     }
 
-    if candles.len() < period + 1 {
-        return vec![None; candles.len()];
+    let mut accum = RsiAccumulator::default();
+    candles
+        .iter()
+        .map(|c| {
+            let (next, value) = accum.next(c.close.parse().unwrap_or(0.0), period);
+            accum = next;
+            value
+        })
+        .collect()
+}
+
+/// Wilder-smoothed average true range over `(high, low, close)` bars: the
+/// first value is the mean true range of the first `period` bars.
+pub fn calculate_atr(bars: &[(f64, f64, f64)], period: usize) -> Vec<Option<f64>> {
+    if period == 0 {
+        return vec![None; bars.len()];
+    }
+
+    let mut atr = Vec::with_capacity(bars.len());
+    let mut prev_close: Option<f64> = None;
+    let mut tr_sum = 0.0;
+    let mut current: Option<f64> = None;
+
+    for (idx, (high, low, close)) in bars.iter().enumerate() {
+        let true_range = match prev_close {
+            Some(prev) => (high - low)
+                .max((high - prev).abs())
+                .max((low - prev).abs()),
+            None => high - low,
+        };
+        prev_close = Some(*close);
+
+        current = match current {
+            Some(avg) => Some((avg * (period - 1) as f64 + true_range) / period as f64),
+            None => {
+                tr_sum += true_range;
+                (idx + 1 == period).then(|| tr_sum / period as f64)
+            }
+        };
+        atr.push(current);
+    }
+
+    atr
+}
+
+/// Exponential moving average seeded with the simple mean of the first
+/// `period` values.
+pub fn calculate_ema(values: &[f64], period: usize) -> Vec<Option<f64>> {
+    if period == 0 {
+        return vec![None; values.len()];
+    }
+
+    let alpha = 2.0 / (period as f64 + 1.0);
+    let mut sum = 0.0;
+    let mut current: Option<f64> = None;
+    values
+        .iter()
+        .enumerate()
+        .map(|(idx, value)| {
+            current = match current {
+                Some(ema) => Some(ema + alpha * (value - ema)),
+                None => {
+                    sum += value;
+                    (idx + 1 == period).then(|| sum / period as f64)
+                }
+            };
+            current
+        })
+        .collect()
+}
+
+/// Upper and lower lines around a middle one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Band {
+    pub middle: f64,
+    pub upper: f64,
+    pub lower: f64,
+}
+
+/// Bollinger Bands: the `period` mean of `closes` ± `std_devs` population
+/// standard deviations.
+pub fn calculate_bollinger(closes: &[f64], period: usize, std_devs: f64) -> Vec<Option<Band>> {
+    if period == 0 {
+        return vec![None; closes.len()];
+    }
+
+    (0..closes.len())
+        .map(|idx| {
+            let start = (idx + 1).checked_sub(period)?;
+            let window = &closes[start..=idx];
+            let mean = window.iter().sum::<f64>() / period as f64;
+            let variance = window.iter().map(|c| (c - mean).powi(2)).sum::<f64>() / period as f64;
+            let width = std_devs * variance.sqrt();
+            Some(Band {
+                middle: mean,
+                upper: mean + width,
+                lower: mean - width,
+            })
+        })
+        .collect()
+}
+
+/// Keltner Channels over `(high, low, close)` bars: the `period` EMA of the
+/// close ± `atr_multiple` times the `period` ATR.
+pub fn calculate_keltner(
+    bars: &[(f64, f64, f64)],
+    period: usize,
+    atr_multiple: f64,
+) -> Vec<Option<Band>> {
+    let closes: Vec<f64> = bars.iter().map(|(_, _, close)| *close).collect();
+    calculate_ema(&closes, period)
+        .into_iter()
+        .zip(calculate_atr(bars, period))
+        .map(|(ema, atr)| {
+            let (middle, atr) = (ema?, atr?);
+            Some(Band {
+                middle,
+                upper: middle + atr_multiple * atr,
+                lower: middle - atr_multiple * atr,
+            })
+        })
+        .collect()
+}
+
+/// Where the Bollinger Bands sit inside the Keltner Channels: volatility
+/// has contracted, which often comes before a move. Indices where either
+/// is missing aren't a squeeze.
+pub fn is_squeeze(bb: &[Option<Band>], kc: &[Option<Band>]) -> Vec<bool> {
+    bb.iter()
+        .zip(kc)
+        .map(|pair| match pair {
+            (Some(bb), Some(kc)) => bb.upper < kc.upper && bb.lower > kc.lower,
+            _ => false,
+        })
+        .collect()
+}
+
+/// Indices where a squeeze has just ended, the signal traders act on.
+pub fn squeeze_releases(squeeze: &[bool]) -> Vec<bool> {
+    (0..squeeze.len())
+        .map(|idx| idx > 0 && squeeze[idx - 1] && !squeeze[idx])
+        .collect()
+}
+
+/// ATR as a percent of price, comparable across differently priced assets.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PercentRange {
+    /// Latest ATR divided by the latest close, in percent.
+    pub value: f64,
+    /// Share of the window's readings at or below `value`, 0–100.
+    pub percentile: f64,
+    /// Mean reading over the window.
+    pub average: f64,
+}
+
+/// Normalized volatility of `(high, low, close)` bars; `None` until the ATR
+/// has `period` bars to seed from. On a log basis the ATR runs over log
+/// prices, where it is already a fraction of price, instead of being divided
+/// by the close afterwards.
+pub fn percent_range(
+    bars: &[(f64, f64, f64)],
+    period: usize,
+    basis: IndicatorBasis,
+) -> Option<PercentRange> {
+    let readings: Vec<f64> = if basis.is_log() {
+        let column = |pick: fn(&(f64, f64, f64)) -> f64| {
+            let values: Vec<f64> = bars.iter().map(pick).collect();
+            transform_closes(&values, IndicatorBasis::LogPrice)
+        };
+        let log_bars: Vec<(f64, f64, f64)> = column(|bar| bar.0)
+            .into_iter()
+            .zip(column(|bar| bar.1))
+            .zip(column(|bar| bar.2))
+            .filter_map(|((high, low), close)| Some((high?, low?, close?)))
+            .collect();
+        calculate_atr(&log_bars, period)
+            .into_iter()
+            .filter_map(|atr| atr.filter(|atr| atr.is_finite()).map(|atr| atr * 100.0))
+            .collect()
+    } else {
+        calculate_atr(bars, period)
+            .iter()
+            .zip(bars)
+            .filter_map(|(atr, (_, _, close))| {
+                let atr = (*atr)?;
+                (*close > 0.0 && atr.is_finite()).then(|| atr / close * 100.0)
+            })
+            .collect()
+    };
+    let value = *readings.last()?;
+    let at_or_below = readings.iter().filter(|reading| **reading <= value).count();
+    Some(PercentRange {
+        value,
+        percentile: at_or_below as f64 / readings.len() as f64 * 100.0,
+        average: readings.iter().sum::<f64>() / readings.len() as f64,
+    })
+}
+
+/// Drops non-finite readings (from NaN/inf closes or overflowing sums) so
+/// nothing downstream has to guard against them when mapping to the screen.
+pub fn finite_only(values: impl IntoIterator<Item = Option<f64>>) -> Vec<Option<f64>> {
+    values
+        .into_iter()
+        .map(|value| value.filter(|v| v.is_finite()))
+        .collect()
+}
+
+/// A fast average crossing a slow one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cross {
+    /// Fast crossed above slow.
+    Golden,
+    /// Fast crossed below slow.
+    Death,
+}
+
+/// The cross, if any, completed at each index: the fast series was at or
+/// below (above) the slow one on the previous reading and is above (at or
+/// below) it now. Indices where either series has no value never cross.
+pub fn crossovers(fast: &[Option<f64>], slow: &[Option<f64>]) -> Vec<Option<Cross>> {
+    let len = fast.len().min(slow.len());
+    (0..len)
+        .map(|idx| {
+            let prev = idx.checked_sub(1)?;
+            let (Some(fast_prev), Some(slow_prev), Some(fast_now), Some(slow_now)) =
+                (fast[prev], slow[prev], fast[idx], slow[idx])
+            else {
+                return None;
+            };
+            match (fast_prev > slow_prev, fast_now > slow_now) {
+                (false, true) => Some(Cross::Golden),
+                (true, false) => Some(Cross::Death),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+fn rsi_from_averages(avg_gain: f64, avg_loss: f64) -> f64 {
+    if avg_loss == 0.0 {
+        100.0
+    } else {
+        let rs = avg_gain / avg_loss;
+        100.0 - (100.0 / (1.0 + rs))
+    }
+}
+
+/// Wilder-smoothed RSI state: seeded with a simple average over the first
+/// `period` changes, then updated as `avg = (avg * (period - 1) + x) / period`.
+#[derive(Debug, Clone, Copy, Default)]
+struct RsiAccumulator {
+    prev_close: Option<f64>,
+    changes: usize,
+    gain_sum: f64,
+    loss_sum: f64,
+    avg_gain: f64,
+    avg_loss: f64,
+}
+
+impl RsiAccumulator {
+    fn next(mut self, close: f64, period: usize) -> (Self, Option<f64>) {
+        let Some(prev_close) = self.prev_close.replace(close) else {
+            return (self, None);
+        };
+
+        let change = close - prev_close;
+        let (gain, loss) = if change > 0.0 {
+            (change, 0.0)
+        } else {
+            (0.0, -change)
+        };
+        self.changes += 1;
+
+        if self.changes < period {
+            self.gain_sum += gain;
+            self.loss_sum += loss;
+            return (self, None);
+        }
+
+        if self.changes == period {
+            self.avg_gain = (self.gain_sum + gain) / period as f64;
+            self.avg_loss = (self.loss_sum + loss) / period as f64;
+        } else {
+            let weight = (period - 1) as f64;
+            self.avg_gain = (self.avg_gain * weight + gain) / period as f64;
+            self.avg_loss = (self.avg_loss * weight + loss) / period as f64;
+        }
+
+        (self, Some(rsi_from_averages(self.avg_gain, self.avg_loss)))
+    }
+}
+
+/// Streaming SMA/RSI over a chart's candle buffer.
+///
+/// Every candle except the last is treated as committed and folded into the
+/// running accumulators exactly once. The last candle is committed too once
+/// the stream flags it closed; until then it is in progress and its values
+/// are derived from the accumulators on each sync without mutating them.
+/// Readouts (`latest_*`) only move when a candle is committed.
+#[derive(Debug, Clone)]
+pub struct IndicatorState {
+    sma_period: usize,
+    rsi_period: usize,
+    open_times: VecDeque<u64>,
+    sma: VecDeque<Option<f64>>,
+    rsi: VecDeque<Option<f64>>,
+    sma_window: VecDeque<f64>,
+    sma_sum: f64,
+    rsi_accum: RsiAccumulator,
+    live_sma: Option<f64>,
+    live_rsi: Option<f64>,
+    has_live: bool,
+}
+
+impl IndicatorState {
+    pub fn new(sma_period: usize, rsi_period: usize) -> Self {
+        Self {
+            sma_period,
+            rsi_period,
+            open_times: VecDeque::new(),
+            sma: VecDeque::new(),
+            rsi: VecDeque::new(),
+            sma_window: VecDeque::new(),
+            sma_sum: 0.0,
+            rsi_accum: RsiAccumulator::default(),
+            live_sma: None,
+            live_rsi: None,
+            has_live: false,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new(self.sma_period, self.rsi_period);
+    }
+
+    pub fn sync(&mut self, candles: &VecDeque<Candle>) {
+        let Some(front) = candles.front().map(|c| c.open_time) else {
+            self.reset();
+            return;
+        };
+
+        let had_history = self.rsi_accum.prev_close.is_some();
+        while self.open_times.front().is_some_and(|t| *t < front) {
+            self.open_times.pop_front();
+            self.sma.pop_front();
+            self.rsi.pop_front();
+        }
+
+        let last_idx = candles.len() - 1;
+        let commit_end = if candles[last_idx].is_closed {
+            candles.len()
+        } else {
+            last_idx
+        };
+
+        let committed = self.open_times.len();
+        let is_prefix = committed <= commit_end
+            && self.open_times.front().is_none_or(|t| *t == front)
+            && self
+                .open_times
+                .back()
+                .is_none_or(|t| *t == candles[committed - 1].open_time);
+        if !is_prefix || (committed == 0 && had_history) {
+            self.reset();
+        }
+
+        for candle in candles.range(self.open_times.len()..commit_end) {
+            self.commit(candle);
+        }
+
+        if commit_end == candles.len() {
+            self.live_sma = None;
+            self.live_rsi = None;
+            self.has_live = false;
+            return;
+        }
+
+        let live_close = candles[last_idx].close.parse().unwrap_or(0.0);
+        self.live_sma = self.sma_with(live_close);
+        self.live_rsi = self.rsi_accum.next(live_close, self.rsi_period).1;
+        self.has_live = true;
     }
 
-    let mut rsi = vec![None; period];
-    let mut gains = Vec::new();
-    let mut losses = Vec::new();
+    fn commit(&mut self, candle: &Candle) {
+        let close = candle.close.parse().unwrap_or(0.0);
+        let sma = self.sma_with(close);
+
+        if self.sma_period > 0 {
+            self.sma_window.push_back(close);
+            self.sma_sum += close;
+            if self.sma_window.len() > self.sma_period {
+                self.sma_sum -= self.sma_window.pop_front().unwrap_or(0.0);
+            }
+        }
+
+        let rsi = if self.rsi_period > 0 {
+            let (next, value) = self.rsi_accum.next(close, self.rsi_period);
+            self.rsi_accum = next;
+            value
+        } else {
+            None
+        };
 
-    for i in 1..candles.len() {
-        let prev_close: f64 = candles[i - 1].close.parse().unwrap_or(0.0);
-        let curr_close: f64 = candles[i].close.parse().unwrap_or(0.0);
-        let change = curr_close - prev_close;
+        self.open_times.push_back(candle.open_time);
+        self.sma.push_back(sma);
+        self.rsi.push_back(rsi);
+    }
 
-        if change > 0.0 {
-            gains.push(change);
-            losses.push(0.0);
+    fn sma_with(&self, close: f64) -> Option<f64> {
+        if self.sma_period == 0 || self.sma_window.len() + 1 < self.sma_period {
+            return None;
+        }
+
+        let dropped = if self.sma_window.len() == self.sma_period {
+            self.sma_window.front().copied().unwrap_or(0.0)
         } else {
-            gains.push(0.0);
-            losses.push(-change);
+            0.0
+        };
+        Some((self.sma_sum - dropped + close) / self.sma_period as f64)
+    }
+
+    fn live_entry(&self, value: Option<f64>) -> Option<Option<f64>> {
+        self.has_live.then_some(value)
+    }
+
+    pub fn sma_period(&self) -> usize {
+        self.sma_period
+    }
+
+    pub fn rsi_period(&self) -> usize {
+        self.rsi_period
+    }
+
+    pub fn sma_values(&self) -> Vec<Option<f64>> {
+        let live = self.live_entry(self.live_sma);
+        finite_only(self.sma.iter().copied().chain(live))
+    }
+
+    pub fn rsi_values(&self) -> Vec<Option<f64>> {
+        let live = self.live_entry(self.live_rsi);
+        finite_only(self.rsi.iter().copied().chain(live))
+    }
+
+    /// SMA as of the last closed candle, so the readout doesn't flicker
+    /// with every tick of the in-progress one.
+    pub fn latest_sma(&self) -> Option<f64> {
+        self.sma.back().copied().flatten().filter(|v| v.is_finite())
+    }
+
+    pub fn latest_rsi(&self) -> Option<f64> {
+        self.rsi.back().copied().flatten().filter(|v| v.is_finite())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candle(open_time: u64, close: f64) -> Candle {
+        crate::data::fetch::candle(open_time, close, close, close, close)
+    }
+
+    fn series(len: usize) -> Vec<Candle> {
+        (0..len)
+            .map(|i| candle(i as u64 * 60_000, 100.0 + ((i * 7) % 11) as f64 - 5.0))
+            .collect()
+    }
+
+    fn assert_close(a: &[Option<f64>], b: &[Option<f64>]) {
+        assert_eq!(a.len(), b.len());
+        for (x, y) in a.iter().zip(b) {
+            match (x, y) {
+                (Some(x), Some(y)) => assert!((x - y).abs() < 1e-9, "{x} != {y}"),
+                (None, None) => {}
+                _ => panic!("{x:?} != {y:?}"),
+            }
         }
+    }
 
-        if i >= period {
-            let avg_gain: f64 = gains[(i - period)..i].iter().sum::<f64>() / period as f64;
-            let avg_loss: f64 = losses[(i - period)..i].iter().sum::<f64>() / period as f64;
+    #[test]
+    fn rsi_matches_wilders_reference_values() {
+        // StockCharts' RSI(14) worked example; its figures round the
+        // averages to four places, hence the tolerance.
+        let closes = [
+            44.34, 44.09, 44.15, 43.61, 44.33, 44.83, 45.10, 45.42, 45.84, 46.08, 45.89, 46.03,
+            45.61, 46.28, 46.28, 46.00, 46.03, 46.41, 46.22, 45.64, 46.21, 46.25, 45.71, 46.45,
+            45.78, 45.35, 44.03, 44.18, 44.22, 44.57, 43.42, 42.66, 43.13,
+        ];
+        let expected = [
+            70.53, 66.32, 66.55, 69.41, 66.36, 57.97, 62.93, 63.26, 56.06, 62.38, 54.71, 50.42,
+            39.99, 41.46, 41.87, 45.46, 37.30, 33.08, 37.77,
+        ];
+        let candles: Vec<Candle> = closes
+            .iter()
+            .enumerate()
+            .map(|(i, close)| candle(i as u64 * 60_000, *close))
+            .collect();
 
-            if avg_loss == 0.0 {
-                rsi.push(Some(100.0));
-            } else {
-                let rs = avg_gain / avg_loss;
-                let rsi_value = 100.0 - (100.0 / (1.0 + rs));
-                rsi.push(Some(rsi_value));
+        let rsi = calculate_rsi(&candles, 14);
+        assert!(rsi[..14].iter().all(Option::is_none));
+        for (value, want) in rsi[14..].iter().zip(expected) {
+            let value = value.unwrap();
+            assert!((value - want).abs() < 0.1, "{value} != {want}");
+        }
+    }
+
+    /// Times a live-candle tick: incremental sync against the full
+    /// recompute it replaced. Run with
+    /// `cargo test --release -- --ignored --nocapture indicator_tick_timing`.
+    #[test]
+    #[ignore]
+    fn indicator_tick_timing() {
+        use std::time::Instant;
+
+        const TICKS: u32 = 1_000;
+        for len in [200, 1_000] {
+            let mut candles: VecDeque<Candle> = series(len).into();
+            let mut state = IndicatorState::new(DEFAULT_SMA_PERIOD, DEFAULT_RSI_PERIOD);
+            state.sync(&candles);
+
+            let start = Instant::now();
+            for tick in 0..TICKS {
+                candles.back_mut().unwrap().close = (100.0 + f64::from(tick % 7)).to_string();
+                state.sync(&candles);
+                std::hint::black_box(state.rsi_values());
             }
+            let incremental = start.elapsed() / TICKS;
+
+            let slice: Vec<Candle> = candles.iter().cloned().collect();
+            let start = Instant::now();
+            for _ in 0..TICKS {
+                std::hint::black_box(calculate_sma(&slice, DEFAULT_SMA_PERIOD));
+                std::hint::black_box(calculate_rsi(&slice, DEFAULT_RSI_PERIOD));
+            }
+            let full = start.elapsed() / TICKS;
+
+            println!(
+                "{len} candles: incremental {incremental:?}/tick, full recompute {full:?}/tick"
+            );
         }
     }
 
-    rsi
+    #[test]
+    fn roc_is_the_percent_move_over_the_period() {
+        let candles: Vec<Candle> = [100.0, 110.0, 120.0, 99.0, 0.0, 50.0]
+            .iter()
+            .enumerate()
+            .map(|(i, close)| candle(i as u64 * 60_000, *close))
+            .collect();
+        assert_close(
+            &calculate_roc(&candles, 2),
+            &[
+                None,
+                None,
+                Some(20.0),
+                Some(-10.0),
+                Some(-100.0),
+                Some(-49.494_949_494_949_5),
+            ],
+        );
+        // A zero base has no percent move.
+        assert_eq!(calculate_roc(&candles[3..], 1)[2], None);
+    }
+
+    #[test]
+    fn crossovers_mark_where_fast_passes_slow() {
+        let fast = [None, Some(1.0), Some(3.0), Some(3.0), Some(2.0), Some(1.0)];
+        let slow = [Some(2.0), Some(2.0), Some(2.0), Some(2.0), Some(2.0), None];
+        assert_eq!(
+            crossovers(&fast, &slow),
+            vec![
+                None,
+                None,
+                Some(Cross::Golden),
+                None,
+                Some(Cross::Death),
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn atr_seeds_with_mean_then_smooths() {
+        let bars = [
+            (11.0, 9.0, 10.0),
+            (12.0, 10.0, 11.0),
+            (15.0, 11.0, 14.0),
+            (14.0, 13.0, 13.5),
+        ];
+        let atr = calculate_atr(&bars, 3);
+        assert_close(&atr[..3], &[None, None, Some(8.0 / 3.0)]);
+        assert_close(&atr[3..], &[Some((8.0 / 3.0 * 2.0 + 1.0) / 3.0)]);
+    }
+
+    #[test]
+    fn active_indicators_round_trip_through_prefs() {
+        let active = [
+            IndicatorConfig {
+                kind: IndicatorKind::Rsi,
+                period: 500,
+            },
+            IndicatorConfig {
+                kind: IndicatorKind::Sma,
+                period: 50,
+            },
+            IndicatorConfig {
+                kind: IndicatorKind::Rsi,
+                period: 7,
+            },
+        ];
+        let prefs = IndicatorPrefs::from_active(&active).sanitized();
+        assert!(prefs.show_sma && prefs.show_rsi);
+        assert_eq!((prefs.sma_period, prefs.rsi_period), (50, 100));
+        assert_eq!(
+            prefs.active(),
+            vec![
+                IndicatorConfig {
+                    kind: IndicatorKind::Sma,
+                    period: 50
+                },
+                IndicatorConfig {
+                    kind: IndicatorKind::Rsi,
+                    period: 100
+                },
+            ]
+        );
+        assert_eq!(IndicatorPrefs::from_active(&[]), IndicatorPrefs::default());
+    }
+
+    #[test]
+    fn ema_seeds_with_the_mean_then_weights_recent_values() {
+        let ema = calculate_ema(&[1.0, 2.0, 3.0, 6.0], 3);
+        assert_close(&ema, &[None, None, Some(2.0), Some(4.0)]);
+        assert_eq!(calculate_ema(&[1.0], 0), vec![None]);
+    }
+
+    #[test]
+    fn bollinger_spreads_by_standard_deviation() {
+        let bands = calculate_bollinger(&[1.0, 3.0, 1.0, 3.0], 2, 2.0);
+        assert_eq!(bands[0], None);
+        assert_eq!(
+            bands[1],
+            Some(Band {
+                middle: 2.0,
+                upper: 4.0,
+                lower: 0.0
+            })
+        );
+        let flat = calculate_bollinger(&[5.0; 3], 3, 2.0);
+        assert_eq!(flat[2].map(|band| band.upper - band.lower), Some(0.0));
+    }
+
+    #[test]
+    fn squeeze_holds_while_bollinger_sits_inside_keltner() {
+        // A steady trend, then flat closes on wide bars: the Bollinger Bands
+        // contract inside the ATR-based channel until the breakout.
+        let mut bars: Vec<(f64, f64, f64)> = (0..10)
+            .map(|i| {
+                let close = 50.0 + 10.0 * i as f64;
+                (close + 1.0, close - 1.0, close)
+            })
+            .collect();
+        bars.extend((0..10).map(|_| (105.0, 95.0, 100.0)));
+        bars.extend([(131.0, 129.0, 130.0), (141.0, 139.0, 140.0)]);
+        let closes: Vec<f64> = bars.iter().map(|bar| bar.2).collect();
+
+        let squeeze = is_squeeze(
+            &calculate_bollinger(&closes, 5, 2.0),
+            &calculate_keltner(&bars, 5, 1.5),
+        );
+        assert!(!squeeze[4]);
+        assert!(squeeze[19]);
+        assert!(!squeeze[21]);
+
+        let releases = squeeze_releases(&squeeze);
+        let fired: Vec<usize> = (0..releases.len()).filter(|idx| releases[*idx]).collect();
+        assert_eq!(fired, vec![20]);
+    }
+
+    #[test]
+    fn percent_range_ranks_latest_volatility() {
+        // Ranges of 2 on a price of 100, then a quieter stretch at 1.
+        let mut bars = vec![(101.0, 99.0, 100.0); 4];
+        bars.extend([(100.5, 99.5, 100.0); 4]);
+
+        let calm = percent_range(&bars, 2, IndicatorBasis::Price).unwrap();
+        assert!(calm.value < calm.average);
+        assert!(calm.value > 1.0 && calm.value < 2.0);
+        assert!((calm.percentile - 100.0 / 7.0).abs() < 1e-9);
+
+        bars.push((106.0, 94.0, 100.0));
+        let spike = percent_range(&bars, 2, IndicatorBasis::Price).unwrap();
+        assert!(spike.value > spike.average);
+        assert_eq!(spike.percentile, 100.0);
+
+        assert_eq!(percent_range(&bars[..1], 2, IndicatorBasis::Price), None);
+    }
+
+    #[test]
+    fn transforms_closes_onto_each_basis() {
+        let closes = [100.0, 200.0, 0.0, 50.0];
+        assert_eq!(
+            transform_closes(&closes, IndicatorBasis::Price),
+            vec![Some(100.0), Some(200.0), Some(0.0), Some(50.0)]
+        );
+        assert_close(
+            &transform_closes(&closes, IndicatorBasis::LogPrice),
+            &[Some(100f64.ln()), Some(200f64.ln()), None, Some(50f64.ln())],
+        );
+        assert_close(
+            &transform_closes(&closes, IndicatorBasis::LogReturn),
+            &[None, Some(2f64.ln()), None, None],
+        );
+        assert!(transform_closes(&[], IndicatorBasis::LogReturn).is_empty());
+    }
+
+    #[test]
+    fn log_basis_range_matches_at_any_price_level() {
+        let cheap = vec![(1.1, 1.0, 1.05); 5];
+        let dear: Vec<_> = cheap
+            .iter()
+            .map(|(h, l, c)| (h * 1000.0, l * 1000.0, c * 1000.0))
+            .collect();
+        let cheap = percent_range(&cheap, 3, IndicatorBasis::LogPrice).unwrap();
+        let dear = percent_range(&dear, 3, IndicatorBasis::LogReturn).unwrap();
+        assert!((cheap.value - dear.value).abs() < 1e-9);
+        assert!((cheap.value - 1.1f64.ln() * 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn pathological_closes_never_yield_non_finite_readings() {
+        let closes = [
+            0.0,
+            0.0,
+            f64::NAN,
+            1e308,
+            1e308,
+            f64::INFINITY,
+            -1e308,
+            0.0,
+            5e-324,
+            5e-324,
+        ];
+        let mut buffer = VecDeque::new();
+        let mut state = IndicatorState::new(2, 2);
+        for (i, close) in closes.iter().enumerate() {
+            let mut c = candle(i as u64 * 60_000, *close);
+            c.is_closed = true;
+            buffer.push_back(c);
+            state.sync(&buffer);
+
+            let values = state.sma_values().into_iter().chain(state.rsi_values());
+            for value in values.flatten() {
+                assert!(value.is_finite(), "{value} after close {close}");
+            }
+            assert!(state.latest_sma().is_none_or(f64::is_finite));
+            assert!(state.latest_rsi().is_none_or(f64::is_finite));
+        }
+        assert_eq!(
+            finite_only([Some(1.0), Some(f64::NAN), None, Some(f64::NEG_INFINITY)]),
+            vec![Some(1.0), None, None, None]
+        );
+    }
+
+    #[test]
+    fn incremental_state_matches_full_recompute() {
+        let candles = series(60);
+        let mut buffer = VecDeque::new();
+        let mut state = IndicatorState::new(20, 14);
+
+        for c in &candles {
+            buffer.push_back(c.clone());
+            state.sync(&buffer);
+        }
+
+        assert_close(&state.sma_values(), &calculate_sma(&candles, 20));
+        assert_close(&state.rsi_values(), &calculate_rsi(&candles, 14));
+    }
+
+    #[test]
+    fn live_candle_updates_do_not_advance_state() {
+        let candles = series(30);
+        let mut buffer: VecDeque<Candle> = candles.iter().cloned().collect();
+        let mut state = IndicatorState::new(20, 14);
+        state.sync(&buffer);
+
+        let last = buffer.back_mut().unwrap();
+        *last = candle(last.open_time, 250.0);
+        state.sync(&buffer);
+        let last = buffer.back_mut().unwrap();
+        *last = candle(last.open_time, 90.0);
+        state.sync(&buffer);
+
+        let expected: Vec<Candle> = buffer.iter().cloned().collect();
+        assert_close(&state.sma_values(), &calculate_sma(&expected, 20));
+        assert_close(&state.rsi_values(), &calculate_rsi(&expected, 14));
+    }
+
+    #[test]
+    fn closed_flag_commits_last_candle_and_freezes_readout() {
+        let candles = series(25);
+        let mut buffer: VecDeque<Candle> = candles.iter().cloned().collect();
+        let mut state = IndicatorState::new(5, 3);
+
+        buffer.back_mut().unwrap().is_closed = true;
+        state.sync(&buffer);
+        let expected: Vec<Candle> = buffer.iter().cloned().collect();
+        assert_close(&state.sma_values(), &calculate_sma(&expected, 5));
+        let readout = state.latest_sma();
+        assert_eq!(readout, calculate_sma(&expected, 5)[24]);
+
+        state.sync(&buffer);
+        assert_eq!(state.sma_values().len(), buffer.len());
+
+        for close in [500.0, 20.0] {
+            buffer.push_back(candle(25 * 60_000, close));
+            state.sync(&buffer);
+            buffer.pop_back();
+            assert_eq!(state.latest_sma(), readout);
+        }
+
+        buffer.push_back(candle(25 * 60_000, 500.0));
+        buffer.back_mut().unwrap().is_closed = true;
+        state.sync(&buffer);
+        assert_ne!(state.latest_sma(), readout);
+        let expected: Vec<Candle> = buffer.iter().cloned().collect();
+        assert_close(&state.sma_values(), &calculate_sma(&expected, 5));
+    }
+
+    #[test]
+    fn trimmed_front_keeps_alignment() {
+        let candles = series(40);
+        let mut buffer: VecDeque<Candle> = candles.iter().cloned().collect();
+        let mut state = IndicatorState::new(5, 3);
+        state.sync(&buffer);
+
+        buffer.pop_front();
+        buffer.push_back(candle(40 * 60_000, 101.0));
+        state.sync(&buffer);
+
+        assert_eq!(state.sma_values().len(), buffer.len());
+        assert_eq!(state.rsi_values().len(), buffer.len());
+    }
 }
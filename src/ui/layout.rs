@@ -1,22 +1,90 @@
-use crate::data::WatchPrice;
-use crate::ui::{Chart, OrderBookPanel, StatusBar, Timeframe, TimeframeSelector, TradeTape};
+use crate::data::{Candle, ChangeBaseline, DailyAnchors, WatchPrice};
+use crate::ui::events::{EventLog, DEFAULT_EVENT_LOG_SIZE};
+use crate::ui::format::format_display_price;
+use crate::ui::preset::{PanelLayout, DEFAULT_SIDE_WIDTH};
+use crate::ui::returns::ReturnsPanel;
+use crate::ui::ribbon::TickerRibbon;
+use crate::ui::theme::{mark_stale, scale_color};
+use crate::ui::watchlist::{
+    section_of, step_selection, watchlist_rows, WatchRow, WatchlistSection,
+};
+use crate::ui::{
+    Chart, MtfStrip, OrderBookPanel, StatusBar, Theme, Timeframe, TimeframeSelector, TradeTape,
+};
 use ratatui::{
+    buffer::Buffer,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
     Frame,
 };
 use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+const FLASH_DURATION: Duration = Duration::from_millis(600);
+
+#[derive(Debug, Clone, Copy)]
+pub struct PriceFlash {
+    pub uptick: bool,
+    pub started: Instant,
+}
+
+impl PriceFlash {
+    /// Remaining flash strength in `0.0..=1.0`, fading linearly to zero.
+    fn intensity(&self, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(self.started).as_secs_f64();
+        (1.0 - elapsed / FLASH_DURATION.as_secs_f64()).clamp(0.0, 1.0)
+    }
+
+    fn style(&self, now: Instant, theme: &Theme) -> Option<Style> {
+        let intensity = self.intensity(now);
+        if intensity <= 0.0 {
+            return None;
+        }
+
+        let bg = theme.adapt(scale_color(
+            theme.direction(self.uptick),
+            0.2 + 0.7 * intensity,
+        ));
+        Some(Style::default().bg(bg).add_modifier(Modifier::BOLD))
+    }
+}
 
 pub struct LayoutManager {
     pub watchlist: Vec<String>,
+    pub sections: Vec<WatchlistSection>,
     pub selected_symbol: usize,
     pub watch_prices: HashMap<String, WatchPrice>,
+    /// Baseline the watchlist's percent change is rebased onto as prices
+    /// arrive.
+    pub change_baseline: ChangeBaseline,
+    /// Daily open and previous close per symbol, for the non-streamed
+    /// baselines.
+    pub daily_anchors: HashMap<String, DailyAnchors>,
     pub orderbook: OrderBookPanel,
     pub tradetape: TradeTape,
     pub statusbar: StatusBar,
     pub timeframe: TimeframeSelector,
+    pub mtf: MtfStrip,
+    pub ribbon: TickerRibbon,
+    pub returns: ReturnsPanel,
+    pub events: EventLog,
+    pub flashes: HashMap<String, PriceFlash>,
+    pub flash_enabled: bool,
+    pub theme: Theme,
+    /// When false, `render` reuses the chart drawn last frame instead of
+    /// rendering it again.
+    pub redraw_chart: bool,
+    /// The watchlist price feed has gone quiet for longer than expected.
+    pub watchlist_stale: bool,
+    /// Width of the chart panel as last laid out.
+    pub chart_width: u16,
+    pub show_watchlist: bool,
+    /// The order book and trade tape column.
+    pub show_side_panels: bool,
+    pub side_width: u16,
+    chart_cache: Option<Buffer>,
 }
 
 impl LayoutManager {
@@ -24,70 +92,284 @@ impl LayoutManager {
         let selected_symbol = selected_symbol.min(watchlist.len().saturating_sub(1));
         Self {
             watchlist,
+            sections: Vec::new(),
             selected_symbol,
             watch_prices: HashMap::new(),
+            change_baseline: ChangeBaseline::default(),
+            daily_anchors: HashMap::new(),
             orderbook: OrderBookPanel::new(),
             tradetape: TradeTape::new(),
             statusbar: StatusBar::new(),
             timeframe: TimeframeSelector::from_timeframe(timeframe),
+            mtf: MtfStrip::new(),
+            ribbon: TickerRibbon::new(),
+            returns: ReturnsPanel::new(),
+            events: EventLog::new(DEFAULT_EVENT_LOG_SIZE),
+            flashes: HashMap::new(),
+            flash_enabled: true,
+            theme: Theme::default(),
+            redraw_chart: true,
+            watchlist_stale: false,
+            chart_width: 0,
+            show_watchlist: true,
+            show_side_panels: true,
+            side_width: DEFAULT_SIDE_WIDTH,
+            chart_cache: None,
+        }
+    }
+
+    fn render_chart(&mut self, frame: &mut Frame, chart: &Chart, area: Rect) {
+        if !self.redraw_chart {
+            if let Some(cached) = self.chart_cache.as_ref().filter(|c| c.area == area) {
+                frame.buffer_mut().merge(cached);
+                return;
+            }
         }
+
+        chart.render(frame, area, &self.theme);
+        let mut cached = Buffer::empty(area);
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                cached[(x, y)] = frame.buffer_mut()[(x, y)].clone();
+            }
+        }
+        self.chart_cache = Some(cached);
     }
 
-    pub fn update_watch_price(&mut self, price: WatchPrice) {
+    pub fn update_watch_price(&mut self, mut price: WatchPrice) {
+        price.change_pct = self
+            .change_baseline
+            .change_pct(&price, self.daily_anchors.get(&price.symbol));
+        if self.flash_enabled {
+            if let Some(previous) = self.watch_prices.get(&price.symbol) {
+                if price.last_price != previous.last_price {
+                    self.flashes.insert(
+                        price.symbol.clone(),
+                        PriceFlash {
+                            uptick: price.last_price > previous.last_price,
+                            started: Instant::now(),
+                        },
+                    );
+                }
+            }
+        }
         self.watch_prices.insert(price.symbol.clone(), price);
     }
 
+    /// The highlighted watchlist symbol; `None` when the watchlist is empty.
+    pub fn selected_symbol(&self) -> Option<&str> {
+        self.watchlist.get(self.selected_symbol).map(String::as_str)
+    }
+
+    /// Adds `symbol` to the end of the watchlist, selecting it if the list was
+    /// empty. Returns false if it was already there.
+    pub fn add_symbol(&mut self, symbol: &str) -> bool {
+        if self.watchlist.iter().any(|s| s == symbol) {
+            return false;
+        }
+        if self.watchlist.is_empty() {
+            self.selected_symbol = 0;
+        }
+        self.watchlist.push(symbol.to_string());
+        true
+    }
+
+    pub fn move_selection(&mut self, delta: isize) {
+        if self.watchlist.is_empty() {
+            return;
+        }
+        self.selected_symbol =
+            step_selection(&self.watchlist, &self.sections, self.selected_symbol, delta);
+    }
+
+    fn selected_section(&self) -> Option<usize> {
+        let symbol = self.watchlist.get(self.selected_symbol)?;
+        section_of(&self.sections, symbol)
+    }
+
+    pub fn selected_section_collapsed(&self) -> bool {
+        self.selected_section()
+            .is_some_and(|idx| self.sections[idx].collapsed)
+    }
+
+    /// Collapses or expands the section holding the selection. Returns false
+    /// when the watchlist has no sections.
+    pub fn toggle_selected_section(&mut self) -> bool {
+        let Some(idx) = self.selected_section() else {
+            return false;
+        };
+        let section = &mut self.sections[idx];
+        section.collapsed = !section.collapsed;
+        true
+    }
+
+    pub fn apply_panels(&mut self, panels: &PanelLayout) {
+        self.show_watchlist = panels.show_watchlist;
+        self.show_side_panels = panels.show_side_panels;
+        self.side_width = panels.side_width;
+    }
+
+    pub fn toggle_flash(&mut self) {
+        self.flash_enabled = !self.flash_enabled;
+        if !self.flash_enabled {
+            self.flashes.clear();
+        }
+    }
+
+    /// Drops finished flashes and reports whether any are still fading or the
+    /// ticker ribbon is scrolling, so the render loop knows to keep drawing
+    /// frames.
+    pub fn is_animating(&mut self, now: Instant) -> bool {
+        self.flashes
+            .retain(|_, flash| now.saturating_duration_since(flash.started) < FLASH_DURATION);
+        !self.flashes.is_empty() || self.ribbon.visible
+    }
+
     pub fn render(&mut self, frame: &mut Frame, chart: &Chart, area: Rect) {
+        self.theme.price_precision = chart.price_precision();
+        let ribbon_height = if self.ribbon.visible { 1 } else { 0 };
         let main_chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(3),
+                Constraint::Length(self.theme.framed_height(1)),
                 Constraint::Min(10),
                 Constraint::Length(1),
+                Constraint::Length(ribbon_height),
             ])
             .split(area);
 
-        self.timeframe.render(frame, main_chunks[0]);
+        if self.mtf.visible {
+            let top_chunks = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Min(30), Constraint::Length(52)])
+                .split(main_chunks[0]);
+            self.timeframe.render(frame, top_chunks[0], &self.theme);
+            self.mtf.render(frame, top_chunks[1], &self.theme);
+        } else {
+            self.timeframe.render(frame, main_chunks[0], &self.theme);
+        }
 
-        let content_chunks = Layout::default()
+        let columns = [
+            (self.show_watchlist, Constraint::Length(20)),
+            (true, Constraint::Min(40)),
+            (self.show_side_panels, Constraint::Length(self.side_width)),
+        ];
+        let split = Layout::default()
             .direction(Direction::Horizontal)
-            .constraints([
-                Constraint::Length(20),
-                Constraint::Min(40),
-                Constraint::Length(30),
-            ])
+            .constraints(columns.iter().filter(|(shown, _)| *shown).map(|(_, c)| *c))
             .split(main_chunks[1]);
+        // Hidden columns get an empty rect so the indexes below stay fixed.
+        let mut next = split.iter().copied();
+        let content_chunks: Vec<Rect> = columns
+            .iter()
+            .map(|(shown, _)| {
+                if *shown {
+                    next.next().unwrap_or_default()
+                } else {
+                    Rect::default()
+                }
+            })
+            .collect();
 
+        // The book keeps 40% once extra panels join; the tape and the extras
+        // split the rest evenly.
+        let extras = [self.returns.visible, self.events.visible]
+            .iter()
+            .filter(|shown| **shown)
+            .count() as u32;
+        let right_constraints = if extras == 0 {
+            vec![Constraint::Percentage(50), Constraint::Percentage(50)]
+        } else {
+            std::iter::once(Constraint::Percentage(40))
+                .chain((0..=extras).map(|_| Constraint::Ratio(3, 5 * (extras + 1))))
+                .collect()
+        };
         let right_chunks = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .constraints(right_constraints)
             .split(content_chunks[2]);
 
-        self.render_watchlist(frame, content_chunks[0], chart);
-        chart.render(frame, content_chunks[1]);
-        self.orderbook.render(frame, right_chunks[0]);
-        self.tradetape.render(frame, right_chunks[1]);
+        if self.show_watchlist {
+            self.render_watchlist(frame, content_chunks[0], chart);
+        }
+        self.chart_width = content_chunks[1].width;
+        self.render_chart(frame, chart, content_chunks[1]);
+        if self.show_side_panels {
+            self.orderbook.render(frame, right_chunks[0], &self.theme);
+            self.tradetape.render(frame, right_chunks[1], &self.theme);
+        }
+        let mut extra_chunks = right_chunks.iter().skip(2).copied();
+        if self.show_side_panels && self.returns.visible {
+            let visible = chart.visible_candles(content_chunks[1].width);
+            let candles: Vec<&Candle> = visible.iter().map(AsRef::as_ref).collect();
+            self.returns.render(
+                frame,
+                extra_chunks.next().unwrap_or_default(),
+                &candles,
+                chart.indicator_basis,
+                &self.theme,
+            );
+        }
+        if self.show_side_panels && self.events.visible {
+            self.events
+                .render(frame, extra_chunks.next().unwrap_or_default(), &self.theme);
+        }
         self.statusbar.symbol = chart.symbol.clone();
+        self.statusbar.wide_spread = self.orderbook.spread.warning();
+        self.statusbar.dropped_candles = chart.dropped_candles.len();
         self.statusbar.render(frame, main_chunks[2]);
+        if self.ribbon.visible {
+            self.ribbon.render(
+                frame,
+                main_chunks[3],
+                &self.watchlist,
+                &self.watch_prices,
+                &self.theme,
+                Instant::now(),
+            );
+        }
     }
 
     fn render_watchlist(&self, frame: &mut Frame, area: Rect, chart: &Chart) {
         let vertical = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Length(3), Constraint::Min(5)])
+            .constraints([
+                Constraint::Length(if self.theme.dense { 1 } else { 3 }),
+                Constraint::Min(5),
+            ])
             .split(area);
+        let now = Instant::now();
 
-        let title_block = Block::default()
-            .title("Watchlist")
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Magenta));
+        let title_block = mark_stale(
+            Block::default()
+                .title("Watchlist")
+                .borders(self.theme.borders())
+                .border_style(Style::default().fg(Color::Magenta)),
+            self.watchlist_stale,
+        );
         frame.render_widget(title_block, vertical[0]);
 
-        let items: Vec<ListItem> = self
-            .watchlist
-            .iter()
-            .enumerate()
-            .map(|(idx, symbol)| {
+        if self.watchlist.is_empty() {
+            let prompt = Paragraph::new(Line::from(Span::styled(
+                "No symbols — press / to add one",
+                Style::default().fg(Color::DarkGray),
+            )))
+            .wrap(Wrap { trim: true });
+            frame.render_widget(prompt, vertical[1]);
+            return;
+        }
+
+        let selected_section = self.selected_section();
+        let items: Vec<ListItem> = watchlist_rows(&self.watchlist, &self.sections)
+            .into_iter()
+            .map(|row| {
+                let idx = match row {
+                    WatchRow::Symbol(idx) => idx,
+                    WatchRow::Header { section } => {
+                        return self.section_header(section, selected_section == Some(section));
+                    }
+                };
+                let symbol = &self.watchlist[idx];
                 let is_selected = idx == self.selected_symbol;
                 let is_current = symbol == &chart.symbol;
                 let style = if is_current {
@@ -101,13 +383,24 @@ impl LayoutManager {
                 };
 
                 if let Some(price) = self.watch_prices.get(symbol) {
-                    let change_color = if price.change_pct >= 0.0 {
-                        Color::Green
-                    } else {
-                        Color::Red
-                    };
+                    let change_color = self.theme.direction(price.change_pct >= 0.0);
+                    let price_style = self
+                        .flashes
+                        .get(symbol)
+                        .and_then(|flash| flash.style(now, &self.theme))
+                        .map_or(style, |flash_style| style.patch(flash_style));
                     let line = Line::from(vec![
-                        Span::styled(format!("{} {:.2} ", symbol, price.last_price), style),
+                        Span::styled(
+                            format!(
+                                "{} {} ",
+                                symbol,
+                                format_display_price(
+                                    price.last_price,
+                                    self.theme.max_price_decimals
+                                )
+                            ),
+                            price_style,
+                        ),
                         Span::styled(
                             format!("{:+.2}%", price.change_pct),
                             Style::default().fg(change_color),
@@ -120,7 +413,10 @@ impl LayoutManager {
                     if let Some(last) = chart.candles.back() {
                         if let Ok(close) = last.close.parse::<f64>() {
                             let line = Line::from(vec![
-                                Span::styled(format!("{} {:.2}", symbol, close), style),
+                                Span::styled(
+                                    format!("{} {}", symbol, self.theme.format_price(close)),
+                                    style,
+                                ),
                                 Span::styled(" ...", Style::default().fg(Color::Gray)),
                             ]);
                             return ListItem::new(line);
@@ -140,4 +436,78 @@ impl LayoutManager {
             .style(Style::default().fg(Color::White));
         frame.render_widget(list, vertical[1]);
     }
+
+    fn section_header(&self, section: usize, holds_selection: bool) -> ListItem<'static> {
+        let section = &self.sections[section];
+        let label = if section.collapsed {
+            format!("▸ {} ({})", section.name, section.symbols.len())
+        } else {
+            format!("▾ {}", section.name)
+        };
+        let mut style = Style::default()
+            .fg(Color::Magenta)
+            .add_modifier(Modifier::BOLD);
+        if section.collapsed && holds_selection {
+            style = style.fg(Color::Yellow);
+        }
+        ListItem::new(Line::from(Span::styled(label, style)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_watchlist_shows_prompt_until_a_symbol_is_added() {
+        let mut layout = LayoutManager::new(Vec::new(), 3, Timeframe::OneDay);
+        layout.move_selection(1);
+        assert_eq!(layout.selected_symbol(), None);
+        assert!(!layout.toggle_selected_section());
+
+        let chart = Chart::new("BTCUSDT".to_string(), "1d".to_string());
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(120, 30)).unwrap();
+        terminal
+            .draw(|f| layout.render(f, &chart, f.area()))
+            .unwrap();
+        let text: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+        assert!(text.contains("No symbols"));
+
+        assert!(layout.add_symbol("ETHUSDT"));
+        assert!(!layout.add_symbol("ETHUSDT"));
+        assert_eq!(layout.selected_symbol(), Some("ETHUSDT"));
+    }
+
+    #[test]
+    fn dense_mode_gives_border_rows_back() {
+        let prompt_row = |dense: bool| {
+            let mut layout = LayoutManager::new(Vec::new(), 0, Timeframe::OneDay);
+            layout.theme.dense = dense;
+            let chart = Chart::new("BTCUSDT".to_string(), "1d".to_string());
+            let mut terminal =
+                ratatui::Terminal::new(ratatui::backend::TestBackend::new(120, 30)).unwrap();
+            terminal
+                .draw(|f| layout.render(f, &chart, f.area()))
+                .unwrap();
+            let buffer = terminal.backend().buffer().clone();
+            (0..buffer.area.height).find(|&y| {
+                let row: String = (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect();
+                row.contains("No symbols")
+            })
+        };
+
+        // The top strip loses its bottom border and the watchlist header
+        // shrinks to its title line.
+        assert_eq!(prompt_row(false), Some(6));
+        assert_eq!(prompt_row(true), Some(3));
+    }
 }
@@ -0,0 +1,99 @@
+use std::collections::VecDeque;
+
+/// Spread, in percent of price, above which a symbol is flagged as thin.
+pub const DEFAULT_WIDE_SPREAD_PCT: f64 = 0.5;
+/// Book updates averaged over; depth arrives every 100ms, so about 5s.
+const SPREAD_SAMPLES: usize = 50;
+/// The flag clears only once the average falls below this share of the
+/// threshold, so a spread hovering at the line doesn't flicker it.
+const CLEAR_RATIO: f64 = 0.8;
+
+/// Rolling average of the book's spread that trips a warning when it stays
+/// wide.
+#[derive(Debug, Clone)]
+pub struct SpreadWatch {
+    samples: VecDeque<f64>,
+    pub threshold_pct: f64,
+    wide: bool,
+}
+
+impl SpreadWatch {
+    pub fn new(threshold_pct: f64) -> Self {
+        Self {
+            samples: VecDeque::with_capacity(SPREAD_SAMPLES),
+            threshold_pct,
+            wide: false,
+        }
+    }
+
+    /// Adds one spread reading; returns whether the warning switched on or
+    /// off.
+    pub fn record(&mut self, spread_pct: f64) -> bool {
+        if !spread_pct.is_finite() {
+            return false;
+        }
+        if self.samples.len() == SPREAD_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(spread_pct);
+
+        // A half-full window is enough to judge a new symbol.
+        if self.samples.len() < SPREAD_SAMPLES / 2 {
+            return false;
+        }
+        let average = self.average().unwrap_or(0.0);
+        let was_wide = self.wide;
+        if average > self.threshold_pct {
+            self.wide = true;
+        } else if average < self.threshold_pct * CLEAR_RATIO {
+            self.wide = false;
+        }
+        self.wide != was_wide
+    }
+
+    fn average(&self) -> Option<f64> {
+        (!self.samples.is_empty())
+            .then(|| self.samples.iter().sum::<f64>() / self.samples.len() as f64)
+    }
+
+    /// The average spread while it is flagged as wide.
+    pub fn warning(&self) -> Option<f64> {
+        self.wide.then(|| self.average()).flatten()
+    }
+
+    pub fn reset(&mut self) {
+        self.samples.clear();
+        self.wide = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trips_above_threshold_and_clears_below_the_band() {
+        let mut watch = SpreadWatch::new(0.5);
+        for _ in 0..SPREAD_SAMPLES / 2 - 1 {
+            assert!(!watch.record(2.0));
+        }
+        assert_eq!(watch.warning(), None);
+        assert!(watch.record(2.0));
+        assert!((watch.warning().unwrap() - 2.0).abs() < 1e-9);
+
+        // Inside the hysteresis band the flag holds.
+        for _ in 0..SPREAD_SAMPLES {
+            watch.record(0.45);
+        }
+        assert!(watch.warning().is_some());
+        for _ in 0..SPREAD_SAMPLES {
+            watch.record(0.1);
+        }
+        assert_eq!(watch.warning(), None);
+
+        watch.record(5.0);
+        watch.reset();
+        assert_eq!(watch.warning(), None);
+        assert!(!watch.record(f64::NAN));
+    }
+}
@@ -0,0 +1,129 @@
+use crate::data::Ticker24h;
+use crate::ui::format::{format_signed_price, format_volume};
+use crate::ui::Theme;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use std::time::{Duration, Instant};
+
+const REFRESH_INTERVAL: Duration = Duration::from_secs(10);
+
+pub struct MarketStatsModal {
+    pub symbol: String,
+    pub stats: Option<Ticker24h>,
+    pub error: Option<String>,
+    pub last_requested: Option<Instant>,
+}
+
+impl MarketStatsModal {
+    pub fn new(symbol: String) -> Self {
+        Self {
+            symbol,
+            stats: None,
+            error: None,
+            last_requested: None,
+        }
+    }
+
+    pub fn needs_refresh(&self, now: Instant) -> bool {
+        self.last_requested
+            .is_none_or(|last| now.saturating_duration_since(last) >= REFRESH_INTERVAL)
+    }
+
+    pub fn apply(&mut self, result: Result<Ticker24h, String>) {
+        match result {
+            Ok(stats) if stats.symbol == self.symbol => {
+                self.stats = Some(stats);
+                self.error = None;
+            }
+            Ok(_) => {}
+            Err(err) => self.error = Some(err),
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, theme: &Theme) {
+        let area = frame.area();
+        let width = 44.min(area.width);
+        let height = 15.min(area.height);
+        let popup = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+
+        let block = Block::default()
+            .title(format!("{} 24h Stats (Esc to close)", self.symbol))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan));
+
+        let lines = match &self.stats {
+            Some(stats) => Self::stat_lines(stats, theme),
+            None => vec![Line::from(Span::styled(
+                "Loading...",
+                Style::default().fg(Color::Gray),
+            ))],
+        };
+        let mut lines = lines;
+        if let Some(err) = &self.error {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                format!("ERR: {}", err),
+                Style::default().fg(Color::Red),
+            )));
+        }
+
+        frame.render_widget(Clear, popup);
+        frame.render_widget(Paragraph::new(lines).block(block), popup);
+    }
+
+    fn stat_lines(stats: &Ticker24h, theme: &Theme) -> Vec<Line<'static>> {
+        let row = |label: &str, value: String, color: Color| {
+            Line::from(vec![
+                Span::styled(format!("{:<14}", label), Style::default().fg(Color::Gray)),
+                Span::styled(value, Style::default().fg(color)),
+            ])
+        };
+        let change_color = theme.direction(stats.price_change >= 0.0);
+        let price = |value: f64| theme.format_price(value);
+
+        vec![
+            row("Open", price(stats.open), Color::White),
+            row("High", price(stats.high), theme.up),
+            row("Low", price(stats.low), theme.down),
+            row("Last", price(stats.last), Color::White),
+            Line::from(vec![
+                Span::styled(
+                    format!("{:<14}", "Change"),
+                    Style::default().fg(Color::Gray),
+                ),
+                Span::styled(
+                    format!(
+                        "{} ({:+.2}%)",
+                        format_signed_price(stats.price_change, theme.price_decimals(stats.last)),
+                        stats.price_change_pct
+                    ),
+                    Style::default()
+                        .fg(change_color)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            ]),
+            row("Wtd avg", price(stats.weighted_avg_price), Color::White),
+            row(
+                "Volume",
+                format_volume(stats.volume, 4, theme.volume_format),
+                Color::Yellow,
+            ),
+            row(
+                "Quote volume",
+                format_volume(stats.quote_volume, 2, theme.volume_format),
+                Color::Yellow,
+            ),
+            row("Trades", stats.trade_count.to_string(), Color::White),
+        ]
+    }
+}
@@ -1,14 +1,40 @@
+pub mod braille;
 pub mod chart;
+pub mod debug;
+pub mod events;
+pub mod format;
+pub mod grid;
+pub mod heatmap;
 pub mod indicators;
 pub mod layout;
+pub mod liquidity;
+pub mod marketstats;
+pub mod mtf;
+pub mod notes;
 pub mod orderbook;
+pub mod outage;
+pub mod overlay;
+pub mod preset;
+pub mod priceaxis;
+pub mod quiet;
+pub mod relative;
+pub mod returns;
+pub mod ribbon;
+pub mod search;
 pub mod statusbar;
+pub mod theme;
+pub mod timeaxis;
 pub mod timeframe;
 pub mod tradetape;
+pub mod warmup;
+pub mod watchlist;
 
 pub use chart::Chart;
 pub use layout::LayoutManager;
+pub use marketstats::MarketStatsModal;
+pub use mtf::MtfStrip;
 pub use orderbook::OrderBookPanel;
 pub use statusbar::{ConnectionMode, StatusBar};
+pub use theme::{Palette, Theme};
 pub use timeframe::{Timeframe, TimeframeSelector};
 pub use tradetape::TradeTape;
@@ -0,0 +1,119 @@
+use crate::data::Candle;
+use crate::ui::Theme;
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Paragraph},
+    Frame,
+};
+use std::time::{Duration, Instant};
+
+pub const MTF_INTERVALS: [&str; 4] = ["5m", "1h", "4h", "1d"];
+const REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Percent change between the last two candles' closes, i.e. how the latest
+/// candle is moving relative to where the previous one finished.
+pub fn change_from_candles(candles: &[Candle]) -> Option<f64> {
+    let [.., previous, latest] = candles else {
+        return None;
+    };
+    let previous: f64 = previous.close.parse().ok()?;
+    let latest: f64 = latest.close.parse().ok()?;
+    if previous <= 0.0 {
+        return None;
+    }
+    Some((latest - previous) / previous * 100.0)
+}
+
+pub struct MtfStrip {
+    pub symbol: String,
+    pub changes: Vec<(&'static str, Option<f64>)>,
+    pub visible: bool,
+    pub last_requested: Option<Instant>,
+}
+
+impl MtfStrip {
+    pub fn new() -> Self {
+        Self {
+            symbol: String::new(),
+            changes: MTF_INTERVALS.iter().map(|iv| (*iv, None)).collect(),
+            visible: true,
+            last_requested: None,
+        }
+    }
+
+    pub fn needs_refresh(&self, symbol: &str, now: Instant) -> bool {
+        self.visible
+            && (self.symbol != symbol
+                || self
+                    .last_requested
+                    .is_none_or(|last| now.saturating_duration_since(last) >= REFRESH_INTERVAL))
+    }
+
+    pub fn begin_refresh(&mut self, symbol: &str, now: Instant) {
+        if self.symbol != symbol {
+            self.symbol = symbol.to_string();
+            self.changes = MTF_INTERVALS.iter().map(|iv| (*iv, None)).collect();
+        }
+        self.last_requested = Some(now);
+    }
+
+    pub fn apply(&mut self, symbol: &str, changes: Vec<(&'static str, Option<f64>)>) {
+        if self.symbol == symbol {
+            self.changes = changes;
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+        self.last_requested = None;
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let block = Block::default()
+            .title("MTF")
+            .borders(theme.borders())
+            .border_style(Style::default().fg(Color::Cyan));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let mut spans = Vec::new();
+        for (idx, (interval, change)) in self.changes.iter().enumerate() {
+            if idx > 0 {
+                spans.push(Span::raw(" "));
+            }
+            spans.push(Span::styled(
+                format!("{} ", interval),
+                Style::default().fg(Color::Gray),
+            ));
+            match change {
+                Some(pct) => spans.push(Span::styled(
+                    format!("{}{:+.2}%", if *pct >= 0.0 { "▲" } else { "▼" }, pct),
+                    Style::default().fg(theme.direction(*pct >= 0.0)),
+                )),
+                None => spans.push(Span::styled("…", Style::default().fg(Color::DarkGray))),
+            }
+        }
+
+        let para = Paragraph::new(Line::from(spans)).alignment(Alignment::Center);
+        frame.render_widget(para, inner);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candle(close: f64) -> Candle {
+        crate::data::fetch::candle(0, close, close, close, close)
+    }
+
+    #[test]
+    fn change_uses_last_two_closes() {
+        let change = change_from_candles(&[candle(90.0), candle(100.0), candle(95.0)]).unwrap();
+        assert!((change + 5.0).abs() < 1e-9);
+        assert_eq!(change_from_candles(&[candle(100.0)]), None);
+        assert_eq!(change_from_candles(&[candle(0.0), candle(1.0)]), None);
+    }
+}
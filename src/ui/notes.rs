@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A user annotation pinned to a candle (by open time) and a price, so it
+/// stays put as the viewport pans or zooms.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Note {
+    pub open_time: u64,
+    pub price: f64,
+    pub text: String,
+}
+
+/// Notes keyed by symbol. A candle holds at most one note.
+pub type SymbolNotes = BTreeMap<String, Vec<Note>>;
+
+pub fn upsert_note(notes: &mut SymbolNotes, symbol: &str, note: Note) {
+    let entries = notes.entry(symbol.to_string()).or_default();
+    match entries.iter_mut().find(|n| n.open_time == note.open_time) {
+        Some(existing) => *existing = note,
+        None => {
+            entries.push(note);
+            entries.sort_by_key(|n| n.open_time);
+        }
+    }
+}
+
+pub fn remove_note(notes: &mut SymbolNotes, symbol: &str, open_time: u64) -> bool {
+    let Some(entries) = notes.get_mut(symbol) else {
+        return false;
+    };
+    let before = entries.len();
+    entries.retain(|n| n.open_time != open_time);
+    let removed = entries.len() != before;
+    if entries.is_empty() {
+        notes.remove(symbol);
+    }
+    removed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(open_time: u64, text: &str) -> Note {
+        Note {
+            open_time,
+            price: 100.0,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn upsert_replaces_note_on_same_candle_and_remove_prunes_symbol() {
+        let mut notes = SymbolNotes::new();
+        upsert_note(&mut notes, "BTCUSDT", note(2, "b"));
+        upsert_note(&mut notes, "BTCUSDT", note(1, "a"));
+        upsert_note(&mut notes, "BTCUSDT", note(2, "entry"));
+
+        let texts: Vec<&str> = notes["BTCUSDT"].iter().map(|n| n.text.as_str()).collect();
+        assert_eq!(texts, vec!["a", "entry"]);
+
+        assert!(remove_note(&mut notes, "BTCUSDT", 1));
+        assert!(remove_note(&mut notes, "BTCUSDT", 2));
+        assert!(!remove_note(&mut notes, "BTCUSDT", 2));
+        assert!(notes.is_empty());
+    }
+}
@@ -1,15 +1,157 @@
+use crate::data::orderbook::{simulate_fill, OrderBookEntry, Side};
+use crate::data::symbols::{check_order_size, SymbolInfo};
 use crate::data::OrderBook;
+use crate::ui::format::format_volume;
+use crate::ui::liquidity::{SpreadWatch, DEFAULT_WIDE_SPREAD_PCT};
+use crate::ui::theme::{mark_stale, scale_color};
+use crate::ui::Theme;
 use ratatui::{
     layout::{Alignment, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Paragraph},
     Frame,
 };
+use serde::{Deserialize, Serialize};
+
+/// A level counts as a wall when it holds more than this many times the
+/// median level size on its side.
+pub const WALL_FACTOR: f64 = 3.0;
+/// Fewer levels than this give no meaningful median, so no walls.
+const MIN_WALL_LEVELS: usize = 5;
+
+/// Narrower than this, shaded views leave too little room for the bars and
+/// fall back to the plain ladder.
+const MIN_SHADED_WIDTH: u16 = 28;
+
+/// How the order book draws its levels; `L` cycles through them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum BookViewMode {
+    /// Price and size per level.
+    #[default]
+    Ladder,
+    /// Bars behind each row sized by the depth from the spread through that
+    /// level.
+    Cumulative,
+    /// Rows shaded by their own size relative to the largest level shown.
+    Heat,
+}
+
+impl BookViewMode {
+    pub fn next(self) -> Self {
+        match self {
+            BookViewMode::Ladder => BookViewMode::Cumulative,
+            BookViewMode::Cumulative => BookViewMode::Heat,
+            BookViewMode::Heat => BookViewMode::Ladder,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            BookViewMode::Ladder => "Ladder",
+            BookViewMode::Cumulative => "Depth",
+            BookViewMode::Heat => "Heat",
+        }
+    }
+
+    /// Shading for each of `entries`, 0–1, or `None` for the plain ladder.
+    pub fn shading(self, entries: &[&OrderBookEntry]) -> Option<Vec<f64>> {
+        match self {
+            BookViewMode::Ladder => None,
+            BookViewMode::Cumulative => {
+                let cumulative: Vec<f64> = entries
+                    .iter()
+                    .scan(0.0, |total, entry| {
+                        *total += entry.quantity;
+                        Some(*total)
+                    })
+                    .collect();
+                let total = cumulative.last().copied().unwrap_or(0.0);
+                Some(
+                    cumulative
+                        .iter()
+                        .map(|depth| ratio(*depth, total))
+                        .collect(),
+                )
+            }
+            BookViewMode::Heat => {
+                let largest = entries.iter().map(|e| e.quantity).fold(0.0, f64::max);
+                Some(entries.iter().map(|e| ratio(e.quantity, largest)).collect())
+            }
+        }
+    }
+}
+
+fn ratio(value: f64, max: f64) -> f64 {
+    if max > 0.0 {
+        (value / max).clamp(0.0, 1.0)
+    } else {
+        0.0
+    }
+}
+
+/// Indices of levels on one side whose quantity exceeds `factor` times the
+/// side's median level size.
+pub fn detect_walls(entries: &[OrderBookEntry], factor: f64) -> Vec<usize> {
+    if entries.len() < MIN_WALL_LEVELS {
+        return Vec::new();
+    }
+    let mut sizes: Vec<f64> = entries.iter().map(|entry| entry.quantity).collect();
+    sizes.sort_by(|a, b| a.total_cmp(b));
+    let mid = sizes.len() / 2;
+    let median = if sizes.len().is_multiple_of(2) {
+        (sizes[mid - 1] + sizes[mid]) / 2.0
+    } else {
+        sizes[mid]
+    };
+    if median <= 0.0 {
+        return Vec::new();
+    }
+
+    entries
+        .iter()
+        .enumerate()
+        .filter(|(_, entry)| entry.quantity > median * factor)
+        .map(|(idx, _)| idx)
+        .collect()
+}
 
 pub struct OrderBookPanel {
     pub orderbook: Option<OrderBook>,
     pub max_entries: usize,
+    pub preview: Option<(Side, f64)>,
+    /// Wall indices into the current book's bids and asks.
+    pub bid_walls: Vec<usize>,
+    pub ask_walls: Vec<usize>,
+    /// The depth feed has gone quiet for longer than expected.
+    pub stale: bool,
+    /// Lot size rules for the current symbol, once exchangeInfo has loaded.
+    pub lot_rules: Option<SymbolInfo>,
+    pub view: BookViewMode,
+    /// Start each side at the last traded price instead of the top of book,
+    /// so the levels price is moving through stay in view.
+    pub follow_trades: bool,
+    /// Price of the latest trade on the tape.
+    pub last_trade: Option<f64>,
+    /// Flags a book whose spread stays wide.
+    pub spread: SpreadWatch,
+}
+
+/// Where each side's shown levels start when following `last_trade`: the
+/// first ask at or above it and the first bid at or below it. A side the
+/// price has run past keeps its farthest level in view.
+pub fn follow_offsets(book: &OrderBook, last_trade: f64) -> (usize, usize) {
+    let start = |levels: &[OrderBookEntry], skip: usize| skip.min(levels.len().saturating_sub(1));
+    (
+        start(
+            &book.asks,
+            book.asks.partition_point(|level| level.price < last_trade),
+        ),
+        start(
+            &book.bids,
+            book.bids.partition_point(|level| level.price > last_trade),
+        ),
+    )
 }
 
 impl OrderBookPanel {
@@ -17,22 +159,115 @@ impl OrderBookPanel {
         Self {
             orderbook: None,
             max_entries: 10,
+            preview: None,
+            stale: false,
+            lot_rules: None,
+            view: BookViewMode::default(),
+            follow_trades: false,
+            last_trade: None,
+            spread: SpreadWatch::new(DEFAULT_WIDE_SPREAD_PCT),
+            bid_walls: Vec::new(),
+            ask_walls: Vec::new(),
         }
     }
 
-    pub fn update(&mut self, book: OrderBook) {
+    /// Stores the latest book and reports whether anything on screen changed.
+    /// With a fill preview open the whole depth feeds the estimate, otherwise
+    /// only the top `max_entries` levels per side are drawn.
+    pub fn update(&mut self, book: OrderBook) -> bool {
+        let depth = if self.preview.is_some() || self.follow_trades {
+            usize::MAX
+        } else {
+            self.max_entries
+        };
+        let previous_walls = self.nearest_walls();
+        let mut changed = self.orderbook.as_ref().is_none_or(|prev| {
+            let top = |levels: &[crate::data::orderbook::OrderBookEntry]| {
+                levels.iter().take(depth).cloned().collect::<Vec<_>>()
+            };
+            top(&prev.bids) != top(&book.bids) || top(&prev.asks) != top(&book.asks)
+        });
+        if let Some(spread) = book.spread_pct() {
+            changed |= self.spread.record(spread);
+        }
+        self.bid_walls = detect_walls(&book.bids, WALL_FACTOR);
+        self.ask_walls = detect_walls(&book.asks, WALL_FACTOR);
         self.orderbook = Some(book);
+        changed |= self.nearest_walls() != previous_walls;
+        changed
+    }
+
+    /// Prices of the walls closest to the spread on each side (bid, ask).
+    pub fn nearest_walls(&self) -> (Option<f64>, Option<f64>) {
+        let Some(book) = &self.orderbook else {
+            return (None, None);
+        };
+        let nearest = |levels: &[OrderBookEntry], walls: &[usize]| {
+            walls.first().map(|idx| levels[*idx].price)
+        };
+        (
+            nearest(&book.bids, &self.bid_walls),
+            nearest(&book.asks, &self.ask_walls),
+        )
+    }
+
+    pub fn cycle_view(&mut self) {
+        self.view = self.view.next();
+    }
+
+    /// Ask and bid offsets of the first level shown.
+    fn offsets(&self, book: &OrderBook) -> (usize, usize) {
+        match self.last_trade {
+            Some(price) if self.follow_trades => follow_offsets(book, price),
+            _ => (0, 0),
+        }
     }
 
-    pub fn render(&self, frame: &mut Frame, area: Rect) {
-        let block = Block::default()
-            .title("Order Book")
-            .borders(Borders::ALL)
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let follow = if self.follow_trades { " · Follow" } else { "" };
+        let mut block = Block::default()
+            .title(format!("Order Book · {}{}", self.view.label(), follow))
+            .borders(theme.borders())
             .border_style(Style::default().fg(Color::Magenta));
-        let inner = block.inner(area);
+        let (bid_wall, ask_wall) = self.nearest_walls();
+        let wall_labels: Vec<String> = [("B", bid_wall), ("A", ask_wall)]
+            .into_iter()
+            .filter_map(|(side, price)| price.map(|p| format!("{side} {}", theme.format_price(p))))
+            .collect();
+        if !wall_labels.is_empty() {
+            block = block.title(
+                Line::styled(
+                    format!(" Wall {} ", wall_labels.join(" ")),
+                    Style::default().fg(Color::Yellow),
+                )
+                .right_aligned(),
+            );
+        }
+        let block = mark_stale(block, self.stale);
+        let mut inner = block.inner(area);
         frame.render_widget(block, area);
 
+        if let (Some(book), Some((side, size))) = (&self.orderbook, self.preview) {
+            let lines = self.preview_lines(book, side, size, theme);
+            let height = lines.len() as u16;
+            if inner.height > height + 2 {
+                let preview_area = Rect {
+                    x: inner.x,
+                    y: inner.y + inner.height - height,
+                    width: inner.width,
+                    height,
+                };
+                inner.height -= height;
+                frame.render_widget(Paragraph::new(lines), preview_area);
+            }
+        }
+
         if let Some(book) = &self.orderbook {
+            let view = if inner.width < MIN_SHADED_WIDTH {
+                BookViewMode::Ladder
+            } else {
+                self.view
+            };
             let asks_height = (inner.height.saturating_sub(2) / 2).min(self.max_entries as u16);
             let bids_height = inner.height.saturating_sub(2).saturating_sub(asks_height);
 
@@ -50,8 +285,31 @@ impl OrderBookPanel {
                 height: bids_height,
             };
 
-            self.render_side(&book.asks, asks_area, frame, true);
-            self.render_side(&book.bids, bids_area, frame, false);
+            let (ask_start, bid_start) = self.offsets(book);
+            let shifted = |walls: &[usize], start: usize| -> Vec<usize> {
+                walls
+                    .iter()
+                    .filter_map(|idx| idx.checked_sub(start))
+                    .collect()
+            };
+            self.render_side(
+                &book.asks[ask_start..],
+                &shifted(&self.ask_walls, ask_start),
+                asks_area,
+                frame,
+                theme.down,
+                view,
+                theme,
+            );
+            self.render_side(
+                &book.bids[bid_start..],
+                &shifted(&self.bid_walls, bid_start),
+                bids_area,
+                frame,
+                theme.up,
+                view,
+                theme,
+            );
         } else {
             let text = Line::from(Span::styled("Loading...", Style::default().fg(Color::Gray)));
             let para = Paragraph::new(text).alignment(Alignment::Center);
@@ -59,15 +317,83 @@ impl OrderBookPanel {
         }
     }
 
+    fn preview_lines(
+        &self,
+        book: &OrderBook,
+        side: Side,
+        size: f64,
+        theme: &Theme,
+    ) -> Vec<Line<'static>> {
+        let fill = simulate_fill(book, side, size);
+        let side_color = theme.direction(side == Side::Buy);
+
+        let summary = Line::from(vec![
+            Span::styled(
+                format!("{} {} ", side.label(), size),
+                Style::default().fg(side_color).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("@ ", Style::default().fg(Color::Gray)),
+            Span::styled(
+                theme.format_price(fill.avg_price),
+                Style::default().fg(Color::White),
+            ),
+            Span::styled(
+                format!(" ({:+.3}%)", fill.slippage_pct),
+                Style::default().fg(Color::Yellow),
+            ),
+        ]);
+
+        let detail = if fill.unfilled > 0.0 {
+            Line::from(Span::styled(
+                format!("Partial: {} filled, {} left", fill.filled, fill.unfilled),
+                Style::default().fg(theme.down),
+            ))
+        } else {
+            Line::from(Span::styled(
+                "Fully filled from visible depth",
+                Style::default().fg(Color::Gray),
+            ))
+        };
+
+        let mut lines = vec![summary, detail];
+        if let Some(rules) = &self.lot_rules {
+            let price = if fill.filled > 0.0 {
+                fill.avg_price
+            } else {
+                book.asks
+                    .first()
+                    .or(book.bids.first())
+                    .map_or(0.0, |l| l.price)
+            };
+            let check = check_order_size(rules, size, price);
+            if !check.issues.is_empty() {
+                let issues: Vec<&str> = check.issues.iter().map(|issue| issue.label()).collect();
+                lines.push(Line::from(Span::styled(
+                    format!(
+                        "Valid size {:.*}: {}",
+                        rules.size_decimals(),
+                        check.valid_size,
+                        issues.join(", ")
+                    ),
+                    Style::default().fg(Color::Yellow),
+                )));
+            }
+        }
+        lines
+    }
+
+    #[allow(clippy::too_many_arguments)]
     fn render_side(
         &self,
-        entries: &[crate::data::orderbook::OrderBookEntry],
+        entries: &[OrderBookEntry],
+        walls: &[usize],
         area: Rect,
         frame: &mut Frame,
-        is_asks: bool,
+        color: Color,
+        view: BookViewMode,
+        theme: &Theme,
     ) {
-        let color = if is_asks { Color::Red } else { Color::Green };
-
+        let volume_format = theme.volume_format;
         let header = Line::from(vec![Span::styled(
             format!("{:>12} {:>12}", "Price", "Size"),
             Style::default()
@@ -89,17 +415,31 @@ impl OrderBookPanel {
             .iter()
             .take(area.height.saturating_sub(1) as usize)
             .collect();
+        let shading = view.shading(&display_entries);
 
         for (idx, entry) in display_entries.iter().enumerate() {
             let y = area.y + 1 + idx as u16;
             if y < area.y + area.height {
-                let price_str = format!("{:>12.2}", entry.price);
-                let qty_str = format!("{:>12.4}", entry.quantity);
-                let line = Line::from(vec![
-                    Span::styled(price_str, Style::default().fg(color)),
+                let price_str = format!("{:>12}", theme.format_price(entry.price));
+                let qty_str = format!("{:>12}", format_volume(entry.quantity, 4, volume_format));
+                let is_wall = walls.contains(&idx);
+                let emphasis = if is_wall {
+                    Modifier::BOLD
+                } else {
+                    Modifier::empty()
+                };
+                let mut spans = vec![
+                    Span::styled(price_str, Style::default().fg(color).add_modifier(emphasis)),
                     Span::raw(" "),
-                    Span::styled(qty_str, Style::default().fg(Color::White)),
-                ]);
+                    Span::styled(
+                        qty_str,
+                        Style::default().fg(Color::White).add_modifier(emphasis),
+                    ),
+                ];
+                if is_wall {
+                    spans.push(Span::styled(" ◀", Style::default().fg(color)));
+                }
+                let line = Line::from(spans);
                 let para = Paragraph::new(line);
                 frame.render_widget(
                     para,
@@ -110,7 +450,136 @@ impl OrderBookPanel {
                         height: 1,
                     },
                 );
+
+                let Some(level) = shading.as_ref().map(|shading| shading[idx]) else {
+                    continue;
+                };
+                // Depth bars grow from the right edge; heat shades the row.
+                let (columns, strength) = match view {
+                    BookViewMode::Cumulative => {
+                        ((level * f64::from(area.width)).round() as u16, 0.35)
+                    }
+                    _ => (area.width, 0.1 + 0.5 * level),
+                };
+                let bg = theme.adapt(scale_color(color, strength));
+                for x in area.right().saturating_sub(columns)..area.right() {
+                    frame.buffer_mut()[(x, y)].set_bg(bg);
+                }
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn book(levels: &[(f64, f64)], last_update: u64) -> OrderBook {
+        let entries: Vec<OrderBookEntry> = levels
+            .iter()
+            .map(|(price, quantity)| OrderBookEntry {
+                price: *price,
+                quantity: *quantity,
+            })
+            .collect();
+        OrderBook {
+            bids: entries.clone(),
+            asks: entries,
+            last_update,
+        }
+    }
+
+    fn levels(sizes: &[f64]) -> Vec<OrderBookEntry> {
+        sizes
+            .iter()
+            .enumerate()
+            .map(|(idx, quantity)| OrderBookEntry {
+                price: 100.0 - idx as f64,
+                quantity: *quantity,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn walls_are_levels_well_above_the_median() {
+        let side = levels(&[1.0, 2.0, 9.0, 1.5, 2.5, 30.0]);
+        // Median is 2.25, so only sizes above 6.75 qualify.
+        assert_eq!(detect_walls(&side, 3.0), vec![2, 5]);
+        assert_eq!(detect_walls(&side, 10.0), vec![5]);
+        assert!(detect_walls(&levels(&[1.0, 1.0, 50.0, 1.0]), 3.0).is_empty());
+        assert!(detect_walls(&levels(&[0.0, 0.0, 0.0, 0.0, 5.0]), 3.0).is_empty());
+    }
+
+    #[test]
+    fn follow_starts_each_side_at_the_last_trade() {
+        let side = |prices: &[f64]| -> Vec<OrderBookEntry> {
+            prices
+                .iter()
+                .map(|price| OrderBookEntry {
+                    price: *price,
+                    quantity: 1.0,
+                })
+                .collect()
+        };
+        let book = OrderBook {
+            bids: side(&[100.0, 99.0, 98.0, 97.0]),
+            asks: side(&[101.0, 102.0, 103.0, 104.0]),
+            last_update: 0,
+        };
+        // Inside the spread nothing moves.
+        assert_eq!(follow_offsets(&book, 100.5), (0, 0));
+        // Price traded up through two asks the snapshot still shows.
+        assert_eq!(follow_offsets(&book, 102.5), (2, 0));
+        assert_eq!(follow_offsets(&book, 98.0), (0, 2));
+        // Past the whole side, its farthest level stays.
+        assert_eq!(follow_offsets(&book, 110.0), (3, 0));
+
+        let mut panel = OrderBookPanel::new();
+        panel.last_trade = Some(102.5);
+        assert_eq!(panel.offsets(&book), (0, 0));
+        panel.follow_trades = true;
+        assert_eq!(panel.offsets(&book), (2, 0));
+    }
+
+    #[test]
+    fn update_tracks_nearest_walls() {
+        let mut panel = OrderBookPanel::new();
+        panel.update(OrderBook {
+            bids: levels(&[1.0, 1.0, 8.0, 1.0, 20.0]),
+            asks: levels(&[1.0, 1.0, 1.0, 1.0, 1.0]),
+            last_update: 1,
+        });
+        assert_eq!(panel.bid_walls, vec![2, 4]);
+        assert_eq!(panel.nearest_walls(), (Some(98.0), None));
+    }
+
+    #[test]
+    fn view_shading_follows_depth_or_size() {
+        let side = levels(&[1.0, 3.0, 4.0]);
+        let rows: Vec<&OrderBookEntry> = side.iter().collect();
+        assert_eq!(BookViewMode::Ladder.shading(&rows), None);
+        assert_eq!(
+            BookViewMode::Cumulative.shading(&rows),
+            Some(vec![0.125, 0.5, 1.0])
+        );
+        assert_eq!(
+            BookViewMode::Heat.shading(&rows),
+            Some(vec![0.25, 0.75, 1.0])
+        );
+        assert_eq!(BookViewMode::Heat.next().next(), BookViewMode::Cumulative);
+    }
+
+    #[test]
+    fn update_reports_change_only_for_visible_levels() {
+        let mut panel = OrderBookPanel::new();
+        panel.max_entries = 1;
+
+        assert!(panel.update(book(&[(100.0, 1.0), (99.0, 1.0)], 1)));
+        assert!(!panel.update(book(&[(100.0, 1.0), (98.0, 5.0)], 2)));
+        assert_eq!(panel.orderbook.as_ref().unwrap().last_update, 2);
+        assert!(panel.update(book(&[(100.0, 2.0)], 3)));
+
+        panel.preview = Some((Side::Buy, 1.0));
+        assert!(panel.update(book(&[(100.0, 2.0), (97.0, 1.0)], 4)));
+    }
+}
@@ -0,0 +1,190 @@
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use std::time::{Duration, Instant};
+
+/// How often the symbol's exchange status is rechecked while data flows.
+const HEALTHY_POLL: Duration = Duration::from_secs(300);
+/// How often it is rechecked while the candle feed is stale.
+const STALE_POLL: Duration = Duration::from_secs(30);
+
+/// The active symbol's status according to `exchangeInfo`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExchangeStatus {
+    Trading,
+    /// Any other status, e.g. `BREAK` or `HALT`.
+    Suspended(String),
+    /// The status check itself failed.
+    Unreachable,
+}
+
+/// Tells an exchange outage apart from a local problem: once the candle feed
+/// goes stale the symbol's status is checked, and a banner is shown only if
+/// the exchange says it isn't trading or can't be reached at all.
+pub struct OutageWatch {
+    symbol: String,
+    status: Option<ExchangeStatus>,
+    requested_at: Option<Instant>,
+    checked_at: Option<Instant>,
+    stale_since: Option<Instant>,
+}
+
+impl OutageWatch {
+    pub fn new() -> Self {
+        Self {
+            symbol: String::new(),
+            status: None,
+            requested_at: None,
+            checked_at: None,
+            stale_since: None,
+        }
+    }
+
+    /// Follows the candle feed's staleness flag.
+    pub fn set_stale(&mut self, stale: bool, now: Instant) {
+        match (stale, self.stale_since) {
+            (true, None) => self.stale_since = Some(now),
+            (false, Some(_)) => self.stale_since = None,
+            _ => {}
+        }
+    }
+
+    pub fn needs_check(&self, symbol: &str, now: Instant) -> bool {
+        let Some(requested) = self.requested_at else {
+            return true;
+        };
+        if self.symbol != symbol || self.stale_since.is_some_and(|since| since > requested) {
+            return true;
+        }
+        let interval = if self.stale_since.is_some() {
+            STALE_POLL
+        } else {
+            HEALTHY_POLL
+        };
+        now.saturating_duration_since(requested) >= interval
+    }
+
+    pub fn begin_check(&mut self, symbol: &str, now: Instant) {
+        if self.symbol != symbol {
+            self.symbol = symbol.to_string();
+            self.status = None;
+            self.checked_at = None;
+        }
+        self.requested_at = Some(now);
+    }
+
+    /// Records a status check; results for a symbol no longer shown are
+    /// dropped.
+    pub fn apply(&mut self, symbol: &str, result: Result<String, String>, now: Instant) {
+        if symbol != self.symbol {
+            return;
+        }
+        self.status = Some(match result {
+            Ok(status) if status == "TRADING" => ExchangeStatus::Trading,
+            Ok(status) => ExchangeStatus::Suspended(status),
+            Err(_) => ExchangeStatus::Unreachable,
+        });
+        self.checked_at = Some(now);
+    }
+
+    /// Banner text while the feed is stale and a check made since then found
+    /// the exchange not trading the symbol.
+    pub fn banner(&self) -> Option<String> {
+        let since = self.stale_since?;
+        if self.checked_at? < since {
+            return None;
+        }
+        match self.status.as_ref()? {
+            ExchangeStatus::Trading => None,
+            ExchangeStatus::Suspended(status) => Some(format!(
+                "Market data unavailable — {} is in {} status",
+                self.symbol, status
+            )),
+            ExchangeStatus::Unreachable => {
+                Some("Market data unavailable — maintenance?".to_string())
+            }
+        }
+    }
+}
+
+impl Default for OutageWatch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Draws `text` in a red box across the top of the screen.
+pub fn render_banner(frame: &mut Frame, text: &str) {
+    let area = frame.area();
+    let width = (text.chars().count() as u16 + 4).min(area.width);
+    let height = 3.min(area.height);
+    let banner = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + 3.min(area.height - height),
+        width,
+        height,
+    };
+    let style = Style::default()
+        .fg(Color::White)
+        .bg(Color::Red)
+        .add_modifier(Modifier::BOLD);
+
+    frame.render_widget(Clear, banner);
+    frame.render_widget(
+        Paragraph::new(Line::from(text.to_string()))
+            .style(style)
+            .alignment(Alignment::Center)
+            .block(Block::default().borders(Borders::ALL).style(style)),
+        banner,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn banner_needs_staleness_and_a_failed_check() {
+        let start = Instant::now();
+        let mut watch = OutageWatch::new();
+        assert!(watch.needs_check("BTCUSDT", start));
+        watch.begin_check("BTCUSDT", start);
+        watch.apply("BTCUSDT", Ok("BREAK".to_string()), start);
+        // Not stale: the status alone doesn't raise the banner.
+        assert_eq!(watch.banner(), None);
+        assert!(!watch.needs_check("BTCUSDT", start + Duration::from_secs(60)));
+
+        // Going stale triggers a fresh check before the banner shows.
+        let stale_at = start + Duration::from_secs(60);
+        watch.set_stale(true, stale_at);
+        assert_eq!(watch.banner(), None);
+        assert!(watch.needs_check("BTCUSDT", stale_at));
+        watch.begin_check("BTCUSDT", stale_at);
+        watch.apply("BTCUSDT", Ok("BREAK".to_string()), stale_at);
+        assert_eq!(
+            watch.banner().as_deref(),
+            Some("Market data unavailable — BTCUSDT is in BREAK status")
+        );
+        assert!(watch.needs_check("BTCUSDT", stale_at + STALE_POLL));
+
+        watch.apply("BTCUSDT", Err("timed out".to_string()), stale_at);
+        assert_eq!(
+            watch.banner().as_deref(),
+            Some("Market data unavailable — maintenance?")
+        );
+        watch.apply("ETHUSDT", Ok("TRADING".to_string()), stale_at);
+        assert!(watch.banner().is_some());
+        watch.apply("BTCUSDT", Ok("TRADING".to_string()), stale_at);
+        assert_eq!(watch.banner(), None);
+
+        // Data resuming clears it.
+        watch.apply("BTCUSDT", Err("timed out".to_string()), stale_at);
+        watch.set_stale(false, stale_at + Duration::from_secs(5));
+        assert_eq!(watch.banner(), None);
+        assert!(watch.needs_check("ETHUSDT", stale_at));
+    }
+}
@@ -0,0 +1,192 @@
+use ratatui::{
+    buffer::Buffer,
+    style::{Color, Style},
+    text::{Line, Span},
+};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// How an overlay's points are joined, so overlays stay apart when colors
+/// alone don't.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum LineStyle {
+    /// A glyph per candle, unconnected.
+    #[default]
+    Dotted,
+    /// Points joined along the straight line between them.
+    Solid,
+    /// Each value held flat until the next candle, then a vertical jump.
+    Stepped,
+}
+
+impl LineStyle {
+    /// A three-cell sample for the legend.
+    fn sample(self, glyph: char) -> String {
+        match self {
+            LineStyle::Dotted => glyph.to_string().repeat(3),
+            LineStyle::Solid => "───".to_string(),
+            LineStyle::Stepped => "┌─┘".to_string(),
+        }
+    }
+}
+
+/// Line style per overlay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LineStyles {
+    pub sma: LineStyle,
+    pub rsi: LineStyle,
+}
+
+/// Draws one overlay from its points, one per candle column in order; `None`
+/// breaks the line. Points overwrite what is under them, but the segments
+/// joining them only fill blank cells so candles stay visible.
+pub fn draw_line(
+    buffer: &mut Buffer,
+    points: &[Option<(u16, u16)>],
+    style: LineStyle,
+    glyph: char,
+    color: Color,
+) {
+    if style != LineStyle::Dotted {
+        for pair in points.windows(2) {
+            if let [Some(from), Some(to)] = *pair {
+                connect(buffer, from, to, style, color);
+            }
+        }
+    }
+    for &(x, y) in points.iter().flatten() {
+        buffer[(x, y)].set_char(glyph).set_fg(color);
+    }
+}
+
+fn connect(
+    buffer: &mut Buffer,
+    (x0, y0): (u16, u16),
+    (x1, y1): (u16, u16),
+    style: LineStyle,
+    color: Color,
+) {
+    if x1 <= x0 {
+        return;
+    }
+    let mut prev = y0;
+    for x in x0 + 1..=x1 {
+        let y = match style {
+            LineStyle::Stepped if x < x1 => y0,
+            LineStyle::Stepped => y1,
+            _ => {
+                let t = f64::from(x - x0) / f64::from(x1 - x0);
+                (f64::from(y0) + (f64::from(y1) - f64::from(y0)) * t).round() as u16
+            }
+        };
+        // The previous column already covers `prev`, so a move only fills
+        // the rows beyond it.
+        match y.cmp(&prev) {
+            Ordering::Equal => fill(buffer, x, y, '─', color),
+            Ordering::Greater => (prev + 1..=y).for_each(|row| fill(buffer, x, row, '│', color)),
+            Ordering::Less => (y..prev).for_each(|row| fill(buffer, x, row, '│', color)),
+        }
+        prev = y;
+    }
+}
+
+fn fill(buffer: &mut Buffer, x: u16, y: u16, ch: char, color: Color) {
+    let cell = &mut buffer[(x, y)];
+    if cell.symbol() == " " {
+        cell.set_char(ch).set_fg(color);
+    }
+}
+
+/// Rows for labels that would sit on `rows`, nudged apart so no two share a
+/// row while all stay within `top..bottom`. Results are in input order.
+pub fn spread_rows(rows: &[u16], top: u16, bottom: u16) -> Vec<u16> {
+    let mut order: Vec<usize> = (0..rows.len()).collect();
+    order.sort_by_key(|&idx| rows[idx]);
+    let mut placed = vec![0; rows.len()];
+    let mut next = top;
+    for &idx in &order {
+        placed[idx] = rows[idx].max(next);
+        next = placed[idx] + 1;
+    }
+    // Push back up whatever ran off the bottom.
+    let mut limit = bottom;
+    for &idx in order.iter().rev() {
+        placed[idx] = placed[idx].min(limit.saturating_sub(1)).max(top);
+        limit = placed[idx];
+    }
+    placed
+}
+
+/// `SMA20 ───  RSI14 ···` for the overlays that are on.
+pub fn legend(entries: &[(String, LineStyle, char, Color)]) -> Line<'static> {
+    let mut spans = Vec::new();
+    for (label, style, glyph, color) in entries {
+        if !spans.is_empty() {
+            spans.push(Span::raw("  "));
+        }
+        spans.push(Span::styled(
+            format!("{label} {}", style.sample(*glyph)),
+            Style::default().fg(*color),
+        ));
+    }
+    Line::from(spans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::layout::Rect;
+
+    fn rows(buffer: &Buffer) -> Vec<String> {
+        (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol().to_string())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn styles_join_points_differently() {
+        let points = [Some((0, 0)), Some((2, 0)), Some((4, 2)), None, Some((6, 0))];
+        let draw = |style| {
+            let mut buffer = Buffer::empty(Rect::new(0, 0, 7, 3));
+            draw_line(&mut buffer, &points, style, '•', Color::Cyan);
+            rows(&buffer)
+        };
+
+        assert_eq!(
+            draw(LineStyle::Dotted),
+            vec!["• •   •", "       ", "    •  "]
+        );
+        assert_eq!(
+            draw(LineStyle::Solid),
+            vec!["•─•   •", "   │   ", "    •  "]
+        );
+        assert_eq!(
+            draw(LineStyle::Stepped),
+            vec!["•─•─  •", "    │  ", "    •  "]
+        );
+    }
+
+    #[test]
+    fn spread_rows_keeps_labels_apart_and_in_bounds() {
+        assert_eq!(spread_rows(&[5, 9], 0, 10), vec![5, 9]);
+        assert_eq!(spread_rows(&[5, 5, 4], 0, 10), vec![5, 6, 4]);
+        // Crowded at the bottom edge, so the stack moves up.
+        assert_eq!(spread_rows(&[9, 9, 9], 0, 10), vec![7, 8, 9]);
+        assert_eq!(spread_rows(&[2, 3], 2, 3), vec![2, 2]);
+    }
+
+    #[test]
+    fn legend_lists_each_overlay_with_a_sample() {
+        let line = legend(&[
+            ("SMA20".to_string(), LineStyle::Solid, '•', Color::Cyan),
+            ("RSI14".to_string(), LineStyle::Dotted, '·', Color::Magenta),
+        ]);
+        let text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(text, "SMA20 ───  RSI14 ···");
+    }
+}
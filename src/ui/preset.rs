@@ -0,0 +1,90 @@
+use crate::ui::orderbook::BookViewMode;
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_SIDE_WIDTH: u16 = 30;
+
+/// Which panels are shown, and chart extras switched on with them. `None`
+/// leaves the saved setting alone.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PanelLayout {
+    pub show_watchlist: bool,
+    /// The order book and trade tape column.
+    pub show_side_panels: bool,
+    pub side_width: u16,
+    pub show_heatmap: Option<bool>,
+    pub book_view: Option<BookViewMode>,
+}
+
+impl Default for PanelLayout {
+    fn default() -> Self {
+        Self {
+            show_watchlist: true,
+            show_side_panels: true,
+            side_width: DEFAULT_SIDE_WIDTH,
+            show_heatmap: None,
+            book_view: None,
+        }
+    }
+}
+
+impl PanelLayout {
+    pub fn sanitized(mut self) -> Self {
+        self.side_width = self.side_width.clamp(24, 80);
+        self
+    }
+}
+
+/// Named startup layout; `Custom` uses `custom_layout` from the config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum LayoutPreset {
+    /// Watchlist, chart, order book and tape as usual.
+    #[default]
+    Standard,
+    /// The chart alone, full width.
+    ChartFocused,
+    /// A wide order book and tape beside the chart with trade heat bands,
+    /// no watchlist.
+    Orderflow,
+    Custom,
+}
+
+impl LayoutPreset {
+    pub fn panels(self, custom: &PanelLayout) -> PanelLayout {
+        match self {
+            LayoutPreset::Standard => PanelLayout::default(),
+            LayoutPreset::ChartFocused => PanelLayout {
+                show_watchlist: false,
+                show_side_panels: false,
+                ..PanelLayout::default()
+            },
+            LayoutPreset::Orderflow => PanelLayout {
+                show_watchlist: false,
+                side_width: 48,
+                show_heatmap: Some(true),
+                book_view: Some(BookViewMode::Cumulative),
+                ..PanelLayout::default()
+            },
+            LayoutPreset::Custom => custom.clone().sanitized(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn custom_preset_is_sanitized_and_partial() {
+        let custom: PanelLayout =
+            serde_json::from_str(r#"{"show_watchlist": false, "side_width": 500}"#).unwrap();
+        let panels = LayoutPreset::Custom.panels(&custom);
+        assert!(!panels.show_watchlist);
+        assert!(panels.show_side_panels);
+        assert_eq!(panels.side_width, 80);
+        assert_eq!(panels.show_heatmap, None);
+
+        let focused = LayoutPreset::ChartFocused.panels(&custom);
+        assert!(!focused.show_watchlist && !focused.show_side_panels);
+    }
+}
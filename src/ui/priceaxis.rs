@@ -0,0 +1,55 @@
+/// The largest 1, 2 or 5 × 10ⁿ step that divides `range` into at most
+/// `max_ticks` intervals, so labels land on round prices. `None` for an
+/// empty or non-finite range.
+pub fn nice_step(range: f64, max_ticks: usize) -> Option<f64> {
+    if !range.is_finite() || range <= 0.0 || max_ticks == 0 {
+        return None;
+    }
+    let raw = range / max_ticks as f64;
+    let magnitude = 10f64.powf(raw.log10().floor());
+    let residual = raw / magnitude;
+    let nice = [1.0, 2.0, 5.0, 10.0]
+        .into_iter()
+        .find(|candidate| *candidate >= residual - 1e-9)
+        .unwrap_or(10.0);
+    Some(nice * magnitude)
+}
+
+/// Multiples of the nice step between `low` and `high`, lowest first, with
+/// the number of decimals needed to print them.
+pub fn price_ticks(low: f64, high: f64, max_ticks: usize) -> (Vec<f64>, usize) {
+    let Some(step) = nice_step(high - low, max_ticks) else {
+        return (Vec::new(), 0);
+    };
+    let decimals = (-step.log10().floor()).max(0.0) as usize;
+    let first = (low / step).ceil() as i64;
+    let last = (high / step + 1e-9).floor() as i64;
+    let ticks = (first..=last).map(|n| n as f64 * step).collect();
+    (ticks, decimals)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steps_snap_to_one_two_five() {
+        assert_eq!(nice_step(100.0, 5), Some(20.0));
+        assert_eq!(nice_step(130.0, 5), Some(50.0));
+        assert_eq!(nice_step(0.9, 10), Some(0.1));
+        assert_eq!(nice_step(0.0, 5), None);
+        assert_eq!(nice_step(10.0, 0), None);
+    }
+
+    #[test]
+    fn ticks_land_on_round_prices_inside_the_range() {
+        let (ticks, decimals) = price_ticks(69_387.21, 69_713.67, 5);
+        assert_eq!(ticks, vec![69_400.0, 69_500.0, 69_600.0, 69_700.0]);
+        assert_eq!(decimals, 0);
+
+        let (ticks, decimals) = price_ticks(0.5012, 0.5291, 4);
+        assert_eq!(decimals, 2);
+        assert_eq!(ticks.len(), 2);
+        assert!((ticks[0] - 0.51).abs() < 1e-9);
+    }
+}
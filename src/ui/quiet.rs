@@ -0,0 +1,116 @@
+use serde::{Deserialize, Serialize};
+
+const SECS_PER_HOUR: u64 = 3600;
+
+/// A daily UTC window, `start_hour` inclusive to `end_hour` exclusive. The
+/// window may wrap midnight (e.g. 22 to 6); equal hours never match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuietHours {
+    pub start_hour: u8,
+    pub end_hour: u8,
+}
+
+impl QuietHours {
+    pub fn is_valid(&self) -> bool {
+        self.start_hour < 24 && self.end_hour < 24
+    }
+
+    pub fn contains(&self, unix_secs: u64) -> bool {
+        let hour = ((unix_secs / SECS_PER_HOUR) % 24) as u8;
+        if self.start_hour <= self.end_hour {
+            (self.start_hour..self.end_hour).contains(&hour)
+        } else {
+            hour >= self.start_hour || hour < self.end_hour
+        }
+    }
+}
+
+/// Whether the UI is currently dimmed: follows the schedule unless the user
+/// forced a state, and a forced state lasts until the schedule next flips.
+#[derive(Debug, Clone, Default)]
+pub struct QuietMode {
+    pub hours: Option<QuietHours>,
+    forced: Option<bool>,
+    scheduled: bool,
+}
+
+impl QuietMode {
+    pub fn new(hours: Option<QuietHours>) -> Self {
+        Self {
+            hours,
+            forced: None,
+            scheduled: false,
+        }
+    }
+
+    pub fn is_dim(&self) -> bool {
+        self.forced.unwrap_or(self.scheduled)
+    }
+
+    pub fn toggle(&mut self) {
+        self.forced = Some(!self.is_dim());
+    }
+
+    /// Re-checks the schedule against the clock; returns true when the dim
+    /// state changed and the screen needs a redraw.
+    pub fn update(&mut self, unix_secs: u64) -> bool {
+        let was_dim = self.is_dim();
+        let scheduled = self.hours.is_some_and(|hours| hours.contains(unix_secs));
+        if scheduled != self.scheduled {
+            self.scheduled = scheduled;
+            self.forced = None;
+        }
+        was_dim != self.is_dim()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at_hour(hour: u64) -> u64 {
+        19_000 * 86_400 + hour * SECS_PER_HOUR + 59
+    }
+
+    #[test]
+    fn window_wraps_midnight() {
+        let overnight = QuietHours {
+            start_hour: 22,
+            end_hour: 6,
+        };
+        let contained: Vec<u64> = (0..24)
+            .filter(|h| overnight.contains(at_hour(*h)))
+            .collect();
+        assert_eq!(contained, vec![0, 1, 2, 3, 4, 5, 22, 23]);
+
+        let daytime = QuietHours {
+            start_hour: 9,
+            end_hour: 11,
+        };
+        assert!(!daytime.contains(at_hour(8)));
+        assert!(daytime.contains(at_hour(10)));
+        assert!(!daytime.contains(at_hour(11)));
+    }
+
+    #[test]
+    fn forced_state_holds_until_schedule_flips() {
+        let mut quiet = QuietMode::new(Some(QuietHours {
+            start_hour: 22,
+            end_hour: 6,
+        }));
+        assert!(quiet.update(at_hour(23)));
+        assert!(quiet.is_dim());
+
+        quiet.toggle();
+        assert!(!quiet.is_dim());
+        assert!(!quiet.update(at_hour(2)));
+        assert!(!quiet.is_dim());
+
+        // Leaving the window clears the override; the schedule says bright.
+        assert!(!quiet.update(at_hour(7)));
+        quiet.toggle();
+        assert!(quiet.is_dim());
+        assert!(!quiet.update(at_hour(22)));
+        assert!(quiet.is_dim());
+    }
+}
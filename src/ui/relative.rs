@@ -0,0 +1,137 @@
+use crate::data::Candle;
+use std::time::{Duration, Instant};
+
+/// The symbol every chart is compared against.
+pub const BENCHMARK: &str = "BTCUSDT";
+const REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Price of each candle relative to the benchmark's close for the same
+/// period, rebased so the first aligned candle reads 100. Candles are
+/// matched on close time, which also lines up merged (fit-to-width) candles;
+/// those without a benchmark candle are `None`.
+pub fn relative_strength(candles: &[&Candle], benchmark: &[(u64, f64)]) -> Vec<Option<f64>> {
+    let mut base = None;
+    candles
+        .iter()
+        .map(|candle| {
+            let close: f64 = candle.close.parse().ok()?;
+            let idx = benchmark
+                .binary_search_by_key(&candle.close_time, |(time, _)| *time)
+                .ok()?;
+            let reference = benchmark[idx].1;
+            if reference <= 0.0 {
+                return None;
+            }
+            let ratio = close / reference;
+            let base = *base.get_or_insert(ratio);
+            (base > 0.0).then(|| ratio / base * 100.0)
+        })
+        .collect()
+}
+
+/// Benchmark closes for the chart's interval, refetched periodically while
+/// the comparison is shown.
+#[derive(Debug, Clone)]
+pub struct RelativeStrength {
+    pub visible: bool,
+    interval: String,
+    /// `(close_time, close)` sorted by time.
+    closes: Vec<(u64, f64)>,
+    last_requested: Option<Instant>,
+}
+
+impl RelativeStrength {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            interval: String::new(),
+            closes: Vec::new(),
+            last_requested: None,
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+        self.last_requested = None;
+    }
+
+    pub fn closes(&self) -> &[(u64, f64)] {
+        &self.closes
+    }
+
+    pub fn needs_refresh(&self, symbol: &str, interval: &str, now: Instant) -> bool {
+        self.visible
+            && symbol != BENCHMARK
+            && (self.interval != interval
+                || self
+                    .last_requested
+                    .is_none_or(|last| now.saturating_duration_since(last) >= REFRESH_INTERVAL))
+    }
+
+    pub fn begin_refresh(&mut self, interval: &str, now: Instant) {
+        if self.interval != interval {
+            self.interval = interval.to_string();
+            self.closes.clear();
+        }
+        self.last_requested = Some(now);
+    }
+
+    pub fn apply(&mut self, interval: &str, candles: &[Candle]) {
+        if self.interval != interval {
+            return;
+        }
+        self.closes = candles
+            .iter()
+            .filter_map(|candle| Some((candle.close_time, candle.close.parse().ok()?)))
+            .collect();
+        self.closes.sort_by_key(|(time, _)| *time);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candle(close_time: u64, close: f64) -> Candle {
+        Candle {
+            is_closed: true,
+            ..crate::data::fetch::candle(close_time - 59_999, close, close, close, close)
+        }
+    }
+
+    #[test]
+    fn rebases_ratio_on_first_aligned_candle() {
+        let alt = [
+            candle(59_999, 1.0),
+            candle(119_999, 2.0),
+            candle(179_999, 3.0),
+        ];
+        let refs: Vec<&Candle> = alt.iter().collect();
+        // No benchmark candle for the first period; BTC doubles over the rest.
+        let btc = [(119_999, 50_000.0), (179_999, 100_000.0)];
+
+        let values = relative_strength(&refs, &btc);
+        assert_eq!(values[0], None);
+        assert_eq!(values[1], Some(100.0));
+        assert_eq!(values[2], Some(75.0));
+    }
+
+    #[test]
+    fn empty_benchmark_yields_no_values() {
+        let alt = [candle(59_999, 1.0)];
+        let refs: Vec<&Candle> = alt.iter().collect();
+        assert_eq!(relative_strength(&refs, &[]), vec![None]);
+    }
+
+    #[test]
+    fn refresh_skips_the_benchmark_itself() {
+        let mut relative = RelativeStrength::new();
+        relative.toggle();
+        let now = Instant::now();
+        assert!(!relative.needs_refresh(BENCHMARK, "1m", now));
+        assert!(relative.needs_refresh("ETHUSDT", "1m", now));
+        relative.begin_refresh("1m", now);
+        assert!(!relative.needs_refresh("ETHUSDT", "1m", now));
+        assert!(relative.needs_refresh("ETHUSDT", "5m", now));
+    }
+}
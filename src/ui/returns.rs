@@ -0,0 +1,217 @@
+use crate::data::Candle;
+use crate::ui::indicators::{transform_closes, IndicatorBasis};
+use crate::ui::Theme;
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Paragraph},
+    Frame,
+};
+use std::ops::Range;
+
+/// Number of return buckets drawn when the panel is tall enough.
+pub const RETURN_BINS: usize = 9;
+/// Below this many candles the distribution says little, so the panel shows
+/// a placeholder instead.
+pub const MIN_RETURNS: usize = 10;
+
+/// Percent change of each candle, skipping unparsable or zero-priced ones:
+/// simple open-to-close on the price basis, log open-to-close on the
+/// log-price basis, and log close-to-close on the log-return basis.
+pub fn candle_returns<'a>(
+    candles: impl IntoIterator<Item = &'a Candle>,
+    basis: IndicatorBasis,
+) -> Vec<f64> {
+    let candles = candles.into_iter();
+    if basis == IndicatorBasis::LogReturn {
+        let closes: Vec<f64> = candles
+            .map(|candle| candle.close.parse().unwrap_or(0.0))
+            .collect();
+        return transform_closes(&closes, basis)
+            .into_iter()
+            .flatten()
+            .map(|value| value * 100.0)
+            .collect();
+    }
+    candles
+        .filter_map(|candle| {
+            let open: f64 = candle.open.parse().ok()?;
+            let close: f64 = candle.close.parse().ok()?;
+            if open <= 0.0 {
+                return None;
+            }
+            match basis {
+                IndicatorBasis::Price => Some((close - open) / open * 100.0),
+                _ => (close > 0.0).then(|| (close / open).ln() * 100.0),
+            }
+        })
+        .collect()
+}
+
+/// Buckets the candles' percent returns into `bins` equal-width ranges
+/// spanning the smallest to the largest return, lowest first. The top bucket
+/// includes its upper bound.
+pub fn return_histogram<'a>(
+    candles: impl IntoIterator<Item = &'a Candle>,
+    bins: usize,
+    basis: IndicatorBasis,
+) -> Vec<(Range<f64>, usize)> {
+    let returns = candle_returns(candles, basis);
+    if returns.is_empty() || bins == 0 {
+        return Vec::new();
+    }
+
+    let min = returns.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = returns.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    // Identical returns still get a sliver of width so every bucket has a
+    // distinct range.
+    let width = (max - min).max(1e-9) / bins as f64;
+
+    let mut counts = vec![0; bins];
+    for value in returns {
+        let idx = (((value - min) / width) as usize).min(bins - 1);
+        counts[idx] += 1;
+    }
+
+    counts
+        .into_iter()
+        .enumerate()
+        .map(|(idx, count)| {
+            let start = min + width * idx as f64;
+            (start..start + width, count)
+        })
+        .collect()
+}
+
+pub struct ReturnsPanel {
+    pub visible: bool,
+}
+
+impl ReturnsPanel {
+    pub fn new() -> Self {
+        Self { visible: false }
+    }
+
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+    }
+
+    /// Draws the histogram of `candles`, highest returns at the top.
+    pub fn render(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        candles: &[&Candle],
+        basis: IndicatorBasis,
+        theme: &Theme,
+    ) {
+        let block = Block::default()
+            .title("Returns")
+            .borders(theme.borders())
+            .border_style(Style::default().fg(Color::Blue));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+        if inner.height == 0 || inner.width == 0 {
+            return;
+        }
+
+        let bins = RETURN_BINS.min(inner.height as usize);
+        let histogram = return_histogram(candles.iter().copied(), bins, basis);
+        let sample: usize = histogram.iter().map(|(_, count)| count).sum();
+        if sample < MIN_RETURNS {
+            let placeholder = Paragraph::new(Line::from(Span::styled(
+                format!("Need {MIN_RETURNS}+ candles"),
+                Style::default().fg(Color::DarkGray),
+            )))
+            .alignment(Alignment::Center);
+            frame.render_widget(
+                placeholder,
+                Rect {
+                    y: inner.y + inner.height / 2,
+                    height: 1,
+                    ..inner
+                },
+            );
+            return;
+        }
+
+        let peak = histogram
+            .iter()
+            .map(|(_, count)| *count)
+            .max()
+            .unwrap_or(1)
+            .max(1);
+        // "+12.34% " label, then the bar, then the count.
+        let bar_width = (inner.width as usize).saturating_sub(8 + 4);
+        let lines: Vec<Line> = histogram
+            .iter()
+            .rev()
+            .map(|(range, count)| {
+                let mid = (range.start + range.end) / 2.0;
+                let filled = (count * bar_width).div_ceil(peak);
+                Line::from(vec![
+                    Span::styled(format!("{:>+7.2}%", mid), Style::default().fg(Color::Gray)),
+                    Span::styled(
+                        "█".repeat(filled),
+                        Style::default().fg(theme.direction(mid >= 0.0)),
+                    ),
+                    Span::styled(format!(" {count}"), Style::default().fg(Color::White)),
+                ])
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(lines), inner);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candle(open: f64, close: f64) -> Candle {
+        Candle {
+            is_closed: true,
+            ..crate::data::fetch::candle(0, open, open.max(close), open.min(close), close)
+        }
+    }
+
+    #[test]
+    fn histogram_buckets_returns_between_extremes() {
+        let candles = [
+            candle(100.0, 98.0),
+            candle(100.0, 100.0),
+            candle(100.0, 101.0),
+            candle(100.0, 102.0),
+        ];
+        let histogram = return_histogram(&candles, 2, IndicatorBasis::Price);
+        assert_eq!(histogram.len(), 2);
+        assert!((histogram[0].0.start + 2.0).abs() < 1e-9);
+        assert!((histogram[1].0.end - 2.0).abs() < 1e-9);
+        // Only -2% is below the 0% boundary; the top bucket keeps +2%.
+        assert_eq!(histogram[0].1, 1);
+        assert_eq!(histogram[1].1, 3);
+    }
+
+    #[test]
+    fn histogram_handles_flat_and_empty_windows() {
+        assert!(return_histogram(&[], 5, IndicatorBasis::Price).is_empty());
+        let flat = [candle(100.0, 100.0), candle(50.0, 50.0)];
+        let histogram = return_histogram(&flat, 3, IndicatorBasis::Price);
+        assert_eq!(histogram.iter().map(|(_, count)| count).sum::<usize>(), 2);
+    }
+
+    #[test]
+    fn log_bases_use_log_returns() {
+        let candles = [candle(100.0, 110.0), candle(110.0, 99.0), candle(0.0, 5.0)];
+        let log_price = candle_returns(&candles, IndicatorBasis::LogPrice);
+        assert_eq!(log_price.len(), 2);
+        assert!((log_price[0] - 1.1f64.ln() * 100.0).abs() < 1e-9);
+        assert!((log_price[1] - 0.9f64.ln() * 100.0).abs() < 1e-9);
+
+        // Close to close: 110 -> 99 -> 5.
+        let log_return = candle_returns(&candles, IndicatorBasis::LogReturn);
+        assert_eq!(log_return.len(), 2);
+        assert!((log_return[0] - 0.9f64.ln() * 100.0).abs() < 1e-9);
+        assert!((log_return[1] - (5.0f64 / 99.0).ln() * 100.0).abs() < 1e-9);
+    }
+}
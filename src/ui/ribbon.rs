@@ -0,0 +1,145 @@
+use crate::data::WatchPrice;
+use crate::ui::format::format_display_price;
+use crate::ui::Theme;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    Frame,
+};
+use std::collections::HashMap;
+use std::time::Instant;
+
+const SCROLL_COLS_PER_SEC: f64 = 10.0;
+const SEPARATOR: &str = "  •  ";
+
+/// Single-row footer that scrolls the watchlist's prices right to left,
+/// wrapping around like a stock ticker.
+pub struct TickerRibbon {
+    pub visible: bool,
+    started: Instant,
+}
+
+impl TickerRibbon {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            started: Instant::now(),
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+        self.started = Instant::now();
+    }
+
+    /// Scroll position in columns. Derived from elapsed time rather than a
+    /// per-frame counter so the speed doesn't depend on the frame rate.
+    fn offset(&self, now: Instant) -> usize {
+        let elapsed = now.saturating_duration_since(self.started).as_secs_f64();
+        (elapsed * SCROLL_COLS_PER_SEC) as usize
+    }
+
+    pub fn render(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        watchlist: &[String],
+        prices: &HashMap<String, WatchPrice>,
+        theme: &Theme,
+        now: Instant,
+    ) {
+        let cells = ribbon_cells(watchlist, prices, theme);
+        if cells.is_empty() || area.width == 0 || area.height == 0 {
+            return;
+        }
+
+        let start = self.offset(now) % cells.len();
+        for col in 0..area.width {
+            let (ch, style) = cells[(start + col as usize) % cells.len()];
+            let cell = &mut frame.buffer_mut()[(area.x + col, area.y)];
+            cell.set_char(ch).set_style(style);
+        }
+    }
+}
+
+/// One full cycle of the ribbon as styled characters.
+fn ribbon_cells(
+    watchlist: &[String],
+    prices: &HashMap<String, WatchPrice>,
+    theme: &Theme,
+) -> Vec<(char, Style)> {
+    let mut cells = Vec::new();
+    let mut push = |text: &str, style: Style| cells.extend(text.chars().map(|ch| (ch, style)));
+
+    for symbol in watchlist {
+        push(symbol, Style::default().fg(Color::White));
+        match prices.get(symbol) {
+            Some(price) => {
+                push(
+                    &format!(
+                        " {} ",
+                        format_display_price(price.last_price, theme.max_price_decimals)
+                    ),
+                    Style::default().fg(Color::Gray),
+                );
+                push(
+                    &format!("{:+.2}%", price.change_pct),
+                    Style::default().fg(theme.direction(price.change_pct >= 0.0)),
+                );
+            }
+            None => push(" ...", Style::default().fg(Color::DarkGray)),
+        }
+        push(SEPARATOR, Style::default().fg(Color::DarkGray));
+    }
+    cells
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn row_text(
+        ribbon: &TickerRibbon,
+        prices: &HashMap<String, WatchPrice>,
+        now: Instant,
+    ) -> String {
+        let watchlist = vec!["BTCUSDT".to_string(), "ETHUSDT".to_string()];
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(20, 1)).unwrap();
+        terminal
+            .draw(|f| ribbon.render(f, f.area(), &watchlist, prices, &Theme::default(), now))
+            .unwrap();
+        let buffer = terminal.backend().buffer().clone();
+        (0..20)
+            .map(|x| buffer[(x, 0)].symbol().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn ribbon_scrolls_with_time_and_wraps() {
+        let mut prices = HashMap::new();
+        prices.insert(
+            "BTCUSDT".to_string(),
+            WatchPrice {
+                symbol: "BTCUSDT".to_string(),
+                last_price: 100.0,
+                change_pct: 1.5,
+            },
+        );
+        let ribbon = TickerRibbon::new();
+        let start = ribbon.started;
+
+        assert_eq!(row_text(&ribbon, &prices, start), "BTCUSDT 100.00 +1.50");
+        assert_eq!(
+            row_text(&ribbon, &prices, start + Duration::from_millis(800)),
+            "100.00 +1.50%  •  ET"
+        );
+
+        // A full cycle is 42 columns, so 43 columns in it has wrapped around.
+        assert_eq!(
+            row_text(&ribbon, &prices, start + Duration::from_millis(4300)),
+            "TCUSDT 100.00 +1.50%"
+        );
+    }
+}
@@ -0,0 +1,101 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Rows shown in the symbol dropdown.
+pub const MAX_MATCHES: usize = 8;
+
+/// Symbols matching `query` case-insensitively: those starting with it first,
+/// then those merely containing it, each group in list order.
+pub fn symbol_matches(symbols: &[String], query: &str, limit: usize) -> Vec<String> {
+    let query = query.trim().to_uppercase();
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    let (prefix, substring): (Vec<&String>, Vec<&String>) = symbols
+        .iter()
+        .filter(|symbol| symbol.contains(&query))
+        .partition(|symbol| symbol.starts_with(&query));
+    prefix
+        .into_iter()
+        .chain(substring)
+        .take(limit)
+        .cloned()
+        .collect()
+}
+
+/// Draws the matches as a floating list just above the status bar, with
+/// `selected` highlighted.
+pub fn render_symbol_dropdown(frame: &mut Frame, matches: &[String], selected: usize) {
+    let area = frame.area();
+    if matches.is_empty() || area.height < 4 {
+        return;
+    }
+
+    let width = (matches.iter().map(|s| s.len()).max().unwrap_or(0) as u16 + 4)
+        .max(16)
+        .min(area.width);
+    let height = (matches.len() as u16 + 2).min(area.height.saturating_sub(1));
+    let popup = Rect {
+        x: area.x,
+        y: area.bottom().saturating_sub(1 + height),
+        width,
+        height,
+    };
+
+    let lines: Vec<Line> = matches
+        .iter()
+        .enumerate()
+        .map(|(idx, symbol)| {
+            let style = if idx == selected {
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            Line::from(Span::styled(format!(" {symbol} "), style))
+        })
+        .collect();
+
+    frame.render_widget(Clear, popup);
+    frame.render_widget(
+        Paragraph::new(lines).block(
+            Block::default()
+                .title("Symbols")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        ),
+        popup,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefix_matches_come_before_substring_matches() {
+        let symbols: Vec<String> = ["ADAUSDT", "BTCUSDT", "ETHBTC", "SOLBTC", "WBTCUSDT"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        assert_eq!(
+            symbol_matches(&symbols, "btc", 10),
+            vec!["BTCUSDT", "ETHBTC", "SOLBTC", "WBTCUSDT"]
+        );
+        assert_eq!(
+            symbol_matches(&symbols, "usdt", 2),
+            vec!["ADAUSDT", "BTCUSDT"]
+        );
+        assert!(symbol_matches(&symbols, "  ", 10).is_empty());
+        assert!(symbol_matches(&symbols, "XYZ", 10).is_empty());
+    }
+}
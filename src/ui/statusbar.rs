@@ -18,6 +18,16 @@ pub struct StatusBar {
     pub symbol: String,
     pub loading: bool,
     pub last_error: Option<String>,
+    pub input_prompt: Option<String>,
+    pub notice: Option<String>,
+    /// Average spread, in percent, while the book is flagged as thin.
+    pub wide_spread: Option<f64>,
+    /// No recent input: redraws are slowed and trades paused.
+    pub idle: bool,
+    /// Candles dropped for impossible prices since the symbol loaded.
+    pub dropped_candles: usize,
+    /// Trades skipped in the last second because the tape fell behind.
+    pub dropped_trades: usize,
 }
 
 impl StatusBar {
@@ -27,6 +37,12 @@ impl StatusBar {
             symbol: String::new(),
             loading: false,
             last_error: None,
+            input_prompt: None,
+            notice: None,
+            wide_spread: None,
+            idle: false,
+            dropped_candles: 0,
+            dropped_trades: 0,
         }
     }
 
@@ -50,7 +66,40 @@ impl StatusBar {
             Span::styled(mode_text, Style::default().fg(mode_color)),
             Span::raw(" | "),
             Span::styled(self.symbol.clone(), Style::default().fg(Color::White)),
-            Span::raw(" | "),
+        ];
+        if let Some(spread) = self.wide_spread {
+            spans.push(Span::styled(
+                format!(" ⚠ THIN {:.2}% spread", spread),
+                Style::default().fg(Color::Black).bg(Color::Yellow),
+            ));
+        }
+        if self.dropped_candles > 0 {
+            spans.push(Span::styled(
+                format!(" ⚠ {} bad candles dropped", self.dropped_candles),
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        if self.dropped_trades > 0 {
+            spans.push(Span::styled(
+                format!(" dropping {}/s", self.dropped_trades),
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        if self.idle {
+            spans.push(Span::styled(" idle", Style::default().fg(Color::DarkGray)));
+        }
+        spans.push(Span::raw(" | "));
+
+        if let Some(prompt) = &self.input_prompt {
+            spans.push(Span::styled(
+                format!("{}█", prompt),
+                Style::default().fg(Color::Yellow),
+            ));
+            frame.render_widget(Paragraph::new(Line::from(spans)), area);
+            return;
+        }
+
+        spans.extend([
             Span::styled("Q", Style::default().fg(Color::Yellow)),
             Span::raw(":Quit "),
             Span::styled("?", Style::default().fg(Color::Yellow)),
@@ -68,8 +117,18 @@ impl StatusBar {
             Span::styled("S", Style::default().fg(Color::Yellow)),
             Span::raw(":SMA "),
             Span::styled("R", Style::default().fg(Color::Yellow)),
-            Span::raw(":RSI"),
-        ];
+            Span::raw(":RSI "),
+            Span::styled("O", Style::default().fg(Color::Yellow)),
+            Span::raw(":Fill"),
+        ]);
+
+        if let Some(notice) = &self.notice {
+            spans.push(Span::raw(" | "));
+            spans.push(Span::styled(
+                notice.clone(),
+                Style::default().fg(Color::Cyan),
+            ));
+        }
 
         if let Some(err) = &self.last_error {
             spans.push(Span::raw(" | "));
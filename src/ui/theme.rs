@@ -0,0 +1,355 @@
+use crate::ui::format::{
+    format_price, symbol_price_decimals, VolumeFormat, DEFAULT_MAX_PRICE_DECIMALS,
+};
+use ratatui::buffer::Buffer;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders};
+use serde::{Deserialize, Serialize};
+
+/// Up/down color pairs. The alternatives avoid relying on red vs green so
+/// bullish/bearish stays readable with deuteranopia and protanopia.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Palette {
+    #[default]
+    Classic,
+    BlueOrange,
+    CyanMagenta,
+}
+
+/// How many colors the terminal can show. Detected once at startup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum ColorCapability {
+    Basic16,
+    Indexed256,
+    #[default]
+    TrueColor,
+}
+
+/// Env var that forces a capability (`16`, `256` or `truecolor`), mainly for
+/// testing the fallbacks on a capable terminal.
+pub const COLOR_OVERRIDE_ENV: &str = "TICKERTUI_COLORS";
+
+pub fn capability_from_env(
+    override_value: Option<&str>,
+    colorterm: Option<&str>,
+    term: Option<&str>,
+) -> ColorCapability {
+    match override_value
+        .map(|v| v.trim().to_ascii_lowercase())
+        .as_deref()
+    {
+        Some("16") => return ColorCapability::Basic16,
+        Some("256") => return ColorCapability::Indexed256,
+        Some("truecolor") | Some("24bit") => return ColorCapability::TrueColor,
+        _ => {}
+    }
+
+    let colorterm = colorterm.unwrap_or_default().to_ascii_lowercase();
+    if colorterm.contains("truecolor") || colorterm.contains("24bit") {
+        return ColorCapability::TrueColor;
+    }
+
+    let term = term.unwrap_or_default().to_ascii_lowercase();
+    if term.contains("256color") || term.contains("direct") {
+        ColorCapability::Indexed256
+    } else {
+        ColorCapability::Basic16
+    }
+}
+
+pub fn detect_color_capability() -> ColorCapability {
+    capability_from_env(
+        std::env::var(COLOR_OVERRIDE_ENV).ok().as_deref(),
+        std::env::var("COLORTERM").ok().as_deref(),
+        std::env::var("TERM").ok().as_deref(),
+    )
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    pub up: Color,
+    pub down: Color,
+    pub capability: ColorCapability,
+    pub volume_format: VolumeFormat,
+    /// Cap on the extra decimals given to prices below 1.
+    pub max_price_decimals: usize,
+    /// Decimals the charted symbol quotes in, when known; see
+    /// [`Chart::price_precision`](crate::ui::chart::Chart::price_precision).
+    pub price_precision: Option<usize>,
+    /// Panels keep only their titled top edge, giving the rest to data.
+    pub dense: bool,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::from_palette(Palette::default())
+    }
+}
+
+impl Theme {
+    pub fn new(palette: Palette, capability: ColorCapability) -> Self {
+        let base = Self::from_palette(palette);
+        Self {
+            up: adapt_color(base.up, capability),
+            down: adapt_color(base.down, capability),
+            capability,
+            volume_format: VolumeFormat::default(),
+            max_price_decimals: DEFAULT_MAX_PRICE_DECIMALS,
+            price_precision: None,
+            dense: false,
+        }
+    }
+
+    pub fn from_palette(palette: Palette) -> Self {
+        let (up, down) = match palette {
+            Palette::Classic => (Color::Green, Color::Red),
+            Palette::BlueOrange => (Color::Rgb(86, 180, 233), Color::Rgb(230, 159, 0)),
+            Palette::CyanMagenta => (Color::Cyan, Color::Magenta),
+        };
+        Self {
+            up,
+            down,
+            capability: ColorCapability::default(),
+            volume_format: VolumeFormat::default(),
+            max_price_decimals: DEFAULT_MAX_PRICE_DECIMALS,
+            price_precision: None,
+            dense: false,
+        }
+    }
+
+    /// Decimals for a price on the charted symbol.
+    pub fn price_decimals(&self, value: f64) -> usize {
+        symbol_price_decimals(value, self.price_precision, self.max_price_decimals)
+    }
+
+    /// A price on the charted symbol, at [`Theme::price_decimals`].
+    pub fn format_price(&self, value: f64) -> String {
+        format_price(value, self.price_decimals(value))
+    }
+
+    /// Borders for a panel: all four sides, or in dense mode only the top
+    /// edge, which still carries the title.
+    pub fn borders(&self) -> Borders {
+        if self.dense {
+            Borders::TOP
+        } else {
+            Borders::ALL
+        }
+    }
+
+    /// Height of a panel holding `rows` lines of content.
+    pub fn framed_height(&self, rows: u16) -> u16 {
+        rows + if self.dense { 1 } else { 2 }
+    }
+
+    /// Maps a computed color (e.g. a blended RGB) into what this terminal can
+    /// display.
+    pub fn adapt(&self, color: Color) -> Color {
+        adapt_color(color, self.capability)
+    }
+
+    pub fn direction(&self, is_up: bool) -> Color {
+        if is_up {
+            self.up
+        } else {
+            self.down
+        }
+    }
+}
+
+/// Approximate RGB for a terminal color, using xterm defaults for the named
+/// colors. Used when a color has to be blended or scaled.
+pub fn color_rgb(color: Color) -> (u8, u8, u8) {
+    match color {
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::Black | Color::Reset => (0, 0, 0),
+        Color::Red => (205, 0, 0),
+        Color::Green => (0, 205, 0),
+        Color::Yellow => (205, 205, 0),
+        Color::Blue => (0, 0, 238),
+        Color::Magenta => (205, 0, 205),
+        Color::Cyan => (0, 205, 205),
+        Color::Gray => (229, 229, 229),
+        Color::DarkGray => (127, 127, 127),
+        Color::LightRed => (255, 0, 0),
+        Color::LightGreen => (0, 255, 0),
+        Color::LightYellow => (255, 255, 0),
+        Color::LightBlue => (92, 92, 255),
+        Color::LightMagenta => (255, 0, 255),
+        Color::LightCyan => (0, 255, 255),
+        Color::White => (255, 255, 255),
+        Color::Indexed(_) => (127, 127, 127),
+    }
+}
+
+const BASE_COLORS: [Color; 16] = [
+    Color::Black,
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::Gray,
+    Color::DarkGray,
+    Color::LightRed,
+    Color::LightGreen,
+    Color::LightYellow,
+    Color::LightBlue,
+    Color::LightMagenta,
+    Color::LightCyan,
+    Color::White,
+];
+
+fn distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> u32 {
+    let d = |a: u8, b: u8| (i32::from(a) - i32::from(b)).unsigned_abs().pow(2);
+    d(r1, r2) + d(g1, g2) + d(b1, b2)
+}
+
+/// Downgrades RGB colors to the 6x6x6 xterm cube (256) or the nearest base
+/// color (16). Named colors are left alone since every terminal has them.
+pub fn adapt_color(color: Color, capability: ColorCapability) -> Color {
+    let Color::Rgb(r, g, b) = color else {
+        return color;
+    };
+
+    match capability {
+        ColorCapability::TrueColor => color,
+        ColorCapability::Indexed256 => {
+            let level = |c: u8| ((u16::from(c) * 5 + 127) / 255) as u8;
+            Color::Indexed(16 + 36 * level(r) + 6 * level(g) + level(b))
+        }
+        ColorCapability::Basic16 => BASE_COLORS
+            .into_iter()
+            .min_by_key(|base| distance(color_rgb(*base), (r, g, b)))
+            .unwrap_or(color),
+    }
+}
+
+/// How far quiet-hours dimming pulls a color toward its own gray level, and
+/// how much it darkens the result.
+const DIM_DESATURATE: f64 = 0.6;
+const DIM_BRIGHTNESS: f64 = 0.5;
+
+/// A muted, darker version of `color` for quiet hours.
+pub fn dim_color(color: Color) -> Color {
+    let (r, g, b) = color_rgb(color);
+    let gray = 0.299 * f64::from(r) + 0.587 * f64::from(g) + 0.114 * f64::from(b);
+    let mix = |channel: u8| {
+        let channel = f64::from(channel);
+        ((channel + (gray - channel) * DIM_DESATURATE) * DIM_BRIGHTNESS).round() as u8
+    };
+    Color::Rgb(mix(r), mix(g), mix(b))
+}
+
+/// Dims every cell of a rendered frame. Default foregrounds are treated as
+/// light gray text; default backgrounds stay untouched.
+pub fn dim_buffer(buffer: &mut Buffer, capability: ColorCapability) {
+    for cell in buffer.content.iter_mut() {
+        let fg = if cell.fg == Color::Reset {
+            Color::Gray
+        } else {
+            cell.fg
+        };
+        cell.fg = adapt_color(dim_color(fg), capability);
+        if cell.bg != Color::Reset {
+            cell.bg = adapt_color(dim_color(cell.bg), capability);
+        }
+    }
+}
+
+/// Flags a panel whose feed has stopped delivering with a red border and a
+/// `STALE` title; returns `block` unchanged otherwise.
+pub fn mark_stale(block: Block<'_>, stale: bool) -> Block<'_> {
+    if !stale {
+        return block;
+    }
+    block.border_style(Style::default().fg(Color::Red)).title(
+        Line::styled(
+            " STALE ",
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        )
+        .right_aligned(),
+    )
+}
+
+/// Scales a color's brightness by `factor` (clamped to `0.0..=1.0`).
+pub fn scale_color(color: Color, factor: f64) -> Color {
+    let (r, g, b) = color_rgb(color);
+    let factor = factor.clamp(0.0, 1.0);
+    let scale = |channel: u8| (f64::from(channel) * factor).round() as u8;
+    Color::Rgb(scale(r), scale(g), scale(b))
+}
+
+/// Mixes `share` (clamped to `0.0..=1.0`) of `toward` into `color`.
+pub fn blend_color(color: Color, toward: Color, share: f64) -> Color {
+    let (r, g, b) = color_rgb(color);
+    let (to_r, to_g, to_b) = color_rgb(toward);
+    let share = share.clamp(0.0, 1.0);
+    let mix = |from: u8, to: u8| {
+        (f64::from(from) + (f64::from(to) - f64::from(from)) * share).round() as u8
+    };
+    Color::Rgb(mix(r, to_r), mix(g, to_g), mix(b, to_b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn capability_prefers_override_then_colorterm_then_term() {
+        assert_eq!(
+            capability_from_env(Some("16"), Some("truecolor"), None),
+            ColorCapability::Basic16
+        );
+        assert_eq!(
+            capability_from_env(None, Some("truecolor"), Some("xterm")),
+            ColorCapability::TrueColor
+        );
+        assert_eq!(
+            capability_from_env(None, None, Some("xterm-256color")),
+            ColorCapability::Indexed256
+        );
+        assert_eq!(
+            capability_from_env(None, None, Some("linux")),
+            ColorCapability::Basic16
+        );
+    }
+
+    #[test]
+    fn sixteen_color_theme_keeps_up_and_down_distinct() {
+        let theme = Theme::new(Palette::BlueOrange, ColorCapability::Basic16);
+        assert_eq!(theme.up, Color::LightBlue);
+        assert_eq!(theme.down, Color::Yellow);
+        assert_ne!(theme.up, theme.down);
+    }
+
+    #[test]
+    fn dimming_darkens_and_mutes_colors() {
+        assert_eq!(dim_color(Color::White), Color::Rgb(128, 128, 128));
+        let Color::Rgb(r, g, b) = dim_color(Color::Rgb(0, 205, 0)) else {
+            unreachable!();
+        };
+        assert!(g < 205 && g > r && r == b && r > 0);
+
+        let mut buffer = Buffer::empty(ratatui::layout::Rect::new(0, 0, 2, 1));
+        buffer[(1, 0)].set_bg(Color::Red);
+        dim_buffer(&mut buffer, ColorCapability::TrueColor);
+        assert_eq!(buffer[(0, 0)].fg, dim_color(Color::Gray));
+        assert_eq!(buffer[(0, 0)].bg, Color::Reset);
+        assert_eq!(buffer[(1, 0)].bg, dim_color(Color::Red));
+    }
+
+    #[test]
+    fn indexed_fallback_maps_into_color_cube() {
+        assert_eq!(
+            adapt_color(Color::Rgb(255, 0, 0), ColorCapability::Indexed256),
+            Color::Indexed(196)
+        );
+        assert_eq!(
+            adapt_color(Color::Green, ColorCapability::Basic16),
+            Color::Green
+        );
+    }
+}
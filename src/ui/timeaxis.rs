@@ -0,0 +1,183 @@
+use crate::data::Candle;
+use serde::{Deserialize, Serialize};
+
+/// Which end of a candle its displayed timestamp refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum CandleTimeBasis {
+    #[default]
+    Open,
+    /// The moment the candle closes, i.e. the next candle's open.
+    Close,
+}
+
+impl CandleTimeBasis {
+    pub fn timestamp(self, candle: &Candle) -> u64 {
+        match self {
+            CandleTimeBasis::Open => candle.open_time,
+            // Binance's `close_time` is the last millisecond of the candle.
+            CandleTimeBasis::Close => candle.close_time.saturating_add(1),
+        }
+    }
+}
+
+/// Calendar fields for a UTC timestamp, derived without a date library.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UtcParts {
+    pub year: i64,
+    pub month: u32,
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+}
+
+/// Converts Unix milliseconds to UTC calendar fields using Howard Hinnant's
+/// days-to-civil algorithm.
+pub fn utc_parts(timestamp_ms: u64) -> UtcParts {
+    let secs = (timestamp_ms / 1000) as i64;
+    let days = secs.div_euclid(86_400);
+    let secs_of_day = secs.rem_euclid(86_400);
+
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    UtcParts {
+        year,
+        month,
+        day,
+        hour: (secs_of_day / 3_600) as u32,
+        minute: (secs_of_day % 3_600 / 60) as u32,
+        second: (secs_of_day % 60) as u32,
+    }
+}
+
+/// Parses a UTC `YYYY-MM-DD` or `YYYY-MM-DD HH:MM` into Unix milliseconds,
+/// the inverse of [`utc_parts`] via Hinnant's days-from-civil.
+pub fn parse_utc(text: &str) -> Result<u64, String> {
+    let invalid = || format!("expected YYYY-MM-DD [HH:MM], got {:?}", text.trim());
+    let (date, time) = match text.trim().split_once(' ') {
+        Some((date, time)) => (date, Some(time.trim())),
+        None => (text.trim(), None),
+    };
+
+    let mut fields = date.split('-').map(str::parse::<i64>);
+    let (Some(Ok(year)), Some(Ok(month)), Some(Ok(day)), None) =
+        (fields.next(), fields.next(), fields.next(), fields.next())
+    else {
+        return Err(invalid());
+    };
+    let (hour, minute) = match time {
+        Some(time) => {
+            let (hour, minute) = time.split_once(':').ok_or_else(invalid)?;
+            (
+                hour.parse::<i64>().map_err(|_| invalid())?,
+                minute.parse::<i64>().map_err(|_| invalid())?,
+            )
+        }
+        None => (0, 0),
+    };
+    let leap = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+    let month_days = [
+        31,
+        if leap { 29 } else { 28 },
+        31,
+        30,
+        31,
+        30,
+        31,
+        31,
+        30,
+        31,
+        30,
+        31,
+    ];
+    if !(1970..=9999).contains(&year)
+        || !(1..=12).contains(&month)
+        || !(1..=month_days[month as usize - 1]).contains(&day)
+        || !(0..24).contains(&hour)
+        || !(0..60).contains(&minute)
+    {
+        return Err(invalid());
+    }
+
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y.rem_euclid(400);
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146_097 + doe - 719_468;
+    Ok(((days * 86_400 + hour * 3_600 + minute * 60) * 1000) as u64)
+}
+
+/// Formats a candle time for the axis, picking the granularity from the
+/// Binance interval: `HH:MM` intraday, `MM-DD` for daily/weekly, `YYYY-MM`
+/// for monthly candles.
+pub fn format_time_label(timestamp_ms: u64, interval: &str) -> String {
+    let parts = utc_parts(timestamp_ms);
+    match interval.chars().last() {
+        Some('d') | Some('w') => format!("{:02}-{:02}", parts.month, parts.day),
+        Some('M') => format!("{}-{:02}", parts.year, parts.month),
+        _ => format!("{:02}:{:02}", parts.hour, parts.minute),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn utc_parts_handles_epoch_and_leap_day() {
+        assert_eq!(
+            utc_parts(0),
+            UtcParts {
+                year: 1970,
+                month: 1,
+                day: 1,
+                hour: 0,
+                minute: 0,
+                second: 0,
+            }
+        );
+        // 2024-02-29T13:45:30Z
+        let parts = utc_parts(1_709_214_330_000);
+        assert_eq!((parts.year, parts.month, parts.day), (2024, 2, 29));
+        assert_eq!((parts.hour, parts.minute, parts.second), (13, 45, 30));
+    }
+
+    #[test]
+    fn parse_utc_inverts_utc_parts() {
+        assert_eq!(parse_utc("1970-01-01"), Ok(0));
+        let ms = parse_utc(" 2024-02-29 13:45 ").unwrap();
+        assert_eq!(ms, 1_709_214_300_000);
+        let parts = utc_parts(ms);
+        assert_eq!((parts.year, parts.month, parts.day), (2024, 2, 29));
+        assert_eq!((parts.hour, parts.minute), (13, 45));
+
+        for bad in [
+            "2023-02-29",
+            "2024-13-01",
+            "2024-01-01 24:00",
+            "yesterday",
+            "2024-1",
+        ] {
+            assert!(parse_utc(bad).is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn labels_adapt_to_interval() {
+        let ts = 1_709_214_330_000;
+        assert_eq!(format_time_label(ts, "5m"), "13:45");
+        assert_eq!(format_time_label(ts, "4h"), "13:45");
+        assert_eq!(format_time_label(ts, "1d"), "02-29");
+        assert_eq!(format_time_label(ts, "1M"), "2024-02");
+    }
+}
@@ -1,8 +1,9 @@
+use crate::ui::Theme;
 use ratatui::{
     layout::Rect,
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Paragraph},
     Frame,
 };
 use serde::{Deserialize, Serialize};
@@ -104,10 +105,10 @@ impl TimeframeSelector {
         };
     }
 
-    pub fn render(&self, frame: &mut Frame, area: Rect) {
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
         let block = Block::default()
             .title("Timeframe")
-            .borders(Borders::ALL)
+            .borders(theme.borders())
             .border_style(Style::default().fg(Color::Cyan));
         let inner = block.inner(area);
         frame.render_widget(block, area);
@@ -141,3 +142,23 @@ impl TimeframeSelector {
         frame.render_widget(para, inner);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::stream::validate_interval;
+    use crate::ui::mtf::MTF_INTERVALS;
+
+    #[test]
+    fn offers_only_supported_intervals() {
+        for timeframe in TimeframeSelector::new().timeframes {
+            assert_eq!(
+                validate_interval(timeframe.binance_interval()),
+                Ok(timeframe.binance_interval())
+            );
+        }
+        for interval in MTF_INTERVALS {
+            assert!(validate_interval(interval).is_ok(), "{interval}");
+        }
+    }
+}
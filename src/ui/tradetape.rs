@@ -1,16 +1,228 @@
 use crate::data::Trade;
+use crate::ui::format::format_volume;
+use crate::ui::theme::mark_stale;
+use crate::ui::timeaxis::utc_parts;
+use crate::ui::Theme;
 use ratatui::{
     layout::Rect,
     style::{Color, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem},
+    widgets::{Block, List, ListItem},
     Frame,
 };
+use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const DEFAULT_TAPE_FORMAT: &str = "{arrow} {price} x {size}";
+/// Seconds of trade rate shown in the tape header.
+pub const RATE_BUCKETS: usize = 16;
+const SPARK_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Trades per second over the last [`RATE_BUCKETS`] seconds, by local clock.
+#[derive(Debug, Clone, Default)]
+pub struct TradeRate {
+    /// `(unix second, trades)`, oldest first; seconds without trades are
+    /// simply absent.
+    buckets: VecDeque<(u64, u32)>,
+    /// First second counted since the last reset; earlier seconds are
+    /// unknown rather than quiet.
+    since: Option<u64>,
+}
+
+impl TradeRate {
+    pub fn record(&mut self, now_secs: u64) {
+        self.since.get_or_insert(now_secs);
+        match self.buckets.back_mut() {
+            Some((second, count)) if *second == now_secs => *count += 1,
+            _ => self.buckets.push_back((now_secs, 1)),
+        }
+        while self
+            .buckets
+            .front()
+            .is_some_and(|(second, _)| second + RATE_BUCKETS as u64 <= now_secs)
+        {
+            self.buckets.pop_front();
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.buckets.clear();
+        self.since = None;
+    }
+
+    /// Counts for the `RATE_BUCKETS` seconds ending at `now_secs`, oldest
+    /// first; `None` for seconds before counting started.
+    pub fn series(&self, now_secs: u64) -> Vec<Option<u32>> {
+        let first = (now_secs + 1).saturating_sub(RATE_BUCKETS as u64);
+        (first..=now_secs)
+            .map(|second| {
+                let since = self.since?;
+                (second >= since).then(|| {
+                    self.buckets
+                        .iter()
+                        .find(|(bucket, _)| *bucket == second)
+                        .map_or(0, |(_, count)| *count)
+                })
+            })
+            .collect()
+    }
+}
+
+/// Block sparkline of `series`, scaled to its busiest second; unknown seconds
+/// are left blank.
+pub fn sparkline(series: &[Option<u32>]) -> String {
+    let peak = series.iter().flatten().copied().max().unwrap_or(0).max(1);
+    series
+        .iter()
+        .map(|count| match count {
+            None => ' ',
+            Some(count) => {
+                let level = (*count as usize * (SPARK_LEVELS.len() - 1)).div_ceil(peak as usize);
+                SPARK_LEVELS[level]
+            }
+        })
+        .collect()
+}
+
+fn unix_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
+}
+
+/// How a trade is judged a buy or a sell for its color and arrow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TapeDirection {
+    /// By the aggressor: a taker buying lifts the ask, a taker selling hits
+    /// the bid.
+    #[default]
+    Aggressor,
+    /// By the tick rule: up or down from the previous trade's price, falling
+    /// back to the aggressor when the price is unchanged.
+    TickRule,
+}
+
+impl TapeDirection {
+    /// Whether `trade` counts as a buy, given the trade before it.
+    pub fn is_buy(self, trade: &Trade, previous: Option<&Trade>) -> bool {
+        let aggressor_buy = !trade.is_buyer_maker;
+        match (self, previous) {
+            (TapeDirection::TickRule, Some(previous)) if trade.price != previous.price => {
+                trade.price > previous.price
+            }
+            _ => aggressor_buy,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TapeField {
+    Text(String),
+    Time,
+    Price,
+    Size,
+    Side,
+    Arrow,
+}
+
+/// Parsed tape row layout: literal text mixed with `{time}`, `{price}`,
+/// `{size}`, `{side}` and `{arrow}` placeholders.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TapeFormat {
+    spec: String,
+    fields: Vec<TapeField>,
+}
+
+impl Default for TapeFormat {
+    fn default() -> Self {
+        Self::parse(DEFAULT_TAPE_FORMAT).expect("default tape format parses")
+    }
+}
+
+impl TapeFormat {
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut fields = Vec::new();
+        let mut rest = spec;
+        while !rest.is_empty() {
+            let Some(open) = rest.find(['{', '}']) else {
+                fields.push(TapeField::Text(rest.to_string()));
+                break;
+            };
+            if open > 0 {
+                fields.push(TapeField::Text(rest[..open].to_string()));
+            }
+            if rest[open..].starts_with('}') {
+                return Err("unmatched '}'".to_string());
+            }
+            let Some(close) = rest[open..].find('}') else {
+                return Err("unclosed '{'".to_string());
+            };
+            let name = &rest[open + 1..open + close];
+            fields.push(match name {
+                "time" => TapeField::Time,
+                "price" => TapeField::Price,
+                "size" => TapeField::Size,
+                "side" => TapeField::Side,
+                "arrow" => TapeField::Arrow,
+                _ => return Err(format!("unknown field {{{name}}}")),
+            });
+            rest = &rest[open + close + 1..];
+        }
+
+        if !fields
+            .iter()
+            .any(|field| !matches!(field, TapeField::Text(_)))
+        {
+            return Err("no fields".to_string());
+        }
+        Ok(Self {
+            spec: spec.to_string(),
+            fields,
+        })
+    }
+
+    /// The format string this was parsed from, for saving back to config.
+    pub fn spec(&self) -> &str {
+        &self.spec
+    }
+
+    fn row(&self, trade: &Trade, arrow: &str, theme: &Theme) -> String {
+        let mut row = String::new();
+        for field in &self.fields {
+            match field {
+                TapeField::Text(text) => row.push_str(text),
+                TapeField::Time => {
+                    let parts = utc_parts(trade.timestamp);
+                    row.push_str(&format!(
+                        "{:02}:{:02}:{:02}",
+                        parts.hour, parts.minute, parts.second
+                    ));
+                }
+                TapeField::Price => {
+                    row.push_str(&format!("{:>10}", theme.format_price(trade.price)))
+                }
+                TapeField::Size => row.push_str(&format!(
+                    "{:>10}",
+                    format_volume(trade.quantity, 4, theme.volume_format)
+                )),
+                TapeField::Side => row.push_str(if trade.is_buyer_maker { "SELL" } else { "BUY " }),
+                TapeField::Arrow => row.push_str(arrow),
+            }
+        }
+        row
+    }
+}
 
 pub struct TradeTape {
     pub trades: VecDeque<Trade>,
     pub max_trades: usize,
+    /// The trade feed has gone quiet for longer than expected.
+    pub stale: bool,
+    pub format: TapeFormat,
+    pub direction: TapeDirection,
+    pub rate: TradeRate,
 }
 
 impl TradeTape {
@@ -18,21 +230,44 @@ impl TradeTape {
         Self {
             trades: VecDeque::with_capacity(100),
             max_trades: 50,
+            stale: false,
+            format: TapeFormat::default(),
+            direction: TapeDirection::default(),
+            rate: TradeRate::default(),
         }
     }
 
     pub fn add_trade(&mut self, trade: Trade) {
+        self.rate.record(unix_secs());
         self.trades.push_back(trade);
         if self.trades.len() > self.max_trades {
             self.trades.pop_front();
         }
     }
 
-    pub fn render(&self, frame: &mut Frame, area: Rect) {
-        let block = Block::default()
-            .title("Trade Tape")
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Blue));
+    /// Clears the tape and its rate history, e.g. for a new symbol.
+    pub fn reset(&mut self) {
+        self.trades.clear();
+        self.rate.reset();
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let series = self.rate.series(unix_secs());
+        let latest = series.last().copied().flatten().unwrap_or(0);
+        let block = mark_stale(
+            Block::default()
+                .title("Trade Tape")
+                .title(
+                    Line::styled(
+                        format!(" {}/s {} ", latest, sparkline(&series)),
+                        Style::default().fg(Color::Cyan),
+                    )
+                    .right_aligned(),
+                )
+                .borders(theme.borders())
+                .border_style(Style::default().fg(Color::Blue)),
+            self.stale,
+        );
         let inner = block.inner(area);
         frame.render_widget(block, area);
 
@@ -46,30 +281,13 @@ impl TradeTape {
             .iter()
             .enumerate()
             .map(|(idx, trade)| {
-                let (color, direction) = if idx < trades_vec.len() - 1 {
-                    let prev_trade = trades_vec[idx + 1];
-                    if trade.price > prev_trade.price {
-                        (Color::Green, "↑")
-                    } else if trade.price < prev_trade.price {
-                        (Color::Red, "↓")
-                    } else {
-                        if !trade.is_buyer_maker {
-                            (Color::Green, "↑")
-                        } else {
-                            (Color::Red, "↓")
-                        }
-                    }
+                let previous = trades_vec.get(idx + 1).copied();
+                let (color, direction) = if self.direction.is_buy(trade, previous) {
+                    (theme.up, "↑")
                 } else {
-                    if !trade.is_buyer_maker {
-                        (Color::Green, "↑")
-                    } else {
-                        (Color::Red, "↓")
-                    }
+                    (theme.down, "↓")
                 };
-                let text = format!(
-                    "{} {:>10.2} x {:>10.4}",
-                    direction, trade.price, trade.quantity
-                );
+                let text = self.format.row(trade, direction, theme);
                 ListItem::new(Line::from(Span::styled(text, Style::default().fg(color))))
             })
             .collect();
@@ -78,3 +296,79 @@ impl TradeTape {
         frame.render_widget(list, inner);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade() -> Trade {
+        Trade {
+            price: 64_250.5,
+            quantity: 0.25,
+            is_buyer_maker: true,
+            timestamp: 1_709_214_330_000,
+            first_trade_id: 1,
+            last_trade_id: 1,
+        }
+    }
+
+    #[test]
+    fn trade_rate_pads_cold_start_and_drops_old_seconds() {
+        let mut rate = TradeRate::default();
+        assert!(rate.series(100).iter().all(Option::is_none));
+
+        rate.record(100);
+        rate.record(100);
+        rate.record(102);
+        let series = rate.series(103);
+        assert_eq!(series.len(), RATE_BUCKETS);
+        assert_eq!(
+            &series[RATE_BUCKETS - 4..],
+            &[Some(2), Some(0), Some(1), Some(0)]
+        );
+        assert!(series[..RATE_BUCKETS - 4].iter().all(Option::is_none));
+        assert_eq!(sparkline(&series[RATE_BUCKETS - 4..]), "█▁▅▁");
+
+        rate.record(200);
+        assert_eq!(rate.buckets.len(), 1);
+        rate.reset();
+        assert!(rate.series(200).iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn direction_modes_classify_trades() {
+        let sell = trade();
+        let previous = Trade {
+            price: 64_000.0,
+            ..trade()
+        };
+
+        // A taker sell at a higher price than the last trade.
+        assert!(!TapeDirection::Aggressor.is_buy(&sell, Some(&previous)));
+        assert!(TapeDirection::TickRule.is_buy(&sell, Some(&previous)));
+        // Unchanged or unknown previous prices fall back to the aggressor.
+        assert!(!TapeDirection::TickRule.is_buy(&sell, Some(&trade())));
+        assert!(!TapeDirection::TickRule.is_buy(&sell, None));
+    }
+
+    #[test]
+    fn default_format_matches_classic_row() {
+        let row = TapeFormat::default().row(&trade(), "↓", &Theme::default());
+        assert_eq!(row, format!("↓ {:>10} x {:>10}", "64,250.50", "0.25"));
+    }
+
+    #[test]
+    fn custom_format_reorders_and_drops_fields() {
+        let format = TapeFormat::parse("{time} {side}{price}").unwrap();
+        let row = format.row(&trade(), "↓", &Theme::default());
+        assert_eq!(row, format!("13:45:30 SELL{:>10}", "64,250.50"));
+    }
+
+    #[test]
+    fn invalid_formats_are_rejected() {
+        assert!(TapeFormat::parse("{price").is_err());
+        assert!(TapeFormat::parse("price}").is_err());
+        assert!(TapeFormat::parse("{qty}").is_err());
+        assert!(TapeFormat::parse("just text").is_err());
+    }
+}
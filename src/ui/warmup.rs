@@ -0,0 +1,144 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use std::time::{Duration, Instant};
+
+/// How long the startup panel waits for slow feeds before getting out of
+/// the way; a quiet market may not trade for a while.
+const WARMUP_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    Pending,
+    Done,
+    Failed,
+}
+
+impl Step {
+    fn mark(self) -> (&'static str, Color) {
+        match self {
+            Step::Pending => ("…", Color::Yellow),
+            Step::Done => ("✓", Color::Green),
+            Step::Failed => ("✗", Color::Red),
+        }
+    }
+}
+
+/// Startup progress: the first candle fetch, then each market stream until
+/// it delivers data.
+pub struct Warmup {
+    symbol: String,
+    started: Instant,
+    candles: Step,
+    kline: Step,
+    orderbook: Step,
+    trades: Step,
+}
+
+impl Warmup {
+    pub fn new(symbol: &str, now: Instant) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            started: now,
+            candles: Step::Pending,
+            kline: Step::Pending,
+            orderbook: Step::Pending,
+            trades: Step::Pending,
+        }
+    }
+
+    pub fn set_candles(&mut self, loaded: bool) {
+        self.candles = if loaded { Step::Done } else { Step::Failed };
+    }
+
+    /// Marks the streams that have gone live. A stream never goes back to
+    /// pending once it has delivered.
+    pub fn set_streams(&mut self, kline: bool, orderbook: bool, trades: bool) {
+        for (step, live) in [
+            (&mut self.kline, kline),
+            (&mut self.orderbook, orderbook),
+            (&mut self.trades, trades),
+        ] {
+            if live {
+                *step = Step::Done;
+            }
+        }
+    }
+
+    /// Everything has reported in, or the wait ran out.
+    pub fn is_done(&self, now: Instant) -> bool {
+        let settled = [self.candles, self.kline, self.orderbook, self.trades]
+            .iter()
+            .all(|step| *step != Step::Pending);
+        settled || now.saturating_duration_since(self.started) >= WARMUP_TIMEOUT
+    }
+
+    /// Draws the progress box in the middle of the screen.
+    pub fn render(&self, frame: &mut Frame) {
+        let rows = [
+            ("Candles", self.candles),
+            ("Kline stream", self.kline),
+            ("Order book", self.orderbook),
+            ("Trades", self.trades),
+        ];
+        let area = frame.area();
+        let width = 30.min(area.width);
+        let height = (rows.len() as u16 + 2).min(area.height);
+        let popup = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+
+        let lines: Vec<Line> = rows
+            .iter()
+            .map(|(label, step)| {
+                let (mark, color) = step.mark();
+                Line::from(vec![
+                    Span::styled(format!(" {mark} "), Style::default().fg(color)),
+                    Span::styled(*label, Style::default().fg(Color::White)),
+                ])
+            })
+            .collect();
+        let block = Block::default()
+            .title(Span::styled(
+                format!("Loading {}…", self.symbol),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow));
+
+        frame.render_widget(Clear, popup);
+        frame.render_widget(Paragraph::new(lines).block(block), popup);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finishes_once_every_step_reports_or_time_runs_out() {
+        let start = Instant::now();
+        let mut warmup = Warmup::new("BTCUSDT", start);
+        assert!(!warmup.is_done(start));
+
+        warmup.set_candles(true);
+        warmup.set_streams(true, true, false);
+        assert!(!warmup.is_done(start));
+        // A later update doesn't undo a stream that already delivered.
+        warmup.set_streams(false, false, false);
+        assert!(!warmup.is_done(start + Duration::from_secs(9)));
+        assert!(warmup.is_done(start + WARMUP_TIMEOUT));
+
+        warmup.set_streams(false, false, true);
+        assert!(warmup.is_done(start));
+    }
+}
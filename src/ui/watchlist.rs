@@ -0,0 +1,199 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A named group of watchlist symbols, e.g. "Majors" or "Alts".
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WatchlistSection {
+    pub name: String,
+    pub symbols: Vec<String>,
+    #[serde(default)]
+    pub collapsed: bool,
+}
+
+/// One rendered watchlist line. Symbol rows carry their index into the flat
+/// watchlist, which stays the order used by the price streams.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchRow {
+    Header { section: usize },
+    Symbol(usize),
+}
+
+/// Flat watchlist order derived from the sections, first occurrence wins.
+pub fn flatten_sections(sections: &[WatchlistSection]) -> Vec<String> {
+    let mut symbols: Vec<String> = Vec::new();
+    for symbol in sections.iter().flat_map(|s| &s.symbols) {
+        if !symbols.contains(symbol) {
+            symbols.push(symbol.clone());
+        }
+    }
+    symbols
+}
+
+/// How the watchlist is ordered when the app starts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum WatchlistOrder {
+    /// As listed in the config.
+    #[default]
+    Config,
+    /// Busiest first by 24h quote volume, fetched once at startup.
+    QuoteVolume,
+}
+
+/// Orders `symbols` by descending `volumes`; symbols without a volume keep
+/// their relative order after the rest.
+pub fn sort_by_volume(symbols: &mut [String], volumes: &HashMap<String, f64>) {
+    symbols.sort_by(|a, b| {
+        let volume = |symbol: &String| volumes.get(symbol).copied().unwrap_or(f64::NEG_INFINITY);
+        volume(b).total_cmp(&volume(a))
+    });
+}
+
+/// Index of the symbol with the largest absolute change, ignoring symbols
+/// without one; `None` when none has a change.
+pub fn top_mover(symbols: &[String], changes: &HashMap<String, f64>) -> Option<usize> {
+    symbols
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, symbol)| Some((idx, changes.get(symbol)?.abs())))
+        .filter(|(_, change)| change.is_finite())
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(idx, _)| idx)
+}
+
+pub fn section_of(sections: &[WatchlistSection], symbol: &str) -> Option<usize> {
+    sections
+        .iter()
+        .position(|s| s.symbols.iter().any(|sym| sym == symbol))
+}
+
+pub fn watchlist_rows(watchlist: &[String], sections: &[WatchlistSection]) -> Vec<WatchRow> {
+    if sections.is_empty() {
+        return (0..watchlist.len()).map(WatchRow::Symbol).collect();
+    }
+
+    let mut rows = Vec::new();
+    for (section_idx, section) in sections.iter().enumerate() {
+        rows.push(WatchRow::Header {
+            section: section_idx,
+        });
+        if section.collapsed {
+            continue;
+        }
+        for (idx, symbol) in watchlist.iter().enumerate() {
+            if section_of(sections, symbol) == Some(section_idx) {
+                rows.push(WatchRow::Symbol(idx));
+            }
+        }
+    }
+    rows
+}
+
+/// Moves the selection by `delta` visible entries. Expanded headers are
+/// skipped; a collapsed section counts as one entry standing in for its
+/// first symbol so it can still be reached and expanded.
+pub fn step_selection(
+    watchlist: &[String],
+    sections: &[WatchlistSection],
+    selected: usize,
+    delta: isize,
+) -> usize {
+    let first_symbol_of = |section: usize| {
+        watchlist
+            .iter()
+            .position(|symbol| section_of(sections, symbol) == Some(section))
+    };
+
+    let entries: Vec<(usize, Option<usize>)> = watchlist_rows(watchlist, sections)
+        .into_iter()
+        .filter_map(|row| match row {
+            WatchRow::Symbol(idx) => Some((idx, None)),
+            WatchRow::Header { section } if sections[section].collapsed => {
+                first_symbol_of(section).map(|idx| (idx, Some(section)))
+            }
+            WatchRow::Header { .. } => None,
+        })
+        .collect();
+
+    let selected_section = watchlist
+        .get(selected)
+        .and_then(|symbol| section_of(sections, symbol));
+    let Some(position) = entries.iter().position(|(idx, section)| match section {
+        Some(section) => selected_section == Some(*section),
+        None => *idx == selected,
+    }) else {
+        return entries.first().map_or(selected, |(idx, _)| *idx);
+    };
+
+    let target = position.saturating_add_signed(delta).min(entries.len() - 1);
+    entries[target].0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn section(name: &str, symbols: &[&str], collapsed: bool) -> WatchlistSection {
+        WatchlistSection {
+            name: name.to_string(),
+            symbols: symbols.iter().map(|s| s.to_string()).collect(),
+            collapsed,
+        }
+    }
+
+    #[test]
+    fn volume_sort_puts_busiest_first_and_unknowns_last() {
+        let mut symbols: Vec<String> = ["ADAUSDT", "NEWUSDT", "BTCUSDT", "ETHUSDT", "OLDUSDT"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let volumes = HashMap::from([
+            ("BTCUSDT".to_string(), 900.0),
+            ("ETHUSDT".to_string(), 400.0),
+            ("ADAUSDT".to_string(), 10.0),
+        ]);
+        sort_by_volume(&mut symbols, &volumes);
+        assert_eq!(
+            symbols,
+            vec!["BTCUSDT", "ETHUSDT", "ADAUSDT", "NEWUSDT", "OLDUSDT"]
+        );
+    }
+
+    #[test]
+    fn top_mover_ranks_by_absolute_change() {
+        let symbols: Vec<String> = ["BTCUSDT", "ETHUSDT", "NEWUSDT"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let changes = HashMap::from([("BTCUSDT".to_string(), 3.5), ("ETHUSDT".to_string(), -7.25)]);
+        assert_eq!(top_mover(&symbols, &changes), Some(1));
+        assert_eq!(top_mover(&symbols, &HashMap::new()), None);
+    }
+
+    #[test]
+    fn navigation_skips_headers_and_stops_on_collapsed_sections() {
+        let mut sections = vec![
+            section("Majors", &["BTCUSDT", "ETHUSDT"], false),
+            section("Alts", &["SOLUSDT", "ADAUSDT"], true),
+            section("Memes", &["DOGEUSDT", "BTCUSDT"], false),
+        ];
+        let watchlist = flatten_sections(&sections);
+        assert_eq!(watchlist.len(), 5);
+
+        assert_eq!(step_selection(&watchlist, &sections, 1, 1), 2);
+        assert_eq!(step_selection(&watchlist, &sections, 2, 1), 4);
+        assert_eq!(step_selection(&watchlist, &sections, 4, -1), 2);
+        assert_eq!(step_selection(&watchlist, &sections, 4, 1), 4);
+        assert_eq!(step_selection(&watchlist, &sections, 0, -1), 0);
+
+        sections[1].collapsed = false;
+        assert_eq!(step_selection(&watchlist, &sections, 2, 1), 3);
+        assert_eq!(
+            watchlist_rows(&watchlist, &sections)[..3],
+            [
+                WatchRow::Header { section: 0 },
+                WatchRow::Symbol(0),
+                WatchRow::Symbol(1)
+            ]
+        );
+    }
+}